@@ -2,7 +2,7 @@ use actions_core::error::ActionError;
 use actix_web::body::BoxBody;
 use actix_web::http::StatusCode;
 use actix_web::{HttpResponse, ResponseError};
-use common::error::ProblemDetails;
+use common::error::{InvalidParam, ProblemDetails};
 use std::fmt;
 
 #[derive(Debug)]
@@ -15,6 +15,10 @@ impl ApiError {
     pub fn new(status: StatusCode, body: ProblemDetails) -> Self {
         Self { status, body }
     }
+
+    pub fn into_body(self) -> ProblemDetails {
+        self.body
+    }
 }
 
 impl fmt::Display for ApiError {
@@ -46,11 +50,80 @@ impl From<ActionError> for ApiError {
                 ProblemDetails::new("/problems/field-not-found", "Field Not Found", 400)
                     .with_detail(value.to_string()),
             ),
+            ActionError::ActionNotFound(_) => ApiError::new(
+                StatusCode::NOT_FOUND,
+                ProblemDetails::new("/problems/action-not-found", "Action Not Found", 404)
+                    .with_detail(value.to_string()),
+            ),
             ActionError::InvalidAction(_) => ApiError::new(
                 StatusCode::BAD_REQUEST,
                 ProblemDetails::new("/problems/invalid-action", "Invalid Action", 400)
                     .with_detail(value.to_string()),
             ),
+            ActionError::InvalidFieldMapping(ref violations) => {
+                let invalid_params = violations
+                    .iter()
+                    .map(|violation| InvalidParam::new(&violation.role, violation.to_string()))
+                    .collect();
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    ProblemDetails::new(
+                        "/problems/invalid-field-mapping",
+                        "Invalid Field Mapping",
+                        400,
+                    )
+                    .with_detail(value.to_string())
+                    .with_invalid_params(invalid_params),
+                )
+            }
+            ActionError::CalculationCycle(_) => ApiError::new(
+                StatusCode::CONFLICT,
+                ProblemDetails::new("/problems/calculation-cycle", "Calculation Cycle", 409)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::UnknownGameSystem(_) => ApiError::new(
+                StatusCode::NOT_FOUND,
+                ProblemDetails::new("/problems/unknown-game-system", "Unknown Game System", 404)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::UnsupportedAction { .. } => ApiError::new(
+                StatusCode::BAD_REQUEST,
+                ProblemDetails::new("/problems/unsupported-action", "Unsupported Action", 400)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::GameSystemMismatch { .. } => ApiError::new(
+                StatusCode::CONFLICT,
+                ProblemDetails::new(
+                    "/problems/game-system-mismatch",
+                    "Game System Mismatch",
+                    409,
+                )
+                .with_detail(value.to_string()),
+            ),
+            ActionError::TemplateNotFound(_) => ApiError::new(
+                StatusCode::NOT_FOUND,
+                ProblemDetails::new("/problems/template-not-found", "Template Not Found", 404)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::InvalidTemplate(_) => ApiError::new(
+                StatusCode::BAD_REQUEST,
+                ProblemDetails::new("/problems/invalid-template", "Invalid Template", 400)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::TemplateMismatch(_) => ApiError::new(
+                StatusCode::CONFLICT,
+                ProblemDetails::new("/problems/template-mismatch", "Template Mismatch", 409)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::PreviewUnsupported(_) => ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ProblemDetails::new("/problems/preview-unsupported", "Preview Unsupported", 422)
+                    .with_detail(value.to_string()),
+            ),
+            ActionError::InvalidActionDefinition(_) => ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ProblemDetails::internal(),
+            ),
             ActionError::LoadPdfError => ApiError::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 ProblemDetails::internal(),
@@ -1,9 +1,21 @@
 use crate::error::ApiError;
-use actions_core::action::{ActionTypeMetadata, CalculationAction, FieldRoleMetadata};
-use actions_core::ports::driving::ActionService;
-use actix_web::{HttpResponse, get, put, web};
-use serde::Serialize;
-use utoipa::ToSchema;
+use actions_core::action::{
+    ActionTypeMetadata, AttachActionResult, CalculationAction, FieldKind, FieldRoleMetadata,
+};
+use actions_core::error::ActionError;
+use actions_core::graph::{DependencyGraph, FieldDependency};
+use actions_core::ports::driven::LocalSheet;
+use actions_core::ports::driving::{ActionService, MappingTemplateService};
+use actions_core::preview::{FieldValue, PreviewResult};
+use actions_core::suggestion::{ActionSuggestion, MappingSuggestions};
+use actions_core::system::{Dnd5e, find_game_system, game_systems};
+use actions_core::template::MappingTemplate;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use common::error::ProblemDetails;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -12,6 +24,32 @@ pub struct FieldRoleMetadataDto {
     pub key: String,
     pub required: bool,
     pub is_target: bool,
+    /// Whether the role takes a list of fields rather than a single one.
+    pub multiple: bool,
+    pub kind: FieldKindDto,
+}
+
+/// Kind of form field a role expects to be mapped to.
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldKindDto {
+    NumericText,
+    Text,
+    Checkbox,
+    Choice,
+    Any,
+}
+
+impl From<FieldKind> for FieldKindDto {
+    fn from(kind: FieldKind) -> Self {
+        match kind {
+            FieldKind::NumericText => Self::NumericText,
+            FieldKind::Text => Self::Text,
+            FieldKind::Checkbox => Self::Checkbox,
+            FieldKind::Choice => Self::Choice,
+            FieldKind::Any => Self::Any,
+        }
+    }
 }
 
 impl From<FieldRoleMetadata> for FieldRoleMetadataDto {
@@ -20,6 +58,8 @@ impl From<FieldRoleMetadata> for FieldRoleMetadataDto {
             key: role.key,
             required: role.required,
             is_target: role.is_target,
+            multiple: role.multiple,
+            kind: role.kind.into(),
         }
     }
 }
@@ -29,6 +69,8 @@ impl From<FieldRoleMetadata> for FieldRoleMetadataDto {
 pub struct ActionTypeMetadataDto {
     pub id: String,
     pub action_label: String,
+    /// Whether the inputs come from a free-form expression rather than fixed roles.
+    pub free_form: bool,
     pub roles: Vec<FieldRoleMetadataDto>,
 }
 
@@ -37,6 +79,7 @@ impl From<ActionTypeMetadata> for ActionTypeMetadataDto {
         Self {
             id: meta.id,
             action_label: meta.action_label,
+            free_form: meta.free_form,
             roles: meta.roles.into_iter().map(Into::into).collect(),
         }
     }
@@ -82,6 +125,168 @@ enum CalculationActionSchema {
         #[schema(rename = "skillModifierFieldName")]
         skill_modifier_field_name: String,
     },
+    PassiveScore {
+        #[schema(rename = "skillModifierFieldName")]
+        skill_modifier_field_name: String,
+        #[schema(rename = "bonusFieldName")]
+        bonus_field_name: Option<String>,
+        #[schema(rename = "advantageFieldName")]
+        advantage_field_name: Option<String>,
+        #[schema(rename = "disadvantageFieldName")]
+        disadvantage_field_name: Option<String>,
+        #[schema(rename = "passiveScoreFieldName")]
+        passive_score_field_name: String,
+    },
+    ProficiencyBonus {
+        #[schema(rename = "levelFieldName")]
+        level_field_name: String,
+        #[schema(rename = "multiclassLevelFieldNames", required = false)]
+        multiclass_level_field_names: Vec<String>,
+        #[schema(rename = "proficiencyBonusFieldName")]
+        proficiency_bonus_field_name: String,
+    },
+    Initiative {
+        #[schema(rename = "dexModifierFieldName")]
+        dex_modifier_field_name: String,
+        #[schema(rename = "miscBonusFieldNames", required = false)]
+        misc_bonus_field_names: Vec<String>,
+        #[schema(rename = "jackOfAllTradesFieldName")]
+        jack_of_all_trades_field_name: Option<String>,
+        #[schema(rename = "proficiencyBonusFieldName")]
+        proficiency_bonus_field_name: Option<String>,
+        #[schema(rename = "initiativeFieldName")]
+        initiative_field_name: String,
+    },
+    ArmorClass {
+        #[schema(rename = "baseArmorFieldName")]
+        base_armor_field_name: String,
+        #[schema(rename = "dexModifierFieldName")]
+        dex_modifier_field_name: String,
+        #[schema(rename = "maxDexBonusFieldName")]
+        max_dex_bonus_field_name: Option<String>,
+        #[schema(rename = "shieldFieldName")]
+        shield_field_name: Option<String>,
+        #[schema(rename = "miscBonusFieldName")]
+        misc_bonus_field_name: Option<String>,
+        #[schema(rename = "armorClassFieldName")]
+        armor_class_field_name: String,
+    },
+    SpellSaveDc {
+        #[schema(rename = "spellcastingAbilityFieldName")]
+        spellcasting_ability_field_name: String,
+        #[schema(rename = "intelligenceModifierFieldName")]
+        intelligence_modifier_field_name: String,
+        #[schema(rename = "wisdomModifierFieldName")]
+        wisdom_modifier_field_name: String,
+        #[schema(rename = "charismaModifierFieldName")]
+        charisma_modifier_field_name: String,
+        #[schema(rename = "proficiencyBonusFieldName")]
+        proficiency_bonus_field_name: String,
+        #[schema(rename = "miscBonusFieldName")]
+        misc_bonus_field_name: Option<String>,
+        #[schema(rename = "spellSaveDcFieldName")]
+        spell_save_dc_field_name: String,
+    },
+    SpellAttackBonus {
+        #[schema(rename = "spellcastingAbilityFieldName")]
+        spellcasting_ability_field_name: String,
+        #[schema(rename = "intelligenceModifierFieldName")]
+        intelligence_modifier_field_name: String,
+        #[schema(rename = "wisdomModifierFieldName")]
+        wisdom_modifier_field_name: String,
+        #[schema(rename = "charismaModifierFieldName")]
+        charisma_modifier_field_name: String,
+        #[schema(rename = "proficiencyBonusFieldName")]
+        proficiency_bonus_field_name: String,
+        #[schema(rename = "miscBonusFieldName")]
+        misc_bonus_field_name: Option<String>,
+        #[schema(rename = "spellAttackBonusFieldName")]
+        spell_attack_bonus_field_name: String,
+    },
+    WeaponAttackBonus {
+        #[schema(rename = "strengthModifierFieldName")]
+        strength_modifier_field_name: String,
+        #[schema(rename = "dexterityModifierFieldName")]
+        dexterity_modifier_field_name: String,
+        #[schema(rename = "finesseFieldName")]
+        finesse_field_name: Option<String>,
+        #[schema(rename = "rangedFieldName")]
+        ranged_field_name: Option<String>,
+        #[schema(rename = "proficiencyFieldName")]
+        proficiency_field_name: String,
+        #[schema(rename = "proficiencyBonusFieldName")]
+        proficiency_bonus_field_name: String,
+        #[schema(rename = "magicBonusFieldName")]
+        magic_bonus_field_name: Option<String>,
+        #[schema(rename = "attackBonusFieldName")]
+        attack_bonus_field_name: String,
+    },
+    WeaponDamageBonus {
+        #[schema(rename = "strengthModifierFieldName")]
+        strength_modifier_field_name: String,
+        #[schema(rename = "dexterityModifierFieldName")]
+        dexterity_modifier_field_name: String,
+        #[schema(rename = "finesseFieldName")]
+        finesse_field_name: Option<String>,
+        #[schema(rename = "rangedFieldName")]
+        ranged_field_name: Option<String>,
+        #[schema(rename = "magicBonusFieldName")]
+        magic_bonus_field_name: Option<String>,
+        #[schema(rename = "damageBonusFieldName")]
+        damage_bonus_field_name: String,
+    },
+    CarryingCapacity {
+        #[schema(rename = "strengthScoreFieldName")]
+        strength_score_field_name: String,
+        #[schema(rename = "sizeFieldName")]
+        size_field_name: Option<String>,
+        #[schema(rename = "carryingCapacityFieldName")]
+        carrying_capacity_field_name: String,
+    },
+    /// Writes "Unencumbered", "Encumbered" or "Heavily Encumbered" to a text field.
+    EncumbranceStatus {
+        #[schema(rename = "strengthScoreFieldName")]
+        strength_score_field_name: String,
+        #[schema(rename = "weightFieldNames")]
+        weight_field_names: Vec<String>,
+        #[schema(rename = "sizeFieldName")]
+        size_field_name: Option<String>,
+        #[schema(rename = "encumbranceStatusFieldName")]
+        encumbrance_status_field_name: String,
+    },
+    /// Pathfinder 2e modifier: ability modifier plus the rank bonus and level once trained,
+    /// plus an optional item bonus. The rank field holds Untrained, Trained, Expert, Master or
+    /// Legendary (or their initials).
+    ProficiencyRankModifier {
+        #[schema(rename = "abilityModifierFieldName")]
+        ability_modifier_field_name: String,
+        #[schema(rename = "proficiencyRankFieldName")]
+        proficiency_rank_field_name: String,
+        #[schema(rename = "levelFieldName")]
+        level_field_name: String,
+        #[schema(rename = "itemBonusFieldName")]
+        item_bonus_field_name: Option<String>,
+        #[schema(rename = "modifierFieldName")]
+        modifier_field_name: String,
+    },
+    /// Sums the listed source fields plus every form field matching `sourceFieldPattern`
+    /// (`*` matches any run of characters, `?` a single one), expanded when attached.
+    SumFields {
+        #[schema(rename = "sourceFieldNames", required = false)]
+        source_field_names: Vec<String>,
+        #[schema(rename = "sourceFieldPattern")]
+        source_field_pattern: Option<String>,
+        #[schema(rename = "sumFieldName")]
+        sum_field_name: String,
+    },
+    /// Free-form arithmetic over fields referenced in braces, e.g. `floor({STR} * 15 / 2)`.
+    /// Supports integers, `+ - * /`, parentheses, `floor`, `ceil`, `min` and `max`;
+    /// checkboxes read as 0 or 1.
+    Formula {
+        expression: String,
+        #[schema(rename = "targetFieldName")]
+        target_field_name: String,
+    },
 }
 
 #[utoipa::path(
@@ -92,7 +297,8 @@ enum CalculationActionSchema {
     summary = "Attach a calculation action to a sheet",
     description = "Attaches a JavaScript calculation script to a target PDF AcroForm field in a DnD 5e character sheet.\n\n\
 The request body is a JSON object with the action type as the key and field mappings as the value.\n\n\
-Supported action types: `AbilityModifier`, `SavingThrowModifier`, `SkillModifier`.\n\n\
+Supported action types: `AbilityModifier`, `SavingThrowModifier`, `SkillModifier`, `PassiveScore`, `ProficiencyBonus`, `Initiative`, `ArmorClass`, `SpellSaveDc`, `SpellAttackBonus`, `WeaponAttackBonus`, `WeaponDamageBonus`, `CarryingCapacity`, `EncumbranceStatus`, `SumFields`, `Formula`, \
+plus any action type loaded from definition files (see `/dnd5e/action-types`).\n\n\
 The script is embedded directly in the PDF's AcroForm structure for real-time updates.\n\n\
 Note: Sending a request targeting the same field will replace the existing script on that field.",
     params(
@@ -104,7 +310,12 @@ Note: Sending a request targeting the same field will replace the existing scrip
         description = "JSON object with the action variant as key and field name mappings as value."
     ),
     responses(
-        (status = 204, description = "Calculation script successfully attached.")
+        (status = 204, description = "Calculation script successfully attached."),
+        (status = CONFLICT, description = "The action would make a calculation read its own result", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("calculation_cycle" = (summary = "Attaching the action closes a dependency cycle", value = json!({"type": "/problems/calculation-cycle", "title": "Calculation Cycle", "status": 409, "detail": "calculation cycle: STR -> STRmod -> STR"})))
+            )
+        )
     ),
 )]
 #[put("/dnd5e/{sheet_id}/actions")]
@@ -115,33 +326,130 @@ pub async fn attach_calculation_action(
 ) -> Result<HttpResponse, ApiError> {
     let sheet_id = sheet_id.into_inner();
     action_service
-        .attach_calculation_script(&sheet_id, request.into_inner())
+        .attach_calculation_script(&Dnd5e, &sheet_id, request.into_inner())
         .await?;
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachActionResultDto {
+    pub action_type: String,
+    pub target_field: String,
+    /// ID of the attached action, present when the action was attached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Why the action was not attached, present when it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProblemDetails>,
+}
+
+impl From<AttachActionResult> for AttachActionResultDto {
+    fn from(result: AttachActionResult) -> Self {
+        let (id, error) = match result.outcome {
+            Ok(id) => (Some(id.to_string()), None),
+            Err(e) => (None, Some(ApiError::from(e).into_body())),
+        };
+        Self {
+            action_type: result.action_type,
+            target_field: result.target_field,
+            id,
+            error,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/dnd5e/{sheet_id}/actions/batch",
+    tag = "DnD 5e",
+    operation_id = "attachCalculationActions",
+    summary = "Attach many calculation actions to a sheet",
+    description = "Attaches several calculation actions to a DnD 5e character sheet in one request.\n\n\
+The sheet is downloaded, modified and uploaded once for the whole batch, so mapping a full sheet does not pay the PDF round trip per action.\n\n\
+Actions are applied independently: the response lists one result per action, in request order, with either the attached action ID or a problem describing why it was not attached.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the uploaded sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = Vec<CalculationActionSchema>,
+        content_type = "application/json",
+        description = "JSON array of calculation actions, each an object with the action variant as key and field name mappings as value."
+    ),
+    responses(
+        (status = 200, description = "Per-action attach results", body = Vec<AttachActionResultDto>),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/dnd5e/{sheet_id}/actions/batch")]
+pub async fn attach_calculation_actions(
+    action_service: web::Data<ActionService>,
+    sheet_id: web::Path<Uuid>,
+    request: web::Json<Vec<CalculationAction>>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let results = action_service
+        .attach_calculation_scripts(&Dnd5e, &sheet_id, request.into_inner())
+        .await?;
+
+    let response: Vec<AttachActionResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/dnd5e/{sheet_id}/regenerate",
+    tag = "DnD 5e",
+    operation_id = "regenerateSheet",
+    summary = "Regenerate a sheet from its attached actions",
+    description = "Rebuilds the sheet PDF from the original upload by replaying every attached calculation action with the current script generator and helper JS.\n\n\
+Use it to ship helper JS fixes to an existing sheet or to recover from a corrupted edit. Sheets uploaded before originals were retained are rebuilt from their current PDF.\n\n\
+The response lists one result per attached action with either its ID or a problem describing why it could not be replayed. The stored sheet is only replaced when every action replays.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the uploaded sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Per-action replay results", body = Vec<AttachActionResultDto>),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/dnd5e/{sheet_id}/regenerate")]
+pub async fn regenerate_sheet(
+    action_service: web::Data<ActionService>,
+    sheet_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let results = action_service.regenerate_sheet(&Dnd5e, &sheet_id).await?;
+
+    let response: Vec<AttachActionResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 #[utoipa::path(
     get,
     path = "/dnd5e/action-types",
     tag = "DnD 5e",
     operation_id = "listActionTypes",
     summary = "List available action types",
-    description = "Returns metadata for all available calculation action types, including their field roles.\n\n\
+    description = "Returns metadata for the calculation action types available on DnD 5e sheets, including their field roles.\n\n\
 This endpoint powers the frontend action configuration UI dynamically.",
     responses(
         (status = 200, description = "List of available action types", body = Vec<ActionTypeMetadataDto>)
     ),
 )]
 #[get("/dnd5e/action-types")]
-pub async fn list_action_types() -> HttpResponse {
-    let catalog: Vec<ActionTypeMetadataDto> = CalculationAction::action_type_catalog()
+pub async fn list_action_types(action_service: web::Data<ActionService>) -> HttpResponse {
+    let catalog: Vec<ActionTypeMetadataDto> = action_service
+        .action_type_catalog(&Dnd5e)
         .into_iter()
         .map(Into::into)
         .collect();
     HttpResponse::Ok().json(catalog)
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AttachedActionResponse {
     pub id: String,
@@ -184,3 +492,1035 @@ pub async fn list_attached_actions(
 
     Ok(HttpResponse::Ok().json(response))
 }
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDependencyDto {
+    /// ID of the attached action whose calculation reads `sourceField`.
+    pub action_id: String,
+    pub source_field: String,
+    pub target_field: String,
+}
+
+impl From<FieldDependency> for FieldDependencyDto {
+    fn from(dependency: FieldDependency) -> Self {
+        Self {
+            action_id: dependency.action_id.to_string(),
+            source_field: dependency.source_field,
+            target_field: dependency.target_field,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphResponse {
+    /// Every field read or written by an attached calculation, sorted by name.
+    pub fields: Vec<String>,
+    pub dependencies: Vec<FieldDependencyDto>,
+}
+
+impl From<DependencyGraph> for DependencyGraphResponse {
+    fn from(graph: DependencyGraph) -> Self {
+        Self {
+            fields: graph.fields().into_iter().map(str::to_string).collect(),
+            dependencies: graph.dependencies.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dnd5e/{sheet_id}/actions/graph",
+    tag = "DnD 5e",
+    operation_id = "getActionDependencyGraph",
+    summary = "Get the field dependency graph of a sheet",
+    description = "Returns the dependencies between form fields introduced by the calculation actions attached to this sheet.\n\n\
+Each dependency links a field read by a calculation to the field it calculates. Attaching an action that would close a cycle is rejected with `409 Conflict`.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Field dependency graph", body = DependencyGraphResponse)
+    ),
+)]
+#[get("/dnd5e/{sheet_id}/actions/graph")]
+pub async fn get_action_dependency_graph(
+    action_service: web::Data<ActionService>,
+    sheet_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let graph = action_service.dependency_graph(&sheet_id).await?;
+
+    Ok(HttpResponse::Ok().json(DependencyGraphResponse::from(graph)))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionSuggestionDto {
+    pub action_type: String,
+    pub target_field: String,
+    /// The proposed action, ready to be sent to `attachCalculationAction`.
+    #[schema(value_type = CalculationActionSchema)]
+    pub action: CalculationAction,
+    /// Between 0 and 1: how sure the matcher is that each mapped field plays its role.
+    pub confidence: f32,
+}
+
+impl From<ActionSuggestion> for ActionSuggestionDto {
+    fn from(suggestion: ActionSuggestion) -> Self {
+        Self {
+            action_type: suggestion.action.action_label().to_string(),
+            target_field: suggestion.action.target_field().to_string(),
+            action: suggestion.action,
+            confidence: suggestion.confidence,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingSuggestionsResponse {
+    /// Name of the well-known sheet the form fields were recognised as, currently only the official
+    /// Wizards of the Coast sheet.
+    pub sheet_name: Option<String>,
+    pub suggestions: Vec<ActionSuggestionDto>,
+}
+
+impl From<MappingSuggestions> for MappingSuggestionsResponse {
+    fn from(suggestions: MappingSuggestions) -> Self {
+        Self {
+            sheet_name: suggestions.sheet_name.map(str::to_string),
+            suggestions: suggestions
+                .suggestions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dnd5e/{sheet_id}/suggestions",
+    tag = "DnD 5e",
+    operation_id = "suggestCalculationActions",
+    summary = "Suggest calculation actions for a sheet",
+    description = "Proposes calculation actions by matching the sheet's form field names (`STR`, `STRmod`, `Acrobatics`, ...) to the roles of every action type.\n\n\
+The official form-fillable 5e character sheet from Wizards of the Coast is the only sheet recognised by its fields and mapped exactly, including its numbered proficiency checkboxes. \
+Any other sheet, community sheets included, is only matched by field names, so numbered checkboxes are not mapped and `sheetName` is null. \
+Fields that already have a calculation attached are not suggested again. Nothing is attached until the suggestions are sent to `attachCalculationActions`.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Suggested actions", body = MappingSuggestionsResponse),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/dnd5e/{sheet_id}/suggestions")]
+pub async fn suggest_calculation_actions(
+    action_service: web::Data<ActionService>,
+    sheet_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let suggestions = action_service.suggest_actions(&Dnd5e, &sheet_id).await?;
+
+    Ok(HttpResponse::Ok().json(MappingSuggestionsResponse::from(suggestions)))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewRequest {
+    /// Values used instead of the ones stored in the sheet, keyed by form field name, e.g.
+    /// `{"STR": "16"}`. Checkboxes take their export value (`Yes`) or `Off`.
+    pub values: Option<HashMap<String, String>>,
+}
+
+/// Value computed for a field: a number, or text for actions such as encumbrance status.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum PreviewValueDto {
+    Number(f64),
+    Text(String),
+}
+
+impl From<FieldValue> for PreviewValueDto {
+    fn from(value: FieldValue) -> Self {
+        match value {
+            FieldValue::Number(number) => Self::Number(number),
+            FieldValue::Text(text) => Self::Text(text),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewResultDto {
+    pub action_type: String,
+    pub target_field: String,
+    /// Value the calculation writes to the target field, present when it could be computed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<PreviewValueDto>,
+    /// Why the value could not be computed, present when it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProblemDetails>,
+}
+
+impl From<PreviewResult> for PreviewResultDto {
+    fn from(result: PreviewResult) -> Self {
+        let (value, error) = match result.outcome {
+            Ok(value) => (Some(value.into()), None),
+            Err(e) => (None, Some(ApiError::from(e).into_body())),
+        };
+        Self {
+            action_type: result.action_type,
+            target_field: result.target_field,
+            value,
+            error,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/dnd5e/{sheet_id}/preview",
+    tag = "DnD 5e",
+    operation_id = "previewCalculations",
+    summary = "Preview the values computed by a sheet's calculations",
+    description = "Computes the value every attached calculation action writes to its target field, from the values currently stored in the sheet, without modifying it.\n\n\
+Values given in the request replace the stored ones, so a form can show what a change would compute before it is saved. \
+Calculations are evaluated in dependency order with the same rounding and checkbox rules as the helper JS embedded in the sheet, each reading the results of the ones before it.\n\n\
+The response lists one result per attached action with either the computed value or a problem describing why it could not be computed.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = PreviewRequest,
+        content_type = "application/json",
+        description = "Field values to use instead of the stored ones."
+    ),
+    responses(
+        (status = 200, description = "Per-action computed values", body = Vec<PreviewResultDto>),
+        (status = BAD_REQUEST, description = "A value was given for a field the sheet does not have", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("field_not_found" = (summary = "Unknown field", value = json!({"type": "/problems/field-not-found", "title": "Field Not Found", "status": 400, "detail": "field not found in PDF sheet: Strength"})))
+            )
+        ),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/dnd5e/{sheet_id}/preview")]
+pub async fn preview_calculations(
+    action_service: web::Data<ActionService>,
+    sheet_id: web::Path<Uuid>,
+    request: web::Json<PreviewRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let results = action_service
+        .preview(
+            &Dnd5e,
+            &sheet_id,
+            request.into_inner().values.unwrap_or_default(),
+        )
+        .await?;
+
+    let response: Vec<PreviewResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ExportComputedSheetQuery {
+    /// Keep the calculation scripts, so viewers that run JavaScript still recalculate edits.
+    #[serde(default)]
+    pub keep_scripts: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/dnd5e/{sheet_id}/export",
+    tag = "DnD 5e",
+    operation_id = "exportComputedSheet",
+    summary = "Download a sheet with its computed values filled in",
+    description = "Evaluates every attached calculation action and returns a copy of the sheet PDF with the computed values written into the target fields, together with regenerated field appearances.\n\n\
+Many mobile and browser PDF viewers and printing pipelines do not run the calculation scripts, so calculated fields of the regular sheet show up empty there. \
+By default the scripts of the filled-in fields are removed from the copy; set `keepScripts` to keep them so viewers that run JavaScript still recalculate edits. \
+Calculations that cannot be evaluated or that target a button field keep their script and stored value, and calculations whose target field is missing from the sheet are skipped. The stored sheet is not modified.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ExportComputedSheetQuery
+    ),
+    responses(
+        (status = 200, description = "Sheet PDF with computed values", content_type = "application/pdf"),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/dnd5e/{sheet_id}/export")]
+pub async fn export_computed_sheet(
+    action_service: web::Data<ActionService>,
+    sheet_id: web::Path<Uuid>,
+    query: web::Query<ExportComputedSheetQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let local_sheet = action_service
+        .export_computed_sheet(&Dnd5e, &sheet_id, query.keep_scripts)
+        .await?;
+
+    pdf_attachment(&sheet_id, local_sheet).await
+}
+
+/// Responds with the exported copy of a sheet as a PDF download named after the sheet.
+async fn pdf_attachment(
+    sheet_id: &Uuid,
+    local_sheet: LocalSheet,
+) -> Result<HttpResponse, ApiError> {
+    let pdf = web::block(move || std::fs::read(local_sheet.path()))
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .ok_or(ActionError::LoadPdfError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("{}.pdf", sheet_id))],
+        })
+        .body(pdf))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMappingTemplateRequest {
+    /// Name shown when choosing a template, e.g. `Official 5e sheet`.
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingTemplateResponse {
+    pub id: String,
+    pub name: String,
+    /// Fingerprint of the form fields of the sheets the template applies to.
+    pub fingerprint: String,
+    /// Actions of the template, in the format of attached action mappings.
+    pub actions: Vec<serde_json::Value>,
+}
+
+impl From<MappingTemplate> for MappingTemplateResponse {
+    fn from(template: MappingTemplate) -> Self {
+        Self {
+            id: template.id.to_string(),
+            name: template.name,
+            fingerprint: template.fingerprint,
+            actions: template.actions,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/dnd5e/{sheet_id}/templates",
+    tag = "DnD 5e",
+    operation_id = "createMappingTemplate",
+    summary = "Save a sheet's actions as a mapping template",
+    description = "Saves every calculation action attached to the sheet as a named mapping template.\n\n\
+The template is keyed by a fingerprint of the sheet's AcroForm fields, so it can be applied to any other upload of the same sheet with `applyMappingTemplate`. \
+Templates are kept when the sheet they were created from expires.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the mapped sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = CreateMappingTemplateRequest,
+        content_type = "application/json",
+        description = "Name of the template."
+    ),
+    responses(
+        (status = CREATED, description = "Template saved", body = MappingTemplateResponse),
+        (status = BAD_REQUEST, description = "The name is empty or the sheet has no attached actions", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("no_actions" = (summary = "Nothing to save", value = json!({"type": "/problems/invalid-template", "title": "Invalid Template", "status": 400, "detail": "invalid mapping template: sheet has no attached actions to save"})))
+            )
+        ),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/dnd5e/{sheet_id}/templates")]
+pub async fn create_mapping_template(
+    mapping_template_service: web::Data<MappingTemplateService>,
+    sheet_id: web::Path<Uuid>,
+    request: web::Json<CreateMappingTemplateRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let template = mapping_template_service
+        .create_template(&Dnd5e, &sheet_id, &request.name)
+        .await?;
+
+    Ok(HttpResponse::Created().json(MappingTemplateResponse::from(template)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/dnd5e/{sheet_id}/templates",
+    tag = "DnD 5e",
+    operation_id = "listMappingTemplates",
+    summary = "List mapping templates that fit a sheet",
+    description = "Returns the mapping templates created from sheets with exactly the same form fields as this one, oldest first. Each of them can be applied with `applyMappingTemplate`.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Matching templates", body = Vec<MappingTemplateResponse>),
+        (status = NOT_FOUND, description = "Sheet not found", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/dnd5e/{sheet_id}/templates")]
+pub async fn list_mapping_templates(
+    mapping_template_service: web::Data<MappingTemplateService>,
+    sheet_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let sheet_id = sheet_id.into_inner();
+    let templates = mapping_template_service
+        .list_templates(&Dnd5e, &sheet_id)
+        .await?;
+
+    let response: Vec<MappingTemplateResponse> = templates.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/dnd5e/{sheet_id}/templates/{template_id}/apply",
+    tag = "DnD 5e",
+    operation_id = "applyMappingTemplate",
+    summary = "Apply a mapping template to a sheet",
+    description = "Attaches every action of the template to the sheet in one PDF pass, exactly like `attachCalculationActions`.\n\n\
+The sheet must have the same form fields as the sheet the template was created from. \
+The response lists one result per action with either the attached action ID or a problem describing why it was not attached.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ("template_id" = String, Path, description = "ID of the mapping template", example = "6c8f2a1e-4b7d-4e2a-9f3c-1d5e8b7a9c0f")
+    ),
+    responses(
+        (status = 200, description = "Per-action attach results", body = Vec<AttachActionResultDto>),
+        (status = NOT_FOUND, description = "Sheet or template not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The template was created for a sheet with other form fields", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("template_mismatch" = (summary = "Sheet does not fit the template", value = json!({"type": "/problems/template-mismatch", "title": "Template Mismatch", "status": 409, "detail": "mapping template 'Official 5e sheet' was created for a sheet with different form fields"})))
+            )
+        )
+    ),
+)]
+#[post("/dnd5e/{sheet_id}/templates/{template_id}/apply")]
+pub async fn apply_mapping_template(
+    mapping_template_service: web::Data<MappingTemplateService>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (sheet_id, template_id) = path.into_inner();
+    let results = mapping_template_service
+        .apply_template(&Dnd5e, &sheet_id, &template_id)
+        .await?;
+
+    let response: Vec<AttachActionResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    put,
+    path = "/dnd5e/{sheet_id}/actions/{action_id}",
+    tag = "DnD 5e",
+    operation_id = "updateCalculationAction",
+    summary = "Replace an attached calculation action",
+    description = "Replaces a previously attached calculation action, keeping its ID.\n\n\
+When the new action targets another field, the script is removed from the old target field and attached to the new one. \
+A calculation already attached to the new target field is replaced, as with `attachCalculationAction`.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ("action_id" = String, Path, description = "ID of the attached action", example = "8f14e45f-ceea-467f-a8ad-0e9f7b3c2d1a")
+    ),
+    request_body(
+        content = CalculationActionSchema,
+        content_type = "application/json",
+        description = "JSON object with the action variant as key and field name mappings as value."
+    ),
+    responses(
+        (status = 204, description = "Calculation action successfully replaced."),
+        (status = NOT_FOUND, description = "Sheet or attached action not found", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("action_not_found" = (summary = "Attached action does not exist", value = json!({"type": "/problems/action-not-found", "title": "Action Not Found", "status": 404, "detail": "attached action not found: 8f14e45f-ceea-467f-a8ad-0e9f7b3c2d1a"})))
+            )
+        ),
+        (status = CONFLICT, description = "The action would make a calculation read its own result", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[put("/dnd5e/{sheet_id}/actions/{action_id}")]
+pub async fn update_calculation_action(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(Uuid, Uuid)>,
+    request: web::Json<CalculationAction>,
+) -> Result<HttpResponse, ApiError> {
+    let (sheet_id, action_id) = path.into_inner();
+    action_service
+        .update_action(&Dnd5e, &sheet_id, &action_id, request.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/dnd5e/{sheet_id}/actions/{action_id}",
+    tag = "DnD 5e",
+    operation_id = "detachCalculationAction",
+    summary = "Detach a calculation action from a sheet",
+    description = "Removes a previously attached calculation action from a sheet.\n\n\
+The calculation script is removed from the target field's `/AA /C` entry, the field is dropped from the AcroForm calculation order (`/CO`), and the attached action record is deleted.",
+    params(
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ("action_id" = String, Path, description = "ID of the attached action", example = "8f14e45f-ceea-467f-a8ad-0e9f7b3c2d1a")
+    ),
+    responses(
+        (status = 204, description = "Calculation action successfully detached."),
+        (status = NOT_FOUND, description = "Sheet or attached action not found", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("action_not_found" = (summary = "Attached action does not exist", value = json!({"type": "/problems/action-not-found", "title": "Action Not Found", "status": 404, "detail": "attached action not found: 8f14e45f-ceea-467f-a8ad-0e9f7b3c2d1a"})))
+            )
+        )
+    ),
+)]
+#[delete("/dnd5e/{sheet_id}/actions/{action_id}")]
+pub async fn detach_calculation_action(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (sheet_id, action_id) = path.into_inner();
+    action_service
+        .detach_action(&Dnd5e, &sheet_id, &action_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSystemDto {
+    /// Identifier used in `/systems/{system}/...` routes.
+    pub id: String,
+    pub name: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems",
+    tag = "Game Systems",
+    operation_id = "listGameSystems",
+    summary = "List supported game systems",
+    description = "Returns every game system whose sheets can get calculation actions. Each system has its own action types and helper JS.",
+    responses(
+        (status = 200, description = "List of game systems", body = Vec<GameSystemDto>)
+    ),
+)]
+#[get("/systems")]
+pub async fn list_game_systems() -> HttpResponse {
+    let systems: Vec<GameSystemDto> = game_systems()
+        .into_iter()
+        .map(|system| GameSystemDto {
+            id: system.id().to_string(),
+            name: system.name().to_string(),
+        })
+        .collect();
+    HttpResponse::Ok().json(systems)
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems/{system}/action-types",
+    tag = "Game Systems",
+    operation_id = "listSystemActionTypes",
+    summary = "List the action types of a game system",
+    description = "Returns metadata for the calculation action types available on the game system's sheets, including their field roles.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e")
+    ),
+    responses(
+        (status = 200, description = "List of available action types", body = Vec<ActionTypeMetadataDto>),
+        (status = NOT_FOUND, description = "Unknown game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/systems/{system}/action-types")]
+pub async fn list_system_action_types(
+    system: web::Path<String>,
+    action_service: web::Data<ActionService>,
+) -> Result<HttpResponse, ApiError> {
+    let system = find_game_system(&system)?;
+    let catalog: Vec<ActionTypeMetadataDto> = action_service
+        .action_type_catalog(system)
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    Ok(HttpResponse::Ok().json(catalog))
+}
+
+#[utoipa::path(
+    put,
+    path = "/systems/{system}/{sheet_id}/actions",
+    tag = "Game Systems",
+    operation_id = "attachSystemCalculationAction",
+    summary = "Attach a calculation action to a sheet of a game system",
+    description = "Attaches a JavaScript calculation script to a target PDF AcroForm field, installing the game system's helper JS in the sheet.\n\n\
+The action type must be listed by `GET /systems/{system}/action-types`. A sheet holds actions of a single game system: attaching to a sheet whose existing actions belong to another system is rejected with `409 Conflict`.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the uploaded sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = CalculationActionSchema,
+        content_type = "application/json",
+        description = "JSON object with the action variant as key and field name mappings as value."
+    ),
+    responses(
+        (status = 204, description = "Calculation script successfully attached."),
+        (status = BAD_REQUEST, description = "The game system does not provide the action", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system, or the action would make a calculation read its own result", body = ProblemDetails, content_type = "application/problem+json",
+            examples(
+                ("game_system_mismatch" = (summary = "The sheet was mapped with another game system", value = json!({"type": "/problems/game-system-mismatch", "title": "Game System Mismatch", "status": 409, "detail": "sheet already has SkillModifier actions, which Pathfinder 2nd Edition does not support"})))
+            )
+        )
+    ),
+)]
+#[put("/systems/{system}/{sheet_id}/actions")]
+pub async fn attach_system_calculation_action(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+    request: web::Json<CalculationAction>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    action_service
+        .attach_calculation_script(system, &sheet_id, request.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/systems/{system}/{sheet_id}/actions/batch",
+    tag = "Game Systems",
+    operation_id = "attachSystemCalculationActions",
+    summary = "Attach many calculation actions to a sheet of a game system",
+    description = "Attaches several calculation actions in one request, installing the game system's helper JS in the sheet.\n\n\
+The response lists one result per action, in request order; actions the game system does not provide fail individually.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the uploaded sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = Vec<CalculationActionSchema>,
+        content_type = "application/json",
+        description = "JSON array of calculation actions, each an object with the action variant as key and field name mappings as value."
+    ),
+    responses(
+        (status = 200, description = "Per-action attach results", body = Vec<AttachActionResultDto>),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/systems/{system}/{sheet_id}/actions/batch")]
+pub async fn attach_system_calculation_actions(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+    request: web::Json<Vec<CalculationAction>>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let results = action_service
+        .attach_calculation_scripts(system, &sheet_id, request.into_inner())
+        .await?;
+
+    let response: Vec<AttachActionResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems/{system}/{sheet_id}/actions",
+    tag = "Game Systems",
+    operation_id = "listSystemAttachedActions",
+    summary = "List attached actions for a sheet of a game system",
+    description = "Returns all calculation actions that have been attached to form fields in this sheet.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "List of attached actions", body = Vec<AttachedActionResponse>),
+        (status = NOT_FOUND, description = "Unknown game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/systems/{system}/{sheet_id}/actions")]
+pub async fn list_system_attached_actions(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    find_game_system(&system)?;
+    let actions = action_service.list_attached_actions(&sheet_id).await?;
+
+    let response: Vec<AttachedActionResponse> = actions
+        .into_iter()
+        .map(|a| AttachedActionResponse {
+            id: a.id.to_string(),
+            action_type: a.action_type,
+            target_field: a.target_field,
+            mapping: a.mapping,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/systems/{system}/{sheet_id}/regenerate",
+    tag = "Game Systems",
+    operation_id = "regenerateSystemSheet",
+    summary = "Regenerate a sheet of a game system from its attached actions",
+    description = "Rebuilds the sheet PDF from the original upload by replaying every attached calculation action with the current script generator and the game system's helper JS. The stored sheet is only replaced when every action replays.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the uploaded sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Per-action replay results", body = Vec<AttachActionResultDto>),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/systems/{system}/{sheet_id}/regenerate")]
+pub async fn regenerate_system_sheet(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let results = action_service.regenerate_sheet(system, &sheet_id).await?;
+
+    let response: Vec<AttachActionResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/systems/{system}/{sheet_id}/preview",
+    tag = "Game Systems",
+    operation_id = "previewSystemCalculations",
+    summary = "Preview the values computed by the calculations of a game system's sheet",
+    description = "Computes the value every attached calculation action writes to its target field, from the values currently stored in the sheet and the ones given in the request, without modifying it.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = PreviewRequest,
+        content_type = "application/json",
+        description = "Field values to use instead of the stored ones."
+    ),
+    responses(
+        (status = 200, description = "Per-action computed values", body = Vec<PreviewResultDto>),
+        (status = BAD_REQUEST, description = "A value was given for a field the sheet does not have", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/systems/{system}/{sheet_id}/preview")]
+pub async fn preview_system_calculations(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+    request: web::Json<PreviewRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let results = action_service
+        .preview(
+            system,
+            &sheet_id,
+            request.into_inner().values.unwrap_or_default(),
+        )
+        .await?;
+
+    let response: Vec<PreviewResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems/{system}/{sheet_id}/export",
+    tag = "Game Systems",
+    operation_id = "exportSystemComputedSheet",
+    summary = "Download a game system's sheet with its computed values filled in",
+    description = "Evaluates every attached calculation action and returns a copy of the sheet PDF with the computed values written into the target fields, together with regenerated field appearances. The stored sheet is not modified.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ExportComputedSheetQuery
+    ),
+    responses(
+        (status = 200, description = "Sheet PDF with computed values", content_type = "application/pdf"),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/systems/{system}/{sheet_id}/export")]
+pub async fn export_system_computed_sheet(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+    query: web::Query<ExportComputedSheetQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let local_sheet = action_service
+        .export_computed_sheet(system, &sheet_id, query.keep_scripts)
+        .await?;
+
+    pdf_attachment(&sheet_id, local_sheet).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/systems/{system}/{sheet_id}/actions/{action_id}",
+    tag = "Game Systems",
+    operation_id = "updateSystemCalculationAction",
+    summary = "Replace a calculation action attached to a sheet of a game system",
+    description = "Replaces a previously attached calculation action, keeping its ID. When the new action targets another field, the script is moved from the old target field to the new one.\n\n\
+The action type must be listed by `GET /systems/{system}/action-types`.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ("action_id" = String, Path, description = "ID of the attached action", example = "8f14e45f-ceea-467f-a8ad-0e9f7b3c2d1a")
+    ),
+    request_body(
+        content = CalculationActionSchema,
+        content_type = "application/json",
+        description = "JSON object with the action variant as key and field name mappings as value."
+    ),
+    responses(
+        (status = 204, description = "Calculation action successfully replaced."),
+        (status = BAD_REQUEST, description = "The game system does not provide the action", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "Unknown game system, sheet or attached action", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system, or the action would make a calculation read its own result", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[put("/systems/{system}/{sheet_id}/actions/{action_id}")]
+pub async fn update_system_calculation_action(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid, Uuid)>,
+    request: web::Json<CalculationAction>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id, action_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    action_service
+        .update_action(system, &sheet_id, &action_id, request.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/systems/{system}/{sheet_id}/actions/{action_id}",
+    tag = "Game Systems",
+    operation_id = "detachSystemCalculationAction",
+    summary = "Detach a calculation action from a sheet of a game system",
+    description = "Removes a previously attached calculation action from the sheet, together with its calculation script and its place in the AcroForm calculation order.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ("action_id" = String, Path, description = "ID of the attached action", example = "8f14e45f-ceea-467f-a8ad-0e9f7b3c2d1a")
+    ),
+    responses(
+        (status = 204, description = "Calculation action successfully detached."),
+        (status = NOT_FOUND, description = "Unknown game system, sheet or attached action", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[delete("/systems/{system}/{sheet_id}/actions/{action_id}")]
+pub async fn detach_system_calculation_action(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id, action_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    action_service
+        .detach_action(system, &sheet_id, &action_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems/{system}/{sheet_id}/actions/graph",
+    tag = "Game Systems",
+    operation_id = "getSystemActionDependencyGraph",
+    summary = "Get the field dependency graph of a game system's sheet",
+    description = "Returns the dependencies between form fields introduced by the calculation actions attached to this sheet. Each dependency links a field read by a calculation to the field it calculates.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Field dependency graph", body = DependencyGraphResponse),
+        (status = NOT_FOUND, description = "Unknown game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/systems/{system}/{sheet_id}/actions/graph")]
+pub async fn get_system_action_dependency_graph(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    find_game_system(&system)?;
+    let graph = action_service.dependency_graph(&sheet_id).await?;
+
+    Ok(HttpResponse::Ok().json(DependencyGraphResponse::from(graph)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems/{system}/{sheet_id}/suggestions",
+    tag = "Game Systems",
+    operation_id = "suggestSystemCalculationActions",
+    summary = "Suggest calculation actions for a sheet of a game system",
+    description = "Proposes calculation actions by matching the sheet's form field names to the roles of the action types the game system provides.\n\n\
+Fields that already have a calculation attached are not suggested again. Nothing is attached until the suggestions are sent to `attachSystemCalculationActions`.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Suggested actions", body = MappingSuggestionsResponse),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/systems/{system}/{sheet_id}/suggestions")]
+pub async fn suggest_system_calculation_actions(
+    action_service: web::Data<ActionService>,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let suggestions = action_service.suggest_actions(system, &sheet_id).await?;
+
+    Ok(HttpResponse::Ok().json(MappingSuggestionsResponse::from(suggestions)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/systems/{system}/{sheet_id}/templates",
+    tag = "Game Systems",
+    operation_id = "createSystemMappingTemplate",
+    summary = "Save the actions of a game system's sheet as a mapping template",
+    description = "Saves every calculation action attached to the sheet as a named mapping template of the game system, to be applied to other uploads of the same sheet with `applySystemMappingTemplate`.\n\n\
+A sheet whose actions belong to another game system is rejected with `409 Conflict`.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the mapped sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    request_body(
+        content = CreateMappingTemplateRequest,
+        content_type = "application/json",
+        description = "Name of the template."
+    ),
+    responses(
+        (status = CREATED, description = "Template saved", body = MappingTemplateResponse),
+        (status = BAD_REQUEST, description = "The name is empty or the sheet has no attached actions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/systems/{system}/{sheet_id}/templates")]
+pub async fn create_system_mapping_template(
+    mapping_template_service: web::Data<MappingTemplateService>,
+    path: web::Path<(String, Uuid)>,
+    request: web::Json<CreateMappingTemplateRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let template = mapping_template_service
+        .create_template(system, &sheet_id, &request.name)
+        .await?;
+
+    Ok(HttpResponse::Created().json(MappingTemplateResponse::from(template)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/systems/{system}/{sheet_id}/templates",
+    tag = "Game Systems",
+    operation_id = "listSystemMappingTemplates",
+    summary = "List the game system's mapping templates that fit a sheet",
+    description = "Returns the game system's mapping templates created from sheets with exactly the same form fields as this one, oldest first.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000")
+    ),
+    responses(
+        (status = 200, description = "Matching templates", body = Vec<MappingTemplateResponse>),
+        (status = NOT_FOUND, description = "Unknown game system or sheet", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[get("/systems/{system}/{sheet_id}/templates")]
+pub async fn list_system_mapping_templates(
+    mapping_template_service: web::Data<MappingTemplateService>,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let templates = mapping_template_service
+        .list_templates(system, &sheet_id)
+        .await?;
+
+    let response: Vec<MappingTemplateResponse> = templates.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/systems/{system}/{sheet_id}/templates/{template_id}/apply",
+    tag = "Game Systems",
+    operation_id = "applySystemMappingTemplate",
+    summary = "Apply a game system's mapping template to a sheet",
+    description = "Attaches every action of the template to the sheet in one PDF pass, exactly like `attachSystemCalculationActions`.\n\n\
+Templates of another game system are not found, and the sheet must have the same form fields as the sheet the template was created from.",
+    params(
+        ("system" = String, Path, description = "ID of the game system", example = "pf2e"),
+        ("sheet_id" = String, Path, description = "ID of the sheet", example = "123e4567-e89b-12d3-a456-426614174000"),
+        ("template_id" = String, Path, description = "ID of the mapping template", example = "6c8f2a1e-4b7d-4e2a-9f3c-1d5e8b7a9c0f")
+    ),
+    responses(
+        (status = 200, description = "Per-action attach results", body = Vec<AttachActionResultDto>),
+        (status = NOT_FOUND, description = "Unknown game system, sheet or template", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = CONFLICT, description = "The template was created for a sheet with other form fields, or the sheet has actions of another game system", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+)]
+#[post("/systems/{system}/{sheet_id}/templates/{template_id}/apply")]
+pub async fn apply_system_mapping_template(
+    mapping_template_service: web::Data<MappingTemplateService>,
+    path: web::Path<(String, Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (system, sheet_id, template_id) = path.into_inner();
+    let system = find_game_system(&system)?;
+    let results = mapping_template_service
+        .apply_template(system, &sheet_id, &template_id)
+        .await?;
+
+    let response: Vec<AttachActionResultDto> = results.into_iter().map(Into::into).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -17,6 +17,27 @@ pub struct ProblemDetails {
     /// A URI reference that identifies the specific occurrence.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instance: Option<String>,
+    /// Extension member listing each request parameter that failed validation.
+    #[serde(rename = "invalidParams", skip_serializing_if = "Option::is_none")]
+    pub invalid_params: Option<Vec<InvalidParam>>,
+}
+
+/// A request parameter that failed validation and why.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InvalidParam {
+    /// Name of the offending parameter.
+    pub name: String,
+    /// A human-readable explanation of why the parameter is invalid.
+    pub reason: String,
+}
+
+impl InvalidParam {
+    pub fn new(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            reason: reason.into(),
+        }
+    }
 }
 
 impl ProblemDetails {
@@ -27,6 +48,7 @@ impl ProblemDetails {
             status,
             detail: None,
             instance: None,
+            invalid_params: None,
         }
     }
 
@@ -38,6 +60,11 @@ impl ProblemDetails {
         self.detail = Some(detail.into());
         self
     }
+
+    pub fn with_invalid_params(mut self, invalid_params: Vec<InvalidParam>) -> Self {
+        self.invalid_params = Some(invalid_params);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -55,6 +82,7 @@ mod tests {
         assert_eq!(json["status"], 404);
         assert!(json.get("detail").is_none());
         assert!(json.get("instance").is_none());
+        assert!(json.get("invalidParams").is_none());
     }
 
     #[test]
@@ -66,6 +94,16 @@ mod tests {
         assert_eq!(json["detail"], "Sheet abc not found");
     }
 
+    #[test]
+    fn serializes_with_invalid_params() {
+        let problem = ProblemDetails::new("/problems/invalid-field-mapping", "Bad Request", 400)
+            .with_invalid_params(vec![InvalidParam::new("abilityScoreFieldName", "missing")]);
+        let json = serde_json::to_value(&problem).unwrap();
+
+        assert_eq!(json["invalidParams"][0]["name"], "abilityScoreFieldName");
+        assert_eq!(json["invalidParams"][0]["reason"], "missing");
+    }
+
     #[test]
     fn internal_helper_produces_500() {
         let problem = ProblemDetails::internal();
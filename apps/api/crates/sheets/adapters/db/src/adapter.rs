@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use sheets_core::error::SheetError;
 use sheets_core::ports::driven::{FailedSheetDeletionPort, SheetReferencePort};
 use sheets_core::sheet::{FailedSheetDeletion, SheetReference};
-use sqlx::types::Uuid;
+use sqlx::types::{Json, Uuid};
 use sqlx::{FromRow, Pool, Postgres};
 use std::path::PathBuf;
 use tracing::{info, instrument};
@@ -189,6 +189,74 @@ impl actions_core::ports::driven::AttachedActionPort for SheetReferenceDb {
             })
             .collect())
     }
+
+    #[instrument(name = "db.find_attached_action", skip(self), level = "info", fields(%sheet_id, %action_id))]
+    async fn find_by_id(
+        &self,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+    ) -> Result<actions_core::action::AttachedAction, actions_core::error::ActionError> {
+        let row: Option<AttachedActionRow> = sqlx::query_as(
+            r#"SELECT id, sheet_id, action_type, target_field, mapping
+               FROM attached_action
+               WHERE id = $1 AND sheet_id = $2"#,
+        )
+        .bind(action_id)
+        .bind(sheet_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        match row {
+            Some(r) => Ok(actions_core::action::AttachedAction {
+                id: r.id,
+                sheet_id: r.sheet_id,
+                action_type: r.action_type,
+                target_field: r.target_field,
+                mapping: r.mapping,
+            }),
+            None => Err(actions_core::error::ActionError::ActionNotFound(*action_id)),
+        }
+    }
+
+    #[instrument(name = "db.update_attached_action", skip(self, action), level = "info", fields(sheet_id = %action.sheet_id, action_id = %action.id, target_field = %action.target_field))]
+    async fn update(
+        &self,
+        action: &actions_core::action::AttachedAction,
+    ) -> Result<(), actions_core::error::ActionError> {
+        sqlx::query(
+            r#"UPDATE attached_action
+               SET action_type = $3, target_field = $4, mapping = $5
+               WHERE id = $1 AND sheet_id = $2"#,
+        )
+        .bind(action.id)
+        .bind(action.sheet_id)
+        .bind(&action.action_type)
+        .bind(&action.target_field)
+        .bind(&action.mapping)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.delete_attached_action", skip(self), level = "info", fields(%sheet_id, %action_id))]
+    async fn delete(
+        &self,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+    ) -> Result<(), actions_core::error::ActionError> {
+        sqlx::query("DELETE FROM attached_action WHERE id = $1 AND sheet_id = $2")
+            .bind(action_id)
+            .bind(sheet_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        info!(%sheet_id, %action_id, "deleted attached action");
+        Ok(())
+    }
 }
 
 #[derive(FromRow)]
@@ -200,6 +268,94 @@ struct AttachedActionRow {
     mapping: serde_json::Value,
 }
 
+#[async_trait]
+impl actions_core::ports::driven::MappingTemplatePort for SheetReferenceDb {
+    #[instrument(name = "db.save_mapping_template", skip(self, template), level = "info", fields(template_id = %template.id, system = %template.system))]
+    async fn save(
+        &self,
+        template: &actions_core::template::MappingTemplate,
+    ) -> Result<(), actions_core::error::ActionError> {
+        sqlx::query(
+            r#"INSERT INTO mapping_template (id, name, game_system, fingerprint, actions)
+               VALUES ($1, $2, $3, $4, $5)"#,
+        )
+        .bind(template.id)
+        .bind(&template.name)
+        .bind(&template.system)
+        .bind(&template.fingerprint)
+        .bind(Json(&template.actions))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.list_mapping_templates", skip(self), level = "info", fields(%system, %fingerprint))]
+    async fn list_by_fingerprint(
+        &self,
+        system: &str,
+        fingerprint: &str,
+    ) -> Result<Vec<actions_core::template::MappingTemplate>, actions_core::error::ActionError>
+    {
+        let rows: Vec<MappingTemplateRow> = sqlx::query_as(
+            r#"SELECT id, name, game_system, fingerprint, actions
+               FROM mapping_template
+               WHERE game_system = $1 AND fingerprint = $2
+               ORDER BY created_at"#,
+        )
+        .bind(system)
+        .bind(fingerprint)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[instrument(name = "db.find_mapping_template", skip(self), level = "info", fields(%template_id))]
+    async fn find_by_id(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<actions_core::template::MappingTemplate, actions_core::error::ActionError> {
+        let row: Option<MappingTemplateRow> = sqlx::query_as(
+            r#"SELECT id, name, game_system, fingerprint, actions
+               FROM mapping_template
+               WHERE id = $1"#,
+        )
+        .bind(template_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        row.map(Into::into)
+            .ok_or(actions_core::error::ActionError::TemplateNotFound(
+                *template_id,
+            ))
+    }
+}
+
+#[derive(FromRow)]
+struct MappingTemplateRow {
+    id: Uuid,
+    name: String,
+    game_system: String,
+    fingerprint: String,
+    actions: Json<Vec<serde_json::Value>>,
+}
+
+impl From<MappingTemplateRow> for actions_core::template::MappingTemplate {
+    fn from(row: MappingTemplateRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            system: row.game_system,
+            fingerprint: row.fingerprint,
+            actions: row.actions.0,
+        }
+    }
+}
+
 /// Database adapter for failed sheet deletion tracking (dead letter table).
 pub struct FailedSheetDeletionDb {
     pool: Pool<Postgres>,
@@ -1,4 +1,5 @@
 use crate::config::S3Config;
+use actions_core::ports::driven::LocalSheet;
 use async_trait::async_trait;
 use aws_config::BehaviorVersion;
 use aws_sdk_s3::Client;
@@ -86,6 +87,32 @@ impl SheetS3Storage {
         format!("sheets/{}/{}", sheet_reference.id, file_name)
     }
 
+    /// Key of the pristine upload kept next to the sheet, e.g. `sheets/{uuid}/original/{name}.pdf`.
+    fn original_object_key(object_key: &str) -> String {
+        match object_key.rsplit_once('/') {
+            Some((prefix, file_name)) => format!("{}/original/{}", prefix, file_name),
+            None => format!("original/{}", object_key),
+        }
+    }
+
+    async fn upload_file(&self, local_path: &Path, object_key: &str) -> std::io::Result<()> {
+        let body = ByteStream::from_path(local_path)
+            .await
+            .map_err(|e| std::io::Error::other(format!("failed to read file for upload: {e}")))?;
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(object_key)
+            .content_type("application/pdf")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| std::io::Error::other(format!("S3 upload failed: {e}")))?;
+
+        Ok(())
+    }
+
     /// Prepend `self.public_path_prefix` to the path component of a presigned
     /// URL. This is necessary when the public endpoint sits behind a reverse
     /// proxy that strips a path prefix before forwarding to the storage backend.
@@ -111,25 +138,17 @@ impl SheetStoragePort for SheetS3Storage {
 
         debug!(?object_key, "uploading sheet to S3");
 
-        let body = ByteStream::from_path(&sheet_reference.path)
+        let original_object_key = Self::original_object_key(&object_key);
+
+        self.upload_file(&sheet_reference.path, &original_object_key)
             .await
-            .map_err(|e| {
-                SheetError::StorageError(std::io::Error::other(format!(
-                    "failed to read file for upload: {e}"
-                )))
-            })?;
+            .map_err(SheetError::StorageError)?;
 
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(&object_key)
-            .content_type("application/pdf")
-            .body(body)
-            .send()
+        debug!(%original_object_key, "uploaded original sheet to S3");
+
+        self.upload_file(&sheet_reference.path, &object_key)
             .await
-            .map_err(|e| {
-                SheetError::StorageError(std::io::Error::other(format!("S3 upload failed: {e}")))
-            })?;
+            .map_err(SheetError::StorageError)?;
 
         info!(%object_key, "uploaded sheet to S3");
 
@@ -155,7 +174,20 @@ impl SheetStoragePort for SheetS3Storage {
             .key(&object_key)
             .send()
             .await
-            .map_err(|e| SheetError::NotFound(format!("S3 object not found: {e}")))?;
+            .map_err(|sdk_err| {
+                // Only a missing key means the sheet is gone; other errors must not be mistaken
+                // for it, e.g. by regeneration falling back to the current sheet
+                if sdk_err
+                    .as_service_error()
+                    .is_some_and(|service_err| service_err.is_no_such_key())
+                {
+                    SheetError::NotFound(format!("S3 object not found: {sdk_err}"))
+                } else {
+                    SheetError::StorageError(std::io::Error::other(format!(
+                        "failed to download S3 object: {sdk_err}"
+                    )))
+                }
+            })?;
 
         let data = response.body.collect().await.map_err(|e| {
             SheetError::StorageError(std::io::Error::other(format!(
@@ -262,39 +294,45 @@ impl SheetStoragePort for SheetS3Storage {
 #[async_trait]
 impl actions_core::ports::driven::SheetStoragePort for SheetS3Storage {
     #[instrument(name = "s3.read.action_port", skip(self, path), level = "info", err, fields(path = %path.display()))]
-    async fn read(&self, path: PathBuf) -> Result<PathBuf, actions_core::error::ActionError> {
+    async fn read(&self, path: PathBuf) -> Result<LocalSheet, actions_core::error::ActionError> {
         <SheetS3Storage as SheetStoragePort>::read(self, path)
             .await
-            .map_err(|_| actions_core::error::ActionError::FileNotFound)
+            .map(LocalSheet::new)
+            .map_err(|e| match e {
+                SheetError::NotFound(_) => actions_core::error::ActionError::FileNotFound,
+                e => actions_core::error::ActionError::InvalidAction(e.to_string()),
+            })
+    }
+
+    #[instrument(name = "s3.read_original.action_port", skip(self, storage_path), level = "info", err, fields(storage_path = %storage_path.display()))]
+    async fn read_original(
+        &self,
+        storage_path: PathBuf,
+    ) -> Result<LocalSheet, actions_core::error::ActionError> {
+        let original_object_key = Self::original_object_key(&storage_path.to_string_lossy());
+
+        <SheetS3Storage as SheetStoragePort>::read(self, PathBuf::from(original_object_key))
+            .await
+            .map(LocalSheet::new)
+            .map_err(|e| match e {
+                SheetError::NotFound(_) => actions_core::error::ActionError::FileNotFound,
+                e => actions_core::error::ActionError::InvalidAction(e.to_string()),
+            })
     }
 
-    #[instrument(name = "s3.write.action_port", skip(self), level = "info", err, fields(local_path = %local_path.display(), storage_path = %storage_path.display()))]
+    #[instrument(name = "s3.write.action_port", skip(self, local_sheet), level = "info", err, fields(local_path = %local_sheet.path().display(), storage_path = %storage_path.display()))]
     async fn write(
         &self,
-        local_path: PathBuf,
+        local_sheet: LocalSheet,
         storage_path: PathBuf,
     ) -> Result<(), actions_core::error::ActionError> {
         let object_key = storage_path.to_string_lossy().to_string();
 
         debug!(%object_key, "uploading modified sheet to S3");
 
-        let body = ByteStream::from_path(&local_path).await.map_err(|e| {
-            actions_core::error::ActionError::InvalidAction(format!(
-                "failed to read file for upload: {e}"
-            ))
-        })?;
-
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(&object_key)
-            .content_type("application/pdf")
-            .body(body)
-            .send()
+        self.upload_file(local_sheet.path(), &object_key)
             .await
-            .map_err(|e| {
-                actions_core::error::ActionError::InvalidAction(format!("S3 upload failed: {e}"))
-            })?;
+            .map_err(|e| actions_core::error::ActionError::InvalidAction(e.to_string()))?;
 
         info!(%object_key, "uploaded modified sheet to S3");
 
@@ -6,7 +6,7 @@ use actix_web::{HttpResponse, get, post, web};
 use common::error::ProblemDetails;
 use serde::{Deserialize, Serialize};
 use sheets_core::ports::driving::{SheetCleanupPort, SheetService};
-use sheets_core::sheet::{Sheet, SheetField, SheetReference};
+use sheets_core::sheet::{Sheet, SheetField, SheetFieldFlags, SheetFieldKind, SheetReference};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 use utoipa::ToSchema;
@@ -33,22 +33,86 @@ impl UploadSheetResponse {
     }
 }
 
+/// Kind of AcroForm field; buttons are reported as checkboxes or radio groups.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
-pub struct SheetFieldDto {
-    /// Name of the AcroForm field.
-    name: String,
+#[serde(rename_all = "camelCase")]
+pub enum SheetFieldKindDto {
+    Text,
+    Choice,
+    Checkbox,
+    Radio,
 }
 
-impl SheetFieldDto {
-    pub fn new(name: impl Into<String>) -> Self {
-        let name = name.into();
-        Self { name }
+impl From<SheetFieldKind> for SheetFieldKindDto {
+    fn from(value: SheetFieldKind) -> Self {
+        match value {
+            SheetFieldKind::Text => Self::Text,
+            SheetFieldKind::Choice => Self::Choice,
+            SheetFieldKind::Checkbox => Self::Checkbox,
+            SheetFieldKind::Radio => Self::Radio,
+        }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetFieldFlagsDto {
+    pub read_only: bool,
+    pub required: bool,
+    /// Multiline text field.
+    pub multiline: bool,
+    /// Drop-down combo box rather than list box.
+    pub combo: bool,
+}
+
+impl From<SheetFieldFlags> for SheetFieldFlagsDto {
+    fn from(value: SheetFieldFlags) -> Self {
+        Self {
+            read_only: value.read_only,
+            required: value.required,
+            multiline: value.multiline,
+            combo: value.combo,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetFieldDto {
+    /// Fully-qualified name of the AcroForm field, parent field names joined by `.` (e.g. `Skills.Acrobatics`).
+    pub name: String,
+    pub kind: SheetFieldKindDto,
+    pub flags: SheetFieldFlagsDto,
+    /// Current field value (`/V`); the export value of the state for buttons.
+    pub value: Option<String>,
+    /// Value the field resets to (`/DV`).
+    pub default_value: Option<String>,
+    /// Export values of a choice field.
+    pub options: Vec<String>,
+    /// Maximum length of a text field.
+    pub max_length: Option<u32>,
+    /// Zero-based index of the page showing the field.
+    pub page_index: Option<u32>,
+    /// Widget rectangle `[x1, y1, x2, y2]` in PDF user space.
+    pub rect: Option<Vec<f32>>,
+    /// Whether a calculation script is already attached to the field.
+    pub has_calculation: bool,
+}
+
 impl From<SheetField> for SheetFieldDto {
     fn from(value: SheetField) -> Self {
-        SheetFieldDto::new(value.name)
+        Self {
+            name: value.name,
+            kind: value.kind.into(),
+            flags: value.flags.into(),
+            value: value.value,
+            default_value: value.default_value,
+            options: value.options,
+            max_length: value.max_length,
+            page_index: value.page_index,
+            rect: value.rect.map(Vec::from),
+            has_calculation: value.has_calculation,
+        }
     }
 }
 
@@ -181,7 +245,7 @@ pub async fn download_sheet(
     tag = "Sheets",
     operation_id = "getSheetFormFields",
     summary = "Lists sheet form fields",
-    description = "Lists interactive PDF AcroForm fields that support calculation actions. Only text (`/Tx`), choice (`/Ch`) fields with widget annotations are returned.",
+    description = "Lists interactive PDF AcroForm fields that support calculation actions. Text (`/Tx`), choice (`/Ch`), checkbox and radio (`/Btn`) fields with at least one widget annotation are returned; push buttons and signatures are not. The field type and flags are inherited from parent fields, and widgets may be the field itself or its unnamed kids. Checkboxes are listed whatever their field flags, so read-only or required checkboxes that earlier listings left out are now returned.",
     params(
         ("sheet_id" = String, Path, description = "ID of the uploaded sheet", example = "123e4567-e89b-12d3-a456-426614174000")
     ),
@@ -0,0 +1,11 @@
+-- Templates outlive the sheet they were created from, so there is no reference to sheet_reference
+CREATE TABLE IF NOT EXISTS mapping_template (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    game_system TEXT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    actions JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_mapping_template_fingerprint ON mapping_template (game_system, fingerprint);
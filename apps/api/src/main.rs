@@ -1,6 +1,18 @@
-use actions_core::ports::driving::ActionService;
+use actions_core::definition::ActionDefinitions;
+use actions_core::ports::driving::{ActionService, MappingTemplateService};
 use actions_pdf::adapter::PdfActionAdapter;
-use actions_web::handler::{attach_calculation_action, list_action_types, list_attached_actions};
+use actions_web::handler::{
+    apply_mapping_template, apply_system_mapping_template, attach_calculation_action,
+    attach_calculation_actions, attach_system_calculation_action,
+    attach_system_calculation_actions, create_mapping_template, create_system_mapping_template,
+    detach_calculation_action, detach_system_calculation_action, export_computed_sheet,
+    export_system_computed_sheet, get_action_dependency_graph, get_system_action_dependency_graph,
+    list_action_types, list_attached_actions, list_game_systems, list_mapping_templates,
+    list_system_action_types, list_system_attached_actions, list_system_mapping_templates,
+    preview_calculations, preview_system_calculations, regenerate_sheet, regenerate_system_sheet,
+    suggest_calculation_actions, suggest_system_calculation_actions, update_calculation_action,
+    update_system_calculation_action,
+};
 use actix_cors::Cors;
 use actix_web::{App, HttpServer, web};
 use anyhow::{Context, Result};
@@ -18,6 +30,7 @@ use sheets_s3::config::S3Config;
 use sheets_web::handler::{download_sheet, get_sheet_form_fields, handle_s3_event, upload_sheet};
 use sqlx::postgres::PgPoolOptions;
 use std::env;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{error, info};
@@ -131,13 +144,27 @@ async fn main() -> Result<()> {
     let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
         Arc::new(PdfActionAdapter);
     let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+        sheet_reference_db.clone();
+    let mapping_template_port: Arc<dyn actions_core::ports::driven::MappingTemplatePort> =
         sheet_reference_db;
+    let action_definitions = match env::var("ACTION_DEFINITIONS_DIR") {
+        Ok(dir) if !dir.is_empty() => {
+            let definitions = ActionDefinitions::load_dir(Path::new(&dir))
+                .with_context(|| format!("failed to load action definitions from {}", dir))?;
+            info!(count = definitions.len(), dir, "loaded action definitions");
+            definitions
+        }
+        _ => ActionDefinitions::default(),
+    };
     let action_service = ActionService::new(
         action_reference_port,
         action_storage_port,
         action_pdf_port,
         attached_action_port,
-    );
+    )
+    .with_action_definitions(action_definitions);
+    let mapping_template_service =
+        MappingTemplateService::new(action_service.clone(), mapping_template_port);
 
     HttpServer::new(move || {
         let cors = Cors::permissive(); // FIXME: Configure for production.
@@ -147,14 +174,41 @@ async fn main() -> Result<()> {
             .openapi(ApiDoc::openapi())
             .app_data(web::Data::new(sheet_service.clone()))
             .app_data(web::Data::new(action_service.clone()))
+            .app_data(web::Data::new(mapping_template_service.clone()))
             .app_data(web::Data::new(cleanup_service.clone()))
             .service(health_check)
             .service(upload_sheet)
             .service(download_sheet)
             .service(get_sheet_form_fields)
             .service(attach_calculation_action)
+            .service(attach_calculation_actions)
+            .service(regenerate_sheet)
             .service(list_action_types)
             .service(list_attached_actions)
+            .service(get_action_dependency_graph)
+            .service(suggest_calculation_actions)
+            .service(preview_calculations)
+            .service(export_computed_sheet)
+            .service(create_mapping_template)
+            .service(list_mapping_templates)
+            .service(apply_mapping_template)
+            .service(update_calculation_action)
+            .service(detach_calculation_action)
+            .service(list_game_systems)
+            .service(list_system_action_types)
+            .service(attach_system_calculation_action)
+            .service(attach_system_calculation_actions)
+            .service(list_system_attached_actions)
+            .service(regenerate_system_sheet)
+            .service(preview_system_calculations)
+            .service(export_system_computed_sheet)
+            .service(update_system_calculation_action)
+            .service(detach_system_calculation_action)
+            .service(get_system_action_dependency_graph)
+            .service(suggest_system_calculation_actions)
+            .service(create_system_mapping_template)
+            .service(list_system_mapping_templates)
+            .service(apply_system_mapping_template)
             .openapi_service(|api| {
                 SwaggerUi::new("/swagger-ui/{_:.*}").url("/api/openapi.json", api)
             })
@@ -1,8 +1,13 @@
-use actions_web::handler::{ActionTypeMetadataDto, AttachedActionResponse, FieldRoleMetadataDto};
-use common::error::ProblemDetails;
+use actions_web::handler::{
+    ActionSuggestionDto, ActionTypeMetadataDto, AttachActionResultDto, AttachedActionResponse,
+    CreateMappingTemplateRequest, DependencyGraphResponse, FieldDependencyDto, FieldKindDto,
+    FieldRoleMetadataDto, GameSystemDto, MappingSuggestionsResponse, MappingTemplateResponse,
+    PreviewRequest, PreviewResultDto, PreviewValueDto,
+};
+use common::error::{InvalidParam, ProblemDetails};
 use sheets_web::handler::{
-    DownloadSheetResponse, ListSheetFieldsResponse, SheetFieldDto, UploadSheetRequest,
-    UploadSheetResponse,
+    DownloadSheetResponse, ListSheetFieldsResponse, SheetFieldDto, SheetFieldFlagsDto,
+    SheetFieldKindDto, UploadSheetRequest, UploadSheetResponse,
 };
 use utoipa::OpenApi;
 
@@ -16,8 +21,34 @@ use crate::health::HealthResponse;
         sheets_web::handler::download_sheet,
         sheets_web::handler::get_sheet_form_fields,
         actions_web::handler::attach_calculation_action,
+        actions_web::handler::attach_calculation_actions,
+        actions_web::handler::regenerate_sheet,
         actions_web::handler::list_action_types,
         actions_web::handler::list_attached_actions,
+        actions_web::handler::get_action_dependency_graph,
+        actions_web::handler::suggest_calculation_actions,
+        actions_web::handler::preview_calculations,
+        actions_web::handler::export_computed_sheet,
+        actions_web::handler::create_mapping_template,
+        actions_web::handler::list_mapping_templates,
+        actions_web::handler::apply_mapping_template,
+        actions_web::handler::update_calculation_action,
+        actions_web::handler::detach_calculation_action,
+        actions_web::handler::list_game_systems,
+        actions_web::handler::list_system_action_types,
+        actions_web::handler::attach_system_calculation_action,
+        actions_web::handler::attach_system_calculation_actions,
+        actions_web::handler::list_system_attached_actions,
+        actions_web::handler::regenerate_system_sheet,
+        actions_web::handler::preview_system_calculations,
+        actions_web::handler::export_system_computed_sheet,
+        actions_web::handler::update_system_calculation_action,
+        actions_web::handler::detach_system_calculation_action,
+        actions_web::handler::get_system_action_dependency_graph,
+        actions_web::handler::suggest_system_calculation_actions,
+        actions_web::handler::create_system_mapping_template,
+        actions_web::handler::list_system_mapping_templates,
+        actions_web::handler::apply_system_mapping_template,
     ),
     components(schemas(
         HealthResponse,
@@ -26,15 +57,31 @@ use crate::health::HealthResponse;
         DownloadSheetResponse,
         ListSheetFieldsResponse,
         SheetFieldDto,
+        SheetFieldKindDto,
+        SheetFieldFlagsDto,
         ProblemDetails,
+        InvalidParam,
         AttachedActionResponse,
+        AttachActionResultDto,
         ActionTypeMetadataDto,
         FieldRoleMetadataDto,
+        FieldKindDto,
+        DependencyGraphResponse,
+        FieldDependencyDto,
+        MappingSuggestionsResponse,
+        ActionSuggestionDto,
+        PreviewRequest,
+        PreviewResultDto,
+        PreviewValueDto,
+        CreateMappingTemplateRequest,
+        MappingTemplateResponse,
+        GameSystemDto,
     )),
     tags(
         (name = "Health", description = "Health check endpoint"),
         (name = "Sheets", description = "Operations related to form-fillable PDF sheets"),
         (name = "DnD 5e", description = "Operations related to attaching calculation scripts to D&D 5e character sheet's AcroForm fields"),
+        (name = "Game Systems", description = "Operations related to attaching calculation scripts to character sheets of a given game system, e.g. Pathfinder 2e"),
     ),
     info(
         title = "Form Forge API",
@@ -5,12 +5,25 @@ mod test_utils;
 mod tests {
     use crate::test_utils;
     use crate::test_utils::{
-        AsyncTestContext, read_document_javascript, read_field_calculation_js,
+        AsyncTestContext, has_field_calculation_js, read_document_javascript,
+        read_field_calculation_js, read_field_value,
     };
     use actions_core::action::CalculationAction;
-    use actions_core::ports::driving::ActionService;
+    use actions_core::ports::driving::{ActionService, MappingTemplateService};
+    use actions_core::system::{Dnd5e, GameSystem, Pathfinder2e};
     use actions_pdf::adapter::PdfActionAdapter;
-    use actions_web::handler::attach_calculation_action;
+    use actions_web::handler::{
+        AttachedActionResponse, DependencyGraphResponse, MappingSuggestionsResponse,
+        MappingTemplateResponse, apply_mapping_template, attach_calculation_action,
+        attach_calculation_actions, attach_system_calculation_action, create_mapping_template,
+        create_system_mapping_template, detach_calculation_action,
+        detach_system_calculation_action, export_computed_sheet, export_system_computed_sheet,
+        get_action_dependency_graph, get_system_action_dependency_graph, list_attached_actions,
+        list_mapping_templates, list_system_action_types, list_system_attached_actions,
+        list_system_mapping_templates, preview_calculations, preview_system_calculations,
+        regenerate_sheet, suggest_calculation_actions, update_calculation_action,
+        update_system_calculation_action,
+    };
     use actix_web::http::StatusCode;
     use actix_web::test;
     use common_telemetry as telemetry;
@@ -60,10 +73,7 @@ mod tests {
         );
         telemetry::initialize().expect("initialize telemetry");
         let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_action, download_sheet]);
-        let expected_js = include_str!(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/../../crates/actions_core/js/dnd-helpers.js"
-        ));
+        let expected_js = Dnd5e.helpers_js();
         //endregion
 
         //region Sheet upload
@@ -151,10 +161,7 @@ mod tests {
         );
         telemetry::initialize().expect("initialize telemetry");
         let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_action, download_sheet]);
-        let expected_js = include_str!(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/../../crates/actions_core/js/dnd-helpers.js"
-        ));
+        let expected_js = Dnd5e.helpers_js();
         //endregion
 
         //region Sheet upload
@@ -247,10 +254,7 @@ mod tests {
         );
         telemetry::initialize().expect("initialize telemetry");
         let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_action, download_sheet]);
-        let expected_js = include_str!(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/../../crates/actions_core/js/dnd-helpers.js"
-        ));
+        let expected_js = Dnd5e.helpers_js();
         //endregion
 
         //region Sheet upload
@@ -315,4 +319,1010 @@ mod tests {
         );
         //endregion
     }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_detach_dnd5e_calc_script_from_uploaded_sheet(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port.clone());
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_action, list_attached_actions, detach_calculation_action]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach ability mod calc script
+        let req = test::TestRequest::put()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .set_json(CalculationAction::ability_modifier("STR", "STRmod"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .to_request();
+        let attached: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert_eq!(attached.len(), 1);
+        //endregion
+
+        //region Detach calc script
+        let req = test::TestRequest::delete()
+            .uri(&format!("/dnd5e/{}/actions/{}", sheet_id, attached[0].id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        //endregion
+
+        //region Verify action and script removed
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .to_request();
+        let attached: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert!(attached.is_empty());
+
+        let sheet_ref = reference_port
+            .find_by_id(&sheet_id)
+            .await
+            .expect("get sheet reference");
+        let pdf_path = <SheetS3Storage as SheetStoragePort>::read(&s3_storage, sheet_ref.path)
+            .await
+            .expect("read PDF from S3");
+        assert!(!has_field_calculation_js(&pdf_path, "STRmod"));
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_move_dnd5e_calc_script_to_new_target_field(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port.clone());
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_action, list_attached_actions, update_calculation_action]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach ability mod calc script
+        let req = test::TestRequest::put()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .set_json(CalculationAction::ability_modifier("STR", "STRmod"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .to_request();
+        let attached: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert_eq!(attached.len(), 1);
+        //endregion
+
+        //region Move calc script to another field
+        let req = test::TestRequest::put()
+            .uri(&format!("/dnd5e/{}/actions/{}", sheet_id, attached[0].id))
+            .set_json(CalculationAction::ability_modifier("DEX", "DEXmod "))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        //endregion
+
+        //region Verify action and script moved
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .to_request();
+        let updated: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].id, attached[0].id);
+        assert_eq!(updated[0].target_field, "DEXmod ");
+
+        let sheet_ref = reference_port
+            .find_by_id(&sheet_id)
+            .await
+            .expect("get sheet reference");
+        let pdf_path = <SheetS3Storage as SheetStoragePort>::read(&s3_storage, sheet_ref.path)
+            .await
+            .expect("read PDF from S3");
+        assert!(!has_field_calculation_js(&pdf_path, "STRmod"));
+        assert!(has_field_calculation_js(&pdf_path, "DEXmod "));
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_preview_dnd5e_calculations_with_overridden_values(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port);
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_actions, preview_calculations]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach chained calc scripts
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/actions/batch", sheet_id))
+            .set_json(vec![
+                CalculationAction::skill_modifier(
+                    "STRmod",
+                    "Check Box 26",
+                    None::<String>,
+                    None::<String>,
+                    "ProfBonus",
+                    "Athletics",
+                ),
+                CalculationAction::ability_modifier("STR", "STRmod"),
+            ])
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        //endregion
+
+        //region Preview with overridden values
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/preview", sheet_id))
+            .set_json(serde_json::json!({ "values": { "STR": "15", "ProfBonus": "2", "Check Box 26": "Yes" } }))
+            .to_request();
+        let results: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            results,
+            vec![
+                serde_json::json!({ "actionType": "AbilityModifier", "targetField": "STRmod", "value": 2.0 }),
+                serde_json::json!({ "actionType": "SkillModifier", "targetField": "Athletics", "value": 4.0 }),
+            ]
+        );
+        //endregion
+
+        //region Reject value of unknown field
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/preview", sheet_id))
+            .set_json(serde_json::json!({ "values": { "Strength": "15" } }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_export_dnd5e_sheet_with_computed_values_baked_in(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port.clone());
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_action, export_computed_sheet]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach ability mod calc script
+        let req = test::TestRequest::put()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .set_json(CalculationAction::ability_modifier("STR", "STRmod"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        //endregion
+
+        //region Export sheet with computed values
+        let req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/export", sheet_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let pdf = test::read_body(resp).await;
+        let pdf_path = std::env::temp_dir().join(format!("{}-computed.pdf", sheet_id));
+        std::fs::write(&pdf_path, &pdf).expect("write exported PDF");
+        //endregion
+
+        //region Verify value baked in and script removed
+        // The blank STR score reads as 0, so the modifier is floor((0 - 10) / 2)
+        assert_eq!(
+            read_field_value(&pdf_path, "STRmod"),
+            Some("-5".to_string())
+        );
+        assert!(!has_field_calculation_js(&pdf_path, "STRmod"));
+
+        let sheet_ref = reference_port
+            .find_by_id(&sheet_id)
+            .await
+            .expect("get sheet reference");
+        let stored_path = <SheetS3Storage as SheetStoragePort>::read(&s3_storage, sheet_ref.path)
+            .await
+            .expect("read PDF from S3");
+        assert!(has_field_calculation_js(&stored_path, "STRmod"));
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_attach_dnd5e_calc_scripts_in_batch(#[future] async_ctx: AsyncTestContext) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port.clone());
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_actions, list_attached_actions]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach calc scripts in batch
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/actions/batch", sheet_id))
+            .set_json(vec![
+                CalculationAction::ability_modifier("STR", "STRmod"),
+                CalculationAction::ability_modifier("DEX", "DoesNotExist"),
+                CalculationAction::ability_modifier("CON", "CONmod"),
+            ])
+            .to_request();
+        let results: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["targetField"], "STRmod");
+        assert!(results[0]["id"].is_string());
+        assert_eq!(results[1]["targetField"], "DoesNotExist");
+        assert_eq!(
+            results[1]["error"]["type"],
+            "/problems/invalid-field-mapping"
+        );
+        assert_eq!(
+            results[1]["error"]["invalidParams"][0]["name"],
+            "abilityModifierFieldName"
+        );
+        assert_eq!(results[2]["targetField"], "CONmod");
+        assert!(results[2]["id"].is_string());
+        //endregion
+
+        //region Verify attached actions and sheet
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .to_request();
+        let attached: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert_eq!(attached.len(), 2);
+
+        let sheet_ref = reference_port
+            .find_by_id(&sheet_id)
+            .await
+            .expect("get sheet reference");
+        let pdf_path = <SheetS3Storage as SheetStoragePort>::read(&s3_storage, sheet_ref.path)
+            .await
+            .expect("read PDF from S3");
+        assert_eq!(read_document_javascript(&pdf_path).len(), 1);
+        assert_eq!(
+            read_field_calculation_js(&pdf_path, "STRmod"),
+            r#"calculateModifierFromScore("STR");"#
+        );
+        assert_eq!(
+            read_field_calculation_js(&pdf_path, "CONmod"),
+            r#"calculateModifierFromScore("CON");"#
+        );
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_regenerate_dnd5e_sheet_from_original_upload(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port.clone());
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_actions, regenerate_sheet]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach calc scripts in batch
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/actions/batch", sheet_id))
+            .set_json(vec![
+                CalculationAction::ability_modifier("STR", "STRmod"),
+                CalculationAction::ability_modifier("CON", "CONmod"),
+            ])
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        //endregion
+
+        //region Regenerate sheet
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/regenerate", sheet_id))
+            .to_request();
+        let results: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result["id"].is_string()));
+        //endregion
+
+        //region Verify regenerated sheet and untouched original
+        let sheet_ref = reference_port
+            .find_by_id(&sheet_id)
+            .await
+            .expect("get sheet reference");
+        let pdf_path =
+            <SheetS3Storage as SheetStoragePort>::read(&s3_storage, sheet_ref.path.clone())
+                .await
+                .expect("read PDF from S3");
+        assert_eq!(read_document_javascript(&pdf_path).len(), 1);
+        assert_eq!(
+            read_field_calculation_js(&pdf_path, "STRmod"),
+            r#"calculateModifierFromScore("STR");"#
+        );
+        assert_eq!(
+            read_field_calculation_js(&pdf_path, "CONmod"),
+            r#"calculateModifierFromScore("CON");"#
+        );
+
+        let original =
+            <SheetS3Storage as actions_core::ports::driven::SheetStoragePort>::read_original(
+                &s3_storage,
+                sheet_ref.path,
+            )
+            .await
+            .expect("read original PDF from S3");
+        assert!(read_document_javascript(original.path()).is_empty());
+        assert!(!has_field_calculation_js(original.path(), "STRmod"));
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_report_dependency_graph_and_reject_calculation_cycle(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port);
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_actions, attach_calculation_action, get_action_dependency_graph]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Attach chained calculations
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/actions/batch", sheet_id))
+            .set_json(vec![
+                CalculationAction::ability_modifier("STR", "STRmod"),
+                CalculationAction::saving_throw_modifier(
+                    "STRmod",
+                    "Check Box 11",
+                    "ProfBonus",
+                    "ST Strength",
+                ),
+            ])
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        //endregion
+
+        //region Verify dependency graph
+        let graph_req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions/graph", sheet_id))
+            .to_request();
+        let graph: DependencyGraphResponse = test::call_and_read_body_json(&app, graph_req).await;
+        assert_eq!(
+            graph.fields,
+            vec!["Check Box 11", "ProfBonus", "ST Strength", "STR", "STRmod"]
+        );
+        assert_eq!(graph.dependencies.len(), 4);
+        //endregion
+
+        //region Reject calculation cycle
+        let req = test::TestRequest::put()
+            .uri(&format!("/dnd5e/{}/actions", sheet_id))
+            .set_json(CalculationAction::ability_modifier("ST Strength", "STR"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let problem: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(problem["type"], "/problems/calculation-cycle");
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_attach_calc_script_with_helpers_of_the_game_system(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port.clone());
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let mapping_template_port: Arc<dyn actions_core::ports::driven::MappingTemplatePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        let mapping_template_service =
+            MappingTemplateService::new(action_service.clone(), mapping_template_port);
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service, mapping_template_service], services: [upload_sheet, attach_system_calculation_action, list_system_action_types, preview_system_calculations, export_system_computed_sheet, list_system_attached_actions, update_system_calculation_action, create_system_mapping_template, list_system_mapping_templates, get_system_action_dependency_graph, detach_system_calculation_action]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region List the system's action types
+        let req = test::TestRequest::get()
+            .uri("/systems/pf2e/action-types")
+            .to_request();
+        let catalog: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(catalog.as_array().map(Vec::len), Some(4));
+
+        let req = test::TestRequest::get()
+            .uri("/systems/dnd4e/action-types")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        //endregion
+
+        //region Attach ability mod calc script
+        let req = test::TestRequest::put()
+            .uri(&format!("/systems/pf2e/{}/actions", sheet_id))
+            .set_json(CalculationAction::ability_modifier("STR", "STRmod"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let sheet_ref = reference_port
+            .find_by_id(&sheet_id)
+            .await
+            .expect("get sheet reference");
+        let pdf_path = <SheetS3Storage as SheetStoragePort>::read(&s3_storage, sheet_ref.path)
+            .await
+            .expect("read PDF from S3");
+        let actual_doc_level_js = read_document_javascript(&pdf_path);
+        assert_eq!(actual_doc_level_js.len(), 1);
+        assert_eq!(actual_doc_level_js[0].1, Pathfinder2e.helpers_js());
+        //endregion
+
+        //region Preview calculations of the system's sheet
+        let req = test::TestRequest::post()
+            .uri(&format!("/systems/pf2e/{}/preview", sheet_id))
+            .set_json(serde_json::json!({ "values": { "STR": "18" } }))
+            .to_request();
+        let results: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            results,
+            vec![
+                serde_json::json!({ "actionType": "AbilityModifier", "targetField": "STRmod", "value": 4.0 })
+            ]
+        );
+        //endregion
+
+        //region Export the system's sheet with computed values
+        let req = test::TestRequest::get()
+            .uri(&format!("/systems/pf2e/{}/export", sheet_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let pdf = test::read_body(resp).await;
+        let exported_path = std::env::temp_dir().join(format!("{}-pf2e-computed.pdf", sheet_id));
+        std::fs::write(&exported_path, &pdf).expect("write exported PDF");
+        // The blank STR score reads as 0, so the modifier is floor((0 - 10) / 2)
+        assert_eq!(
+            read_field_value(&exported_path, "STRmod"),
+            Some("-5".to_string())
+        );
+        //endregion
+
+        //region Reject action the system does not provide
+        let req = test::TestRequest::put()
+            .uri(&format!("/systems/pf2e/{}/actions", sheet_id))
+            .set_json(CalculationAction::passive_score(
+                "Perception",
+                None::<&str>,
+                None,
+                None,
+                "Passive",
+            ))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let problem: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(problem["type"], "/problems/unsupported-action");
+        //endregion
+
+        //region Move calc script to another field
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/systems/pf2e/{}/actions", sheet_id))
+            .to_request();
+        let attached: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert_eq!(attached.len(), 1);
+
+        let req = test::TestRequest::put()
+            .uri(&format!(
+                "/systems/pf2e/{}/actions/{}",
+                sheet_id, attached[0].id
+            ))
+            .set_json(CalculationAction::ability_modifier("DEX", "DEXmod "))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/systems/pf2e/{}/actions", sheet_id))
+            .to_request();
+        let updated: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].id, attached[0].id);
+        assert_eq!(updated[0].target_field, "DEXmod ");
+        //endregion
+
+        //region Save the mapping as a template of the system
+        let req = test::TestRequest::post()
+            .uri(&format!("/systems/pf2e/{}/templates", sheet_id))
+            .set_json(serde_json::json!({ "name": "Official sheet for PF2e" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let template: MappingTemplateResponse = test::read_body_json(resp).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/systems/pf2e/{}/templates", sheet_id))
+            .to_request();
+        let templates: Vec<MappingTemplateResponse> =
+            test::call_and_read_body_json(&app, req).await;
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].id, template.id);
+        //endregion
+
+        //region Detach calc script
+        let req = test::TestRequest::get()
+            .uri(&format!("/systems/pf2e/{}/actions/graph", sheet_id))
+            .to_request();
+        let graph: DependencyGraphResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(graph.dependencies.len(), 1);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!(
+                "/systems/pf2e/{}/actions/{}",
+                sheet_id, attached[0].id
+            ))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let list_req = test::TestRequest::get()
+            .uri(&format!("/systems/pf2e/{}/actions", sheet_id))
+            .to_request();
+        let remaining: Vec<AttachedActionResponse> =
+            test::call_and_read_body_json(&app, list_req).await;
+        assert!(remaining.is_empty());
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_suggest_dnd5e_calc_scripts_for_official_sheet(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port);
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service], services: [upload_sheet, attach_calculation_actions, suggest_calculation_actions]);
+        //endregion
+
+        //region Sheet upload
+        let (multipart_header, multipart_body) =
+            test_utils::dnd5e_sheet_multipart_form_data().build();
+        let upload_req = test::TestRequest::post()
+            .uri("/sheets")
+            .insert_header(multipart_header)
+            .set_payload(multipart_body)
+            .to_request();
+        let upload_resp: UploadSheetResponse =
+            test::call_and_read_body_json(&app, upload_req).await;
+        let sheet_id = upload_resp.id;
+        //endregion
+
+        //region Suggest actions
+        let req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/suggestions", sheet_id))
+            .to_request();
+        let suggestions: MappingSuggestionsResponse =
+            test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            suggestions.sheet_name.as_deref(),
+            Some("D&D 5e Character Sheet (Wizards of the Coast)")
+        );
+        assert_eq!(suggestions.suggestions.len(), 34);
+        //endregion
+
+        //region Attach every suggestion
+        let actions: Vec<CalculationAction> = suggestions
+            .suggestions
+            .into_iter()
+            .map(|suggestion| suggestion.action)
+            .collect();
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/actions/batch", sheet_id))
+            .set_json(actions)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/suggestions", sheet_id))
+            .to_request();
+        let suggestions: MappingSuggestionsResponse =
+            test::call_and_read_body_json(&app, req).await;
+        assert!(suggestions.suggestions.is_empty());
+        //endregion
+    }
+
+    #[rstest]
+    #[actix_web::test]
+    async fn test_should_apply_dnd5e_mapping_template_to_new_upload_of_same_sheet(
+        #[future] async_ctx: AsyncTestContext,
+    ) {
+        //region Setup
+        let async_ctx = async_ctx.await;
+        let reference_port: Arc<dyn SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let sheet_pdf_port: Arc<dyn SheetPdfPort> = Arc::new(SheetsPdf);
+        let s3_storage: Arc<SheetS3Storage> = async_ctx.s3_storage;
+        let storage_port: Arc<dyn SheetStoragePort> = s3_storage.clone();
+        let sheet_service = SheetService::new(sheet_pdf_port, storage_port, reference_port);
+        let action_storage_port: Arc<dyn actions_core::ports::driven::SheetStoragePort> =
+            s3_storage.clone();
+        let action_reference_port: Arc<dyn actions_core::ports::driven::SheetReferencePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let action_pdf_port: Arc<dyn actions_core::ports::driven::ActionPdfPort> =
+            Arc::new(PdfActionAdapter);
+        let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool.clone()));
+        let mapping_template_port: Arc<dyn actions_core::ports::driven::MappingTemplatePort> =
+            Arc::new(SheetReferenceDb::new(async_ctx.pool));
+        let action_service = ActionService::new(
+            action_reference_port,
+            action_storage_port,
+            action_pdf_port,
+            attached_action_port,
+        );
+        let mapping_template_service =
+            MappingTemplateService::new(action_service.clone(), mapping_template_port);
+        telemetry::initialize().expect("initialize telemetry");
+        let app = test_utils::app!(app_data: [sheet_service, action_service, mapping_template_service], services: [upload_sheet, attach_calculation_actions, list_attached_actions, create_mapping_template, list_mapping_templates, apply_mapping_template]);
+        //endregion
+
+        //region Upload the sheet twice
+        let mut sheet_ids = Vec::new();
+        for _ in 0..2 {
+            let (multipart_header, multipart_body) =
+                test_utils::dnd5e_sheet_multipart_form_data().build();
+            let upload_req = test::TestRequest::post()
+                .uri("/sheets")
+                .insert_header(multipart_header)
+                .set_payload(multipart_body)
+                .to_request();
+            let upload_resp: UploadSheetResponse =
+                test::call_and_read_body_json(&app, upload_req).await;
+            sheet_ids.push(upload_resp.id);
+        }
+        let (mapped_sheet_id, new_sheet_id) = (sheet_ids[0], sheet_ids[1]);
+        //endregion
+
+        //region Save the mapping of the first upload as template
+        let actions = vec![
+            CalculationAction::ability_modifier("STR", "STRmod"),
+            CalculationAction::ability_modifier("DEX", "DEXmod "),
+        ];
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/actions/batch", mapped_sheet_id))
+            .set_json(actions)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/dnd5e/{}/templates", mapped_sheet_id))
+            .set_json(serde_json::json!({ "name": "Official 5e sheet" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let template: MappingTemplateResponse = test::read_body_json(resp).await;
+        assert_eq!(template.actions.len(), 2);
+        //endregion
+
+        //region Apply the template to the second upload
+        let req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/templates", new_sheet_id))
+            .to_request();
+        let templates: Vec<MappingTemplateResponse> =
+            test::call_and_read_body_json(&app, req).await;
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].id, template.id);
+
+        let req = test::TestRequest::post()
+            .uri(&format!(
+                "/dnd5e/{}/templates/{}/apply",
+                new_sheet_id, template.id
+            ))
+            .to_request();
+        let results: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.get("id").is_some()));
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/dnd5e/{}/actions", new_sheet_id))
+            .to_request();
+        let attached: Vec<AttachedActionResponse> = test::call_and_read_body_json(&app, req).await;
+        let targets: Vec<_> = attached.iter().map(|a| a.target_field.as_str()).collect();
+        assert_eq!(targets, vec!["STRmod", "DEXmod "]);
+        //endregion
+    }
 }
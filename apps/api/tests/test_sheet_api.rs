@@ -15,8 +15,8 @@ mod tests {
     use sheets_db::adapter::SheetReferenceDb;
     use sheets_pdf::adapter::SheetsPdf;
     use sheets_web::handler::{
-        DownloadSheetResponse, ListSheetFieldsResponse, UploadSheetResponse, download_sheet,
-        get_sheet_form_fields, upload_sheet,
+        DownloadSheetResponse, ListSheetFieldsResponse, SheetFieldKindDto, UploadSheetResponse,
+        download_sheet, get_sheet_form_fields, upload_sheet,
     };
     use std::sync::Arc;
     use uuid::Uuid;
@@ -144,6 +144,14 @@ mod tests {
             .to_request();
         let resp: ListSheetFieldsResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(resp.fields.len(), 240);
+        // Checkboxes are listed whatever their field flags, hence more fields than text and choice
+        assert_eq!(resp.fields.len(), 332);
+        let proficiency = resp
+            .fields
+            .iter()
+            .find(|field| field.name == "Check Box 11")
+            .expect("proficiency checkbox listed");
+        assert!(matches!(proficiency.kind, SheetFieldKindDto::Checkbox));
+        assert_eq!(proficiency.page_index, Some(0));
     }
 }
@@ -2,7 +2,7 @@
 
 use common::multipart_form::MultipartFormDataBuilder;
 use common_pdf::find_form_field_by_name;
-use lopdf::{Document, Object};
+use lopdf::{Document, Object, decode_text_string};
 use sheets_s3::adapter::SheetS3Storage;
 use sheets_s3::config::S3Config;
 use std::path::{Path, PathBuf};
@@ -315,4 +315,67 @@ pub fn read_field_calculation_js(path: &Path, field_name: &str) -> String {
     }
 }
 
+/// Returns whether the field carries a `/AA /C` calculation action.
+pub fn has_field_calculation_js(path: &Path, field_name: &str) -> bool {
+    let doc = Document::load(path).expect("failed to load PDF document");
+
+    let acroform = doc
+        .catalog()
+        .expect("failed to get catalog")
+        .get_deref(b"AcroForm", &doc)
+        .expect("catalog missing 'AcroForm'")
+        .as_dict()
+        .expect("'AcroForm' object is not a dictionary");
+    let fields_array_id = acroform
+        .get(b"Fields")
+        .expect("'AcroForm' missing 'Fields'")
+        .as_reference()
+        .expect("'Fields' is not a reference");
+
+    let field_id =
+        find_form_field_by_name(&doc, fields_array_id, field_name).expect("field not found");
+    let field_dict = doc
+        .get_object(field_id)
+        .expect("failed to get field object")
+        .as_dict()
+        .expect("field object is not a dictionary");
+
+    field_dict
+        .get(b"AA")
+        .and_then(Object::as_dict)
+        .map(|aa| aa.has(b"C"))
+        .unwrap_or(false)
+}
+
+/// Returns the decoded `/V` value of the field, if it has one.
+pub fn read_field_value(path: &Path, field_name: &str) -> Option<String> {
+    let doc = Document::load(path).expect("failed to load PDF document");
+
+    let acroform = doc
+        .catalog()
+        .expect("failed to get catalog")
+        .get_deref(b"AcroForm", &doc)
+        .expect("catalog missing 'AcroForm'")
+        .as_dict()
+        .expect("'AcroForm' object is not a dictionary");
+    let fields_array_id = acroform
+        .get(b"Fields")
+        .expect("'AcroForm' missing 'Fields'")
+        .as_reference()
+        .expect("'Fields' is not a reference");
+
+    let field_id =
+        find_form_field_by_name(&doc, fields_array_id, field_name).expect("field not found");
+    let field_dict = doc
+        .get_object(field_id)
+        .expect("failed to get field object")
+        .as_dict()
+        .expect("field object is not a dictionary");
+
+    field_dict
+        .get(b"V")
+        .ok()
+        .and_then(|value| decode_text_string(value).ok())
+}
+
 pub(crate) use app;
@@ -1,9 +1,11 @@
+use actions_core::ports::driven::LocalSheet;
 use async_trait::async_trait;
 use sheets_core::error::SheetError;
 use sheets_core::sheet::SheetReference;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use tracing::{debug, info, instrument};
+use uuid::Uuid;
 
 pub struct SheetFsStorage {
     base_dir: PathBuf,
@@ -39,6 +41,17 @@ impl sheets_core::ports::driven::SheetStoragePort for SheetFsStorage {
 
         debug!(from = %sheet_reference.path.display(), to = %target_path.display(), "copying sheet to local storage");
 
+        let original_path = original_copy_path(&target_path);
+        if let Some(parent) = original_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(SheetError::StorageError)?;
+        }
+
+        fs::copy(&sheet_reference.path, &original_path)
+            .await
+            .map_err(SheetError::StorageError)?;
+
         fs::copy(&sheet_reference.path, &target_path)
             .await
             .map_err(SheetError::StorageError)?;
@@ -84,37 +97,87 @@ impl sheets_core::ports::driven::SheetStoragePort for SheetFsStorage {
 
 #[async_trait]
 impl actions_core::ports::driven::SheetStoragePort for SheetFsStorage {
+    /// Copies the stored sheet to a working file next to it so edits never touch the stored
+    /// sheet until `write` promotes them.
     #[instrument(name = "fs.read.action_port", skip(self), level = "info", err, fields(path = %path.display()))]
-    async fn read(&self, path: PathBuf) -> Result<PathBuf, actions_core::error::ActionError> {
-        <SheetFsStorage as sheets_core::ports::driven::SheetStoragePort>::read(self, path)
-            .await
-            .map_err(|_| actions_core::error::ActionError::FileNotFound)
+    async fn read(&self, path: PathBuf) -> Result<LocalSheet, actions_core::error::ActionError> {
+        let path =
+            <SheetFsStorage as sheets_core::ports::driven::SheetStoragePort>::read(self, path)
+                .await
+                .map_err(|_| actions_core::error::ActionError::FileNotFound)?;
+
+        create_working_copy(&path).await
     }
 
-    #[instrument(name = "fs.write.action_port", skip(self), level = "info", err, fields(local_path = %local_path.display(), storage_path = %storage_path.display()))]
+    #[instrument(name = "fs.read_original.action_port", skip(self), level = "info", err, fields(storage_path = %storage_path.display()))]
+    async fn read_original(
+        &self,
+        storage_path: PathBuf,
+    ) -> Result<LocalSheet, actions_core::error::ActionError> {
+        let original_path = original_copy_path(&storage_path);
+        if !fs::try_exists(&original_path).await.unwrap_or(false) {
+            return Err(actions_core::error::ActionError::FileNotFound);
+        }
+
+        create_working_copy(&original_path).await
+    }
+
+    /// Promotes the working file over the stored sheet with an atomic rename. The working file
+    /// is removed if the rename fails.
+    #[instrument(name = "fs.write.action_port", skip(self, local_sheet), level = "info", err, fields(local_path = %local_sheet.path().display(), storage_path = %storage_path.display()))]
     async fn write(
         &self,
-        local_path: PathBuf,
+        local_sheet: LocalSheet,
         storage_path: PathBuf,
     ) -> Result<(), actions_core::error::ActionError> {
-        if local_path != storage_path {
-            fs::copy(&local_path, &storage_path).await.map_err(|_| {
+        fs::rename(local_sheet.path(), &storage_path)
+            .await
+            .map_err(|_| {
                 actions_core::error::ActionError::InvalidAction(
                     "failed to copy modified file back to storage".to_string(),
                 )
             })?;
-        }
 
         info!(path = %storage_path.display(), "wrote modified sheet to filesystem");
         Ok(())
     }
 }
 
+/// Path of the pristine upload kept next to the stored sheet, e.g. `{uuid}/original/{name}.pdf`.
+fn original_copy_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default();
+    path.with_file_name("original").join(file_name)
+}
+
+/// Copies `source` to a uniquely named working file next to it, deleted again when the returned
+/// copy is dropped without being written back.
+async fn create_working_copy(
+    source: &Path,
+) -> Result<LocalSheet, actions_core::error::ActionError> {
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let working = LocalSheet::new(source.with_file_name(format!(
+        ".{}.{}.working",
+        file_name,
+        Uuid::new_v4()
+    )));
+
+    fs::copy(source, working.path()).await.map_err(|_| {
+        actions_core::error::ActionError::InvalidAction(
+            "failed to create working copy of sheet".to_string(),
+        )
+    })?;
+
+    debug!(path = %working.path().display(), "created working copy of sheet");
+    Ok(working)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use sheets_core::ports::driven::SheetStoragePort;
-    use uuid::Uuid;
 
     #[tokio::test]
     async fn test_create_and_read() {
@@ -159,4 +222,195 @@ mod tests {
         assert!(storage.exists(&file).await.unwrap());
         assert!(!storage.exists(Path::new("/nonexistent")).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_action_port_promotes_working_copy_only_on_write() {
+        use actions_core::ports::driven::SheetStoragePort as ActionSheetStoragePort;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = SheetFsStorage::new(tmp.path().to_path_buf());
+
+        let stored = tmp.path().join("sheet.pdf");
+        fs::write(&stored, b"original").await.unwrap();
+
+        let working = ActionSheetStoragePort::read(&storage, stored.clone())
+            .await
+            .unwrap();
+        let working_path = working.path().to_path_buf();
+        assert_ne!(working_path, stored);
+
+        fs::write(&working_path, b"modified").await.unwrap();
+        assert_eq!(fs::read(&stored).await.unwrap(), b"original");
+
+        ActionSheetStoragePort::write(&storage, working, stored.clone())
+            .await
+            .unwrap();
+        assert_eq!(fs::read(&stored).await.unwrap(), b"modified");
+        assert!(!working_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_action_port_reads_original_kept_on_create() {
+        use actions_core::ports::driven::SheetStoragePort as ActionSheetStoragePort;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = SheetFsStorage::new(tmp.path().join("sheets"));
+
+        let source = tmp.path().join("source.pdf");
+        fs::write(&source, b"original").await.unwrap();
+
+        let sheet_ref = SheetReference::new(Uuid::new_v4(), "sheet", "abc", Some("pdf"), source);
+        let stored = storage.create(sheet_ref).await.unwrap().path;
+        fs::write(&stored, b"enhanced").await.unwrap();
+
+        let original = ActionSheetStoragePort::read_original(&storage, stored.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(fs::read(original.path()).await.unwrap(), b"original");
+        assert_eq!(fs::read(&stored).await.unwrap(), b"enhanced");
+    }
+
+    #[tokio::test]
+    async fn test_action_port_read_original_without_retained_original_returns_error() {
+        use actions_core::ports::driven::SheetStoragePort as ActionSheetStoragePort;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = SheetFsStorage::new(tmp.path().to_path_buf());
+
+        let stored = tmp.path().join("sheet.pdf");
+        fs::write(&stored, b"enhanced").await.unwrap();
+
+        let result = ActionSheetStoragePort::read_original(&storage, stored).await;
+
+        assert_eq!(result, Err(actions_core::error::ActionError::FileNotFound));
+    }
+
+    #[tokio::test]
+    async fn test_action_port_removes_working_copy_not_written_back() {
+        use actions_core::ports::driven::SheetStoragePort as ActionSheetStoragePort;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = SheetFsStorage::new(tmp.path().to_path_buf());
+
+        let stored = tmp.path().join("sheet.pdf");
+        fs::write(&stored, b"original").await.unwrap();
+
+        let working = ActionSheetStoragePort::read(&storage, stored.clone())
+            .await
+            .unwrap();
+        let working_path = working.path().to_path_buf();
+        assert!(working_path.exists());
+
+        drop(working);
+
+        assert!(!working_path.exists());
+        assert_eq!(fs::read(&stored).await.unwrap(), b"original");
+    }
+
+    mod action_service {
+        use super::*;
+        use actions_core::action::{AttachedAction, CalculationAction};
+        use actions_core::error::ActionError;
+        use actions_core::ports::driven::{
+            AttachedActionPort, SheetReference as ActionSheetReference, SheetReferencePort,
+        };
+        use actions_core::ports::driving::ActionService;
+        use actions_core::system::Dnd5e;
+        use actions_pdf::adapter::PdfActionAdapter;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        struct StoredSheet(PathBuf);
+
+        #[async_trait]
+        impl SheetReferencePort for StoredSheet {
+            async fn find_by_id(&self, id: &Uuid) -> Result<ActionSheetReference, ActionError> {
+                Ok(ActionSheetReference::new(*id, self.0.clone()))
+            }
+        }
+
+        struct NoAttachedActions;
+
+        #[async_trait]
+        impl AttachedActionPort for NoAttachedActions {
+            async fn save(&self, _: &AttachedAction) -> Result<(), ActionError> {
+                Ok(())
+            }
+
+            async fn list_by_sheet_id(&self, _: &Uuid) -> Result<Vec<AttachedAction>, ActionError> {
+                Ok(Vec::new())
+            }
+
+            async fn find_by_id(&self, _: &Uuid, id: &Uuid) -> Result<AttachedAction, ActionError> {
+                Err(ActionError::ActionNotFound(*id))
+            }
+
+            async fn update(&self, _: &AttachedAction) -> Result<(), ActionError> {
+                Ok(())
+            }
+
+            async fn delete(&self, _: &Uuid, _: &Uuid) -> Result<(), ActionError> {
+                Ok(())
+            }
+        }
+
+        async fn stored_sheet(tmp: &Path) -> (Uuid, PathBuf, ActionService) {
+            let fixture =
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/nested_fields.pdf");
+            let storage = SheetFsStorage::new(tmp.to_path_buf());
+            let sheet_ref =
+                SheetReference::new(Uuid::new_v4(), "sheet", "sheet", Some("pdf"), fixture);
+            let stored = storage.create(sheet_ref).await.unwrap().path;
+
+            let service = ActionService::new(
+                Arc::new(StoredSheet(stored.clone())),
+                Arc::new(storage),
+                Arc::new(PdfActionAdapter),
+                Arc::new(NoAttachedActions),
+            );
+
+            (Uuid::new_v4(), stored, service)
+        }
+
+        async fn sheet_dir_entries(stored: &Path) -> Vec<String> {
+            let mut entries = fs::read_dir(stored.parent().unwrap()).await.unwrap();
+            let mut names = Vec::new();
+            while let Some(entry) = entries.next_entry().await.unwrap() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+            names.sort();
+            names
+        }
+
+        #[tokio::test]
+        async fn test_preview_leaves_only_sheet_and_original() {
+            let tmp = tempfile::tempdir().unwrap();
+            let (sheet_id, stored, service) = stored_sheet(tmp.path()).await;
+
+            service
+                .preview(&Dnd5e, &sheet_id, HashMap::new())
+                .await
+                .unwrap();
+
+            assert_eq!(sheet_dir_entries(&stored).await, ["original", "sheet.pdf"]);
+        }
+
+        #[tokio::test]
+        async fn test_rejected_attach_leaves_only_sheet_and_original() {
+            let tmp = tempfile::tempdir().unwrap();
+            let (sheet_id, stored, service) = stored_sheet(tmp.path()).await;
+
+            let result = service
+                .attach_calculation_script(
+                    &Dnd5e,
+                    &sheet_id,
+                    CalculationAction::ability_modifier("NoSuchField", "STRmod"),
+                )
+                .await;
+
+            assert!(result.is_err());
+            assert_eq!(sheet_dir_entries(&stored).await, ["original", "sheet.pdf"]);
+        }
+    }
 }
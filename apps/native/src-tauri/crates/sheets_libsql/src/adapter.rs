@@ -37,6 +37,17 @@ CREATE TABLE IF NOT EXISTS failed_sheet_deletions (
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     last_retry_at TEXT
 );
+
+CREATE TABLE IF NOT EXISTS mapping_template (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    game_system TEXT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_mapping_template_fingerprint ON mapping_template (game_system, fingerprint);
 "#;
 
 pub struct SheetListRow {
@@ -330,40 +341,253 @@ impl actions_core::ports::driven::AttachedActionPort for SheetReferenceLibSql {
             .await
             .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?
         {
-            let id: String = row
-                .get(0)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-            let sid: String = row
-                .get(1)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-            let action_type: String = row
-                .get(2)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-            let target_field: String = row
-                .get(3)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-            let mapping_str: String = row
-                .get(4)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-
-            let uuid = Uuid::parse_str(&id)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-            let sheet_uuid = Uuid::parse_str(&sid)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-            let mapping: serde_json::Value = serde_json::from_str(&mapping_str)
-                .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
-
-            actions.push(actions_core::action::AttachedAction {
-                id: uuid,
-                sheet_id: sheet_uuid,
-                action_type,
-                target_field,
-                mapping,
-            });
+            actions.push(attached_action_from_row(&row)?);
         }
 
         Ok(actions)
     }
+
+    #[instrument(name = "libsql.find_attached_action", skip(self), level = "info", fields(%sheet_id, %action_id))]
+    async fn find_by_id(
+        &self,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+    ) -> Result<actions_core::action::AttachedAction, actions_core::error::ActionError> {
+        let conn = self
+            .db
+            .connect()
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let mut rows = conn
+            .query(
+                "SELECT id, sheet_id, action_type, target_field, mapping FROM attached_action WHERE id = ?1 AND sheet_id = ?2",
+                params![action_id.to_string(), sheet_id.to_string()],
+            )
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?
+            .ok_or(actions_core::error::ActionError::ActionNotFound(*action_id))?;
+
+        attached_action_from_row(&row)
+    }
+
+    #[instrument(name = "libsql.update_attached_action", skip(self, action), level = "info", fields(sheet_id = %action.sheet_id, action_id = %action.id, target_field = %action.target_field))]
+    async fn update(
+        &self,
+        action: &actions_core::action::AttachedAction,
+    ) -> Result<(), actions_core::error::ActionError> {
+        let conn = self
+            .db
+            .connect()
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let mapping_str = serde_json::to_string(&action.mapping)
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        conn.execute(
+            "UPDATE attached_action SET action_type = ?3, target_field = ?4, mapping = ?5 WHERE id = ?1 AND sheet_id = ?2",
+            params![
+                action.id.to_string(),
+                action.sheet_id.to_string(),
+                action.action_type.clone(),
+                action.target_field.clone(),
+                mapping_str,
+            ],
+        )
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "libsql.delete_attached_action", skip(self), level = "info", fields(%sheet_id, %action_id))]
+    async fn delete(
+        &self,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+    ) -> Result<(), actions_core::error::ActionError> {
+        let conn = self
+            .db
+            .connect()
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        conn.execute(
+            "DELETE FROM attached_action WHERE id = ?1 AND sheet_id = ?2",
+            params![action_id.to_string(), sheet_id.to_string()],
+        )
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        info!(%sheet_id, %action_id, "deleted attached action");
+        Ok(())
+    }
+}
+
+/// Maps an `attached_action` row selected as `id, sheet_id, action_type, target_field, mapping`.
+fn attached_action_from_row(
+    row: &libsql::Row,
+) -> Result<actions_core::action::AttachedAction, actions_core::error::ActionError> {
+    let id: String = row
+        .get(0)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let sid: String = row
+        .get(1)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let action_type: String = row
+        .get(2)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let target_field: String = row
+        .get(3)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let mapping_str: String = row
+        .get(4)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let sheet_uuid = Uuid::parse_str(&sid)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let mapping: serde_json::Value = serde_json::from_str(&mapping_str)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+    Ok(actions_core::action::AttachedAction {
+        id: uuid,
+        sheet_id: sheet_uuid,
+        action_type,
+        target_field,
+        mapping,
+    })
+}
+
+#[async_trait]
+impl actions_core::ports::driven::MappingTemplatePort for SheetReferenceLibSql {
+    #[instrument(name = "libsql.save_mapping_template", skip(self, template), level = "info", fields(template_id = %template.id, system = %template.system))]
+    async fn save(
+        &self,
+        template: &actions_core::template::MappingTemplate,
+    ) -> Result<(), actions_core::error::ActionError> {
+        let conn = self
+            .db
+            .connect()
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let actions_str = serde_json::to_string(&template.actions)
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO mapping_template (id, name, game_system, fingerprint, actions) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                template.id.to_string(),
+                template.name.clone(),
+                template.system.clone(),
+                template.fingerprint.clone(),
+                actions_str,
+            ],
+        )
+        .await
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "libsql.list_mapping_templates", skip(self), level = "info", fields(%system, %fingerprint))]
+    async fn list_by_fingerprint(
+        &self,
+        system: &str,
+        fingerprint: &str,
+    ) -> Result<Vec<actions_core::template::MappingTemplate>, actions_core::error::ActionError>
+    {
+        let conn = self
+            .db
+            .connect()
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let mut rows = conn
+            .query(
+                "SELECT id, name, game_system, fingerprint, actions FROM mapping_template WHERE game_system = ?1 AND fingerprint = ?2 ORDER BY created_at",
+                params![system.to_string(), fingerprint.to_string()],
+            )
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let mut templates = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?
+        {
+            templates.push(mapping_template_from_row(&row)?);
+        }
+
+        Ok(templates)
+    }
+
+    #[instrument(name = "libsql.find_mapping_template", skip(self), level = "info", fields(%template_id))]
+    async fn find_by_id(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<actions_core::template::MappingTemplate, actions_core::error::ActionError> {
+        let conn = self
+            .db
+            .connect()
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let mut rows = conn
+            .query(
+                "SELECT id, name, game_system, fingerprint, actions FROM mapping_template WHERE id = ?1",
+                params![template_id.to_string()],
+            )
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?
+            .ok_or(actions_core::error::ActionError::TemplateNotFound(
+                *template_id,
+            ))?;
+
+        mapping_template_from_row(&row)
+    }
+}
+
+/// Maps a `mapping_template` row selected as `id, name, game_system, fingerprint, actions`.
+fn mapping_template_from_row(
+    row: &libsql::Row,
+) -> Result<actions_core::template::MappingTemplate, actions_core::error::ActionError> {
+    let id: String = row
+        .get(0)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let name: String = row
+        .get(1)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let system: String = row
+        .get(2)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let fingerprint: String = row
+        .get(3)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let actions_str: String = row
+        .get(4)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+    let actions: Vec<serde_json::Value> = serde_json::from_str(&actions_str)
+        .map_err(|e| actions_core::error::ActionError::DatabaseError(e.to_string()))?;
+
+    Ok(actions_core::template::MappingTemplate {
+        id: uuid,
+        name,
+        system,
+        fingerprint,
+        actions,
+    })
 }
 
 /// No-op implementation for desktop — no S3 lifecycle to reconcile.
@@ -484,4 +708,95 @@ mod tests {
         let all = db.list_all().await.unwrap();
         assert_eq!(all.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_find_update_and_delete_attached_action() {
+        use actions_core::action::AttachedAction;
+        use actions_core::error::ActionError;
+        use actions_core::ports::driven::AttachedActionPort;
+
+        let db = setup_db().await;
+        let sheet_id = Uuid::new_v4();
+        db.create(&SheetReference::new(
+            sheet_id,
+            "test",
+            "abc123",
+            Some("pdf"),
+            PathBuf::from("/tmp/test.pdf"),
+        ))
+        .await
+        .unwrap();
+        let action_id = Uuid::new_v4();
+        db.save(&AttachedAction {
+            id: action_id,
+            sheet_id,
+            action_type: "AbilityModifier".to_string(),
+            target_field: "STRmod".to_string(),
+            mapping: serde_json::json!({"AbilityModifier": {"abilityScoreFieldName": "STR", "abilityModifierFieldName": "STRmod"}}),
+        })
+        .await
+        .unwrap();
+
+        let found = AttachedActionPort::find_by_id(&db, &sheet_id, &action_id)
+            .await
+            .unwrap();
+        assert_eq!(found.target_field, "STRmod");
+
+        db.update(&AttachedAction {
+            target_field: "DEXmod".to_string(),
+            mapping: serde_json::json!({"AbilityModifier": {"abilityScoreFieldName": "DEX", "abilityModifierFieldName": "DEXmod"}}),
+            ..found
+        })
+        .await
+        .unwrap();
+        let updated = AttachedActionPort::find_by_id(&db, &sheet_id, &action_id)
+            .await
+            .unwrap();
+        assert_eq!(updated.target_field, "DEXmod");
+
+        AttachedActionPort::delete(&db, &sheet_id, &action_id)
+            .await
+            .unwrap();
+
+        let result = AttachedActionPort::find_by_id(&db, &sheet_id, &action_id).await;
+        assert_eq!(result.unwrap_err(), ActionError::ActionNotFound(action_id));
+        assert!(db.list_by_sheet_id(&sheet_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_list_mapping_templates_by_fingerprint() {
+        use actions_core::error::ActionError;
+        use actions_core::ports::driven::MappingTemplatePort;
+        use actions_core::template::MappingTemplate;
+
+        let db = setup_db().await;
+        let template = MappingTemplate {
+            id: Uuid::new_v4(),
+            name: "Official 5e sheet".to_string(),
+            system: "dnd5e".to_string(),
+            fingerprint: "abc123".to_string(),
+            actions: vec![
+                serde_json::json!({"AbilityModifier": {"abilityScoreFieldName": "STR", "abilityModifierFieldName": "STRmod"}}),
+            ],
+        };
+        MappingTemplatePort::save(&db, &template).await.unwrap();
+
+        let listed = db.list_by_fingerprint("dnd5e", "abc123").await.unwrap();
+        assert_eq!(listed, vec![template.clone()]);
+        assert!(
+            db.list_by_fingerprint("pf2e", "abc123")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let found = MappingTemplatePort::find_by_id(&db, &template.id)
+            .await
+            .unwrap();
+        assert_eq!(found, template);
+
+        let missing = Uuid::new_v4();
+        let result = MappingTemplatePort::find_by_id(&db, &missing).await;
+        assert_eq!(result.unwrap_err(), ActionError::TemplateNotFound(missing));
+    }
 }
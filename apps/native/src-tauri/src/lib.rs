@@ -1,12 +1,19 @@
-use actions_core::action::{ActionTypeMetadata, CalculationAction};
-use actions_core::ports::driving::ActionService;
+use actions_core::action::{ActionTypeMetadata, AttachActionResult, CalculationAction};
+use actions_core::definition::ActionDefinitions;
+use actions_core::graph::DependencyGraph;
+use actions_core::ports::driving::{ActionService, MappingTemplateService};
+use actions_core::preview::{FieldValue, PreviewResult};
+use actions_core::suggestion::MappingSuggestions;
+use actions_core::system::{find_game_system, game_systems};
+use actions_core::template::MappingTemplate;
 use actions_pdf::adapter::PdfActionAdapter;
 use sheets_core::ports::driven::{SheetPdfPort, SheetReferencePort, SheetStoragePort};
 use sheets_core::ports::driving::SheetService;
-use sheets_core::sheet::{Sheet, SheetField};
+use sheets_core::sheet::{Sheet, SheetField, SheetFieldKind};
 use sheets_fs::adapter::SheetFsStorage;
 use sheets_libsql::adapter::SheetReferenceLibSql;
 use sheets_pdf::adapter::SheetsPdf;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
 use uuid::Uuid;
@@ -27,8 +34,55 @@ struct SheetSummaryResponse {
 }
 
 #[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SheetFieldFlagsResponse {
+    read_only: bool,
+    required: bool,
+    multiline: bool,
+    combo: bool,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 struct SheetFieldResponse {
     name: String,
+    kind: &'static str,
+    flags: SheetFieldFlagsResponse,
+    value: Option<String>,
+    default_value: Option<String>,
+    options: Vec<String>,
+    max_length: Option<u32>,
+    page_index: Option<u32>,
+    rect: Option<[f32; 4]>,
+    has_calculation: bool,
+}
+
+impl From<SheetField> for SheetFieldResponse {
+    fn from(field: SheetField) -> Self {
+        let kind = match field.kind {
+            SheetFieldKind::Text => "text",
+            SheetFieldKind::Choice => "choice",
+            SheetFieldKind::Checkbox => "checkbox",
+            SheetFieldKind::Radio => "radio",
+        };
+        Self {
+            name: field.name,
+            kind,
+            flags: SheetFieldFlagsResponse {
+                read_only: field.flags.read_only,
+                required: field.flags.required,
+                multiline: field.flags.multiline,
+                combo: field.flags.combo,
+            },
+            value: field.value,
+            default_value: field.default_value,
+            options: field.options,
+            max_length: field.max_length,
+            page_index: field.page_index,
+            rect: field.rect,
+            has_calculation: field.has_calculation,
+        }
+    }
 }
 
 #[derive(Clone, serde::Serialize)]
@@ -66,10 +120,7 @@ async fn get_sheet_form_fields(
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(fields
-        .into_iter()
-        .map(|f| SheetFieldResponse { name: f.name })
-        .collect())
+    Ok(fields.into_iter().map(SheetFieldResponse::from).collect())
 }
 
 #[tauri::command]
@@ -89,6 +140,27 @@ async fn export_sheet(
     })
 }
 
+#[tauri::command]
+async fn export_computed_sheet(
+    system: String,
+    sheet_id: String,
+    keep_scripts: bool,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<tauri::ipc::Response, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let local_sheet = action_service
+        .export_computed_sheet(system, &id, keep_scripts)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // The export is a working copy next to the stored sheet, removed once its bytes are read
+    let bytes = tokio::fs::read(local_sheet.path())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(tauri::ipc::Response::new(bytes))
+}
+
 #[tauri::command]
 async fn read_pdf_bytes(file_path: String) -> Result<tauri::ipc::Response, String> {
     let bytes = tokio::fs::read(&file_path)
@@ -99,13 +171,146 @@ async fn read_pdf_bytes(file_path: String) -> Result<tauri::ipc::Response, Strin
 
 #[tauri::command]
 async fn attach_calculation_action(
+    system: String,
     sheet_id: String,
     action: CalculationAction,
     action_service: tauri::State<'_, ActionService>,
 ) -> Result<(), String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    action_service
+        .attach_calculation_script(system, &id, action)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttachActionResultResponse {
+    action_type: String,
+    target_field: String,
+    id: Option<String>,
+    error: Option<String>,
+}
+
+impl From<AttachActionResult> for AttachActionResultResponse {
+    fn from(result: AttachActionResult) -> Self {
+        let (id, error) = match result.outcome {
+            Ok(id) => (Some(id.to_string()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            action_type: result.action_type,
+            target_field: result.target_field,
+            id,
+            error,
+        }
+    }
+}
+
+#[tauri::command]
+async fn attach_calculation_actions(
+    system: String,
+    sheet_id: String,
+    actions: Vec<CalculationAction>,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<Vec<AttachActionResultResponse>, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let results = action_service
+        .attach_calculation_scripts(system, &id, actions)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(results.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+async fn regenerate_sheet(
+    system: String,
+    sheet_id: String,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<Vec<AttachActionResultResponse>, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
     let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let results = action_service
+        .regenerate_sheet(system, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(results.into_iter().map(Into::into).collect())
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviewResultResponse {
+    action_type: String,
+    target_field: String,
+    value: Option<FieldValue>,
+    error: Option<String>,
+}
+
+impl From<PreviewResult> for PreviewResultResponse {
+    fn from(result: PreviewResult) -> Self {
+        let (value, error) = match result.outcome {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            action_type: result.action_type,
+            target_field: result.target_field,
+            value,
+            error,
+        }
+    }
+}
+
+#[tauri::command]
+async fn preview_calculations(
+    system: String,
+    sheet_id: String,
+    values: Option<HashMap<String, String>>,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<Vec<PreviewResultResponse>, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let results = action_service
+        .preview(system, &id, values.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(results.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+async fn update_calculation_action(
+    system: String,
+    sheet_id: String,
+    action_id: String,
+    action: CalculationAction,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<(), String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let sheet_id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let action_id = Uuid::parse_str(&action_id).map_err(|e| e.to_string())?;
+    action_service
+        .update_action(system, &sheet_id, &action_id, action)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn detach_calculation_action(
+    system: String,
+    sheet_id: String,
+    action_id: String,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<(), String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let sheet_id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let action_id = Uuid::parse_str(&action_id).map_err(|e| e.to_string())?;
     action_service
-        .attach_calculation_script(&id, action)
+        .detach_action(system, &sheet_id, &action_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -119,9 +324,30 @@ struct AttachedActionResponse {
     mapping: serde_json::Value,
 }
 
+#[derive(Clone, serde::Serialize)]
+struct GameSystemResponse {
+    id: &'static str,
+    name: &'static str,
+}
+
 #[tauri::command]
-fn list_action_types() -> Vec<ActionTypeMetadata> {
-    CalculationAction::action_type_catalog()
+fn list_game_systems() -> Vec<GameSystemResponse> {
+    game_systems()
+        .into_iter()
+        .map(|system| GameSystemResponse {
+            id: system.id(),
+            name: system.name(),
+        })
+        .collect()
+}
+
+#[tauri::command]
+fn list_action_types(
+    system: String,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<Vec<ActionTypeMetadata>, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    Ok(action_service.action_type_catalog(system))
 }
 
 #[tauri::command]
@@ -146,6 +372,172 @@ async fn list_attached_actions(
         .collect())
 }
 
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FieldDependencyResponse {
+    action_id: String,
+    source_field: String,
+    target_field: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DependencyGraphResponse {
+    fields: Vec<String>,
+    dependencies: Vec<FieldDependencyResponse>,
+}
+
+impl From<DependencyGraph> for DependencyGraphResponse {
+    fn from(graph: DependencyGraph) -> Self {
+        Self {
+            fields: graph.fields().into_iter().map(str::to_string).collect(),
+            dependencies: graph
+                .dependencies
+                .into_iter()
+                .map(|d| FieldDependencyResponse {
+                    action_id: d.action_id.to_string(),
+                    source_field: d.source_field,
+                    target_field: d.target_field,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[tauri::command]
+async fn get_action_dependency_graph(
+    system: String,
+    sheet_id: String,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<DependencyGraphResponse, String> {
+    find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    action_service
+        .dependency_graph(&id)
+        .await
+        .map(Into::into)
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ActionSuggestionResponse {
+    action_type: String,
+    target_field: String,
+    action: CalculationAction,
+    confidence: f32,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MappingSuggestionsResponse {
+    sheet_name: Option<String>,
+    suggestions: Vec<ActionSuggestionResponse>,
+}
+
+impl From<MappingSuggestions> for MappingSuggestionsResponse {
+    fn from(suggestions: MappingSuggestions) -> Self {
+        Self {
+            sheet_name: suggestions.sheet_name.map(str::to_string),
+            suggestions: suggestions
+                .suggestions
+                .into_iter()
+                .map(|s| ActionSuggestionResponse {
+                    action_type: s.action.action_label().to_string(),
+                    target_field: s.action.target_field().to_string(),
+                    action: s.action,
+                    confidence: s.confidence,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[tauri::command]
+async fn suggest_calculation_actions(
+    system: String,
+    sheet_id: String,
+    action_service: tauri::State<'_, ActionService>,
+) -> Result<MappingSuggestionsResponse, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    action_service
+        .suggest_actions(system, &id)
+        .await
+        .map(Into::into)
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MappingTemplateResponse {
+    id: String,
+    name: String,
+    fingerprint: String,
+    actions: Vec<serde_json::Value>,
+}
+
+impl From<MappingTemplate> for MappingTemplateResponse {
+    fn from(template: MappingTemplate) -> Self {
+        Self {
+            id: template.id.to_string(),
+            name: template.name,
+            fingerprint: template.fingerprint,
+            actions: template.actions,
+        }
+    }
+}
+
+#[tauri::command]
+async fn create_mapping_template(
+    system: String,
+    sheet_id: String,
+    name: String,
+    mapping_template_service: tauri::State<'_, MappingTemplateService>,
+) -> Result<MappingTemplateResponse, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    mapping_template_service
+        .create_template(system, &id, &name)
+        .await
+        .map(Into::into)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_mapping_templates(
+    system: String,
+    sheet_id: String,
+    mapping_template_service: tauri::State<'_, MappingTemplateService>,
+) -> Result<Vec<MappingTemplateResponse>, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let templates = mapping_template_service
+        .list_templates(system, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(templates.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+async fn apply_mapping_template(
+    system: String,
+    sheet_id: String,
+    template_id: String,
+    mapping_template_service: tauri::State<'_, MappingTemplateService>,
+) -> Result<Vec<AttachActionResultResponse>, String> {
+    let system = find_game_system(&system).map_err(|e| e.to_string())?;
+    let sheet_id = Uuid::parse_str(&sheet_id).map_err(|e| e.to_string())?;
+    let template_id = Uuid::parse_str(&template_id).map_err(|e| e.to_string())?;
+    let results = mapping_template_service
+        .apply_template(system, &sheet_id, &template_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(results.into_iter().map(Into::into).collect())
+}
+
 #[tauri::command]
 async fn list_sheets(
     db: tauri::State<'_, Arc<SheetReferenceLibSql>>,
@@ -231,17 +623,33 @@ pub fn run() {
                 Arc::new(PdfActionAdapter);
             let attached_action_port: Arc<dyn actions_core::ports::driven::AttachedActionPort> =
                 sheet_reference_db.clone();
+            let mapping_template_port: Arc<dyn actions_core::ports::driven::MappingTemplatePort> =
+                sheet_reference_db.clone();
+            // Custom action types are optional: a broken definition file must not keep the app from starting
+            let definitions_dir = app_data_dir.join("action-definitions");
+            let action_definitions = if definitions_dir.is_dir() {
+                ActionDefinitions::load_dir(&definitions_dir).unwrap_or_else(|e| {
+                    tracing::error!(error = %e, "failed to load action definitions");
+                    ActionDefinitions::default()
+                })
+            } else {
+                ActionDefinitions::default()
+            };
             let action_service = ActionService::new(
                 action_reference_port,
                 action_storage_port,
                 action_pdf_port,
                 attached_action_port,
-            );
+            )
+            .with_action_definitions(action_definitions);
+            let mapping_template_service =
+                MappingTemplateService::new(action_service.clone(), mapping_template_port);
 
             // Store in managed state — concrete Arc for list_sheets, trait objects for services
             app.manage(sheet_reference_db);
             app.manage(sheet_service);
             app.manage(action_service);
+            app.manage(mapping_template_service);
 
             // Build native menu
             let file_menu = tauri::menu::SubmenuBuilder::new(app, "File")
@@ -294,9 +702,21 @@ pub fn run() {
             upload_sheet,
             get_sheet_form_fields,
             export_sheet,
+            export_computed_sheet,
             attach_calculation_action,
+            attach_calculation_actions,
+            regenerate_sheet,
+            preview_calculations,
+            update_calculation_action,
+            detach_calculation_action,
+            list_game_systems,
             list_action_types,
             list_attached_actions,
+            get_action_dependency_graph,
+            suggest_calculation_actions,
+            create_mapping_template,
+            list_mapping_templates,
+            apply_mapping_template,
             read_pdf_bytes,
             list_sheets,
             copy_file,
@@ -5,6 +5,9 @@ import type {
 } from "@repo/ui/types/action.ts";
 import { invoke } from "@tauri-apps/api/core";
 
+// Game system whose actions the editor works with, as served under /dnd5e by the web API
+const GAME_SYSTEM = "dnd5e";
+
 interface SheetReferenceResponse {
   id: string;
   original_name: string;
@@ -19,6 +22,11 @@ interface ExportSheetResponse {
   path: string;
 }
 
+export interface GameSystem {
+  id: string;
+  name: string;
+}
+
 export interface SheetSummary {
   actionCount: number;
   createdAt: string;
@@ -47,6 +55,10 @@ export function listSheets(): Promise<SheetSummary[]> {
   );
 }
 
+export function listGameSystems(): Promise<GameSystem[]> {
+  return invoke<GameSystem[]>("list_game_systems");
+}
+
 export function uploadSheetFromPath(
   filePath: string,
   fileName: string
@@ -98,7 +110,9 @@ export const tauriApiClient: ApiClient = {
   },
 
   async getActionTypes(): Promise<ActionTypeMetadata[]> {
-    return await invoke<ActionTypeMetadata[]>("list_action_types");
+    return await invoke<ActionTypeMetadata[]>("list_action_types", {
+      system: GAME_SYSTEM,
+    });
   },
 
   async attachAction(
@@ -108,6 +122,7 @@ export const tauriApiClient: ApiClient = {
     // Build the serde externally-tagged enum: { "ActionLabel": { field mappings } }
     const payload = { [action.actionLabel]: action.mapping };
     await invoke("attach_calculation_action", {
+      system: GAME_SYSTEM,
       sheetId,
       action: payload,
     });
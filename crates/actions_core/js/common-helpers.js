@@ -0,0 +1,95 @@
+/**
+ * Calculate ability modifier from ability score
+ * @param {number} score - The ability score (e.g., Strength, Dexterity)
+ * @returns {number} The calculated modifier
+ */
+function calculateModifier(score) {
+  return Math.floor((score - 10) / 2);
+}
+
+/**
+ * Sum a list of values
+ * @param {number[]} values - The values to add up
+ * @returns {number} The sum of the values
+ */
+function calculateSum(values) {
+  let sum = 0;
+  for (let i = 0; i < values.length; i++) {
+    sum += values[i];
+  }
+  return sum;
+}
+
+/**
+ * Normalize the result of a formula action
+ * @param {number} value - The computed formula value
+ * @returns {number} The value, or 0 when it is not finite (e.g. division by zero)
+ */
+function calculateFormulaResult(value) {
+  return Number.isFinite(value) ? value : 0;
+}
+
+function calculateModifierFromScore(scoreField) {
+  const score = getNumberValueFromField(scoreField);
+  event.value = calculateModifier(score);
+}
+
+function calculateSumFromFields(sourceFields) {
+  const values = [];
+  for (let i = 0; i < sourceFields.length; i++) {
+    values.push(getNumberValueFromField(sourceFields[i]));
+  }
+  event.value = calculateSum(values);
+}
+
+function getNumberValueFromField(fieldName) {
+  const f = this.getField(fieldName);
+  if (!f) {
+    return 0;
+  }
+  const n = Number(f.value);
+  return Number.isNaN(n) ? 0 : n;
+}
+
+function getOptionalNumberValueFromField(fieldName) {
+  const f = this.getField(fieldName);
+  if (!f || f.value === "") {
+    return undefined;
+  }
+  const n = Number(f.value);
+  return Number.isNaN(n) ? undefined : n;
+}
+
+function getFormulaValueFromField(fieldName) {
+  const f = this.getField(fieldName);
+  if (f && (f.type === "checkbox" || f.type === "radiobutton")) {
+    return getBoolValueFromField(fieldName) ? 1 : 0;
+  }
+  return getNumberValueFromField(fieldName);
+}
+
+function getTextValueFromField(fieldName) {
+  const f = this.getField(fieldName);
+  if (!f || f.value == null) {
+    return "";
+  }
+  return String(f.value);
+}
+
+function getBoolValueFromField(fieldName) {
+  const f = this.getField(fieldName);
+  if (!f) {
+    return false;
+  }
+  const v = f.value;
+  return v !== "Off" && v !== 0 && v !== "" && v != null;
+}
+
+// Conditional exports for testing in Node.js (not executed in PDF environment)
+if (typeof module !== "undefined" && module.exports) {
+  module.exports = {
+    calculateModifier,
+    calculateSum,
+    calculateFormulaResult,
+  };
+}
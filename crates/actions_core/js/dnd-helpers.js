@@ -1,12 +1,3 @@
-/**
- * Calculate ability modifier from ability score
- * @param {number} score - The ability score (e.g., Strength, Dexterity)
- * @returns {number} The calculated modifier
- */
-function calculateModifier(score) {
-  return Math.floor((score - 10) / 2);
-}
-
 /**
  * Calculate save bonus from ability modifier and proficiency
  * @param {number} abilityMod - The ability modifier
@@ -38,6 +29,198 @@ function calculateSkillBonus(
   return Math.floor(abilityMod + profMult * proficiencyBonus);
 }
 
+/**
+ * Calculate a passive score (e.g. passive Perception)
+ * @param {number} skillMod - The skill modifier
+ * @param {number} bonus - Flat bonus to the passive score (e.g. Observant feat)
+ * @param {boolean} advantage - Whether the check is made with advantage (+5)
+ * @param {boolean} disadvantage - Whether the check is made with disadvantage (-5)
+ * @returns {number} The calculated passive score
+ */
+function calculatePassiveScore(skillMod, bonus, advantage, disadvantage) {
+  const advantageBonus = (advantage ? 5 : 0) - (disadvantage ? 5 : 0);
+  return 10 + skillMod + bonus + advantageBonus;
+}
+
+/**
+ * Calculate proficiency bonus from total character level
+ * @param {number} level - The total character level (a blank level counts as 1)
+ * @returns {number} The calculated proficiency bonus
+ */
+function calculateProficiencyBonus(level) {
+  return 2 + Math.floor((Math.max(level, 1) - 1) / 4);
+}
+
+/**
+ * Calculate initiative bonus
+ * @param {number} dexMod - The Dexterity modifier
+ * @param {number} miscBonus - Sum of miscellaneous bonuses (e.g. Alert feat)
+ * @param {boolean} halfProf - Whether has half-proficiency (Jack of All Trades)
+ * @param {number} proficiencyBonus - The character's proficiency bonus
+ * @returns {number} The calculated initiative bonus
+ */
+function calculateInitiative(dexMod, miscBonus, halfProf, proficiencyBonus) {
+  const profMult = getProficiencyMultiplier(false, false, halfProf);
+  return Math.floor(dexMod + miscBonus + profMult * proficiencyBonus);
+}
+
+/**
+ * Calculate armor class
+ * @param {number} baseArmor - Base armor value (10 when unarmored)
+ * @param {number} dexMod - The Dexterity modifier
+ * @param {number|undefined} maxDexBonus - Cap on the Dexterity modifier, or undefined for none
+ * @param {boolean} shield - Whether a shield is equipped (+2)
+ * @param {number} miscBonus - Flat bonus to armor class (e.g. ring of protection)
+ * @returns {number} The calculated armor class
+ */
+function calculateArmorClass(baseArmor, dexMod, maxDexBonus, shield, miscBonus) {
+  const dexBonus =
+    maxDexBonus === undefined ? dexMod : Math.min(dexMod, maxDexBonus);
+  return baseArmor + dexBonus + (shield ? 2 : 0) + miscBonus;
+}
+
+/**
+ * Calculate spell save DC
+ * @param {number} abilityMod - The spellcasting ability modifier
+ * @param {number} proficiencyBonus - The character's proficiency bonus
+ * @param {number} miscBonus - Flat bonus to the DC (e.g. rod of the pact keeper)
+ * @returns {number} The calculated spell save DC
+ */
+function calculateSpellSaveDc(abilityMod, proficiencyBonus, miscBonus) {
+  return 8 + proficiencyBonus + abilityMod + miscBonus;
+}
+
+/**
+ * Calculate spell attack bonus
+ * @param {number} abilityMod - The spellcasting ability modifier
+ * @param {number} proficiencyBonus - The character's proficiency bonus
+ * @param {number} miscBonus - Flat bonus to spell attacks
+ * @returns {number} The calculated spell attack bonus
+ */
+function calculateSpellAttackBonus(abilityMod, proficiencyBonus, miscBonus) {
+  return proficiencyBonus + abilityMod + miscBonus;
+}
+
+/**
+ * Pick the modifier field of the selected spellcasting ability
+ * @param {string} ability - The selected ability, e.g. "WIS" or "Wisdom" (case-insensitive)
+ * @param {string} intModField - Name of the Intelligence modifier field
+ * @param {string} wisModField - Name of the Wisdom modifier field
+ * @param {string} chaModField - Name of the Charisma modifier field
+ * @returns {string|undefined} The modifier field name, or undefined when nothing matches
+ */
+function getSpellcastingModifierField(
+  ability,
+  intModField,
+  wisModField,
+  chaModField
+) {
+  switch (String(ability).trim().substring(0, 3).toUpperCase()) {
+    case "INT":
+      return intModField;
+    case "WIS":
+      return wisModField;
+    case "CHA":
+      return chaModField;
+    default:
+      return undefined;
+  }
+}
+
+/**
+ * Pick the ability modifier a weapon uses
+ * @param {number} strMod - The Strength modifier
+ * @param {number} dexMod - The Dexterity modifier
+ * @param {boolean} finesse - Whether the weapon has the finesse property (higher of STR and DEX)
+ * @param {boolean} ranged - Whether the weapon is a ranged weapon (DEX)
+ * @returns {number} The modifier to apply to attack and damage rolls
+ */
+function getWeaponAbilityModifier(strMod, dexMod, finesse, ranged) {
+  if (finesse) {
+    return Math.max(strMod, dexMod);
+  }
+  if (ranged) {
+    return dexMod;
+  }
+
+  return strMod;
+}
+
+/**
+ * Calculate weapon attack bonus
+ * @param {number} abilityMod - The weapon's ability modifier
+ * @param {boolean} isProficient - Whether proficient with the weapon
+ * @param {number} proficiencyBonus - The character's proficiency bonus
+ * @param {number} magicBonus - The weapon's magic bonus (e.g. +1 longsword)
+ * @returns {number} The calculated attack bonus
+ */
+function calculateWeaponAttackBonus(
+  abilityMod,
+  isProficient,
+  proficiencyBonus,
+  magicBonus
+) {
+  return abilityMod + (isProficient ? proficiencyBonus : 0) + magicBonus;
+}
+
+/**
+ * Calculate weapon damage bonus
+ * @param {number} abilityMod - The weapon's ability modifier
+ * @param {number} magicBonus - The weapon's magic bonus
+ * @returns {number} The calculated damage bonus
+ */
+function calculateWeaponDamageBonus(abilityMod, magicBonus) {
+  return abilityMod + magicBonus;
+}
+
+/**
+ * Get the carrying capacity multiplier of a creature size
+ * @param {string} size - The creature size, e.g. "Large" (case-insensitive; blank counts as Medium)
+ * @returns {number} The multiplier applied to carrying capacity and encumbrance thresholds
+ */
+function getSizeCarryingMultiplier(size) {
+  switch (String(size).trim().toLowerCase()) {
+    case "tiny":
+      return 0.5;
+    case "large":
+      return 2;
+    case "huge":
+      return 4;
+    case "gargantuan":
+      return 8;
+    default:
+      return 1;
+  }
+}
+
+/**
+ * Calculate carrying capacity
+ * @param {number} strScore - The Strength score
+ * @param {number} sizeMultiplier - The size multiplier from getSizeCarryingMultiplier
+ * @returns {number} The carrying capacity in pounds
+ */
+function calculateCarryingCapacity(strScore, sizeMultiplier) {
+  return strScore * 15 * sizeMultiplier;
+}
+
+/**
+ * Calculate encumbrance status using the variant encumbrance rules
+ * @param {number} totalWeight - The total carried weight in pounds
+ * @param {number} strScore - The Strength score
+ * @param {number} sizeMultiplier - The size multiplier from getSizeCarryingMultiplier
+ * @returns {string} "Unencumbered", "Encumbered" or "Heavily Encumbered"
+ */
+function calculateEncumbranceStatus(totalWeight, strScore, sizeMultiplier) {
+  if (totalWeight > strScore * 10 * sizeMultiplier) {
+    return "Heavily Encumbered";
+  }
+  if (totalWeight > strScore * 5 * sizeMultiplier) {
+    return "Encumbered";
+  }
+
+  return "Unencumbered";
+}
+
 /**
  * Get proficiency multiplier based on proficiency type
  * @param {boolean} proficient - Whether proficient
@@ -59,11 +242,6 @@ function getProficiencyMultiplier(proficient, expertise, half) {
   return 0;
 }
 
-function calculateModifierFromScore(scoreField) {
-  const score = getNumberValueFromField(scoreField);
-  event.value = calculateModifier(score);
-}
-
 function calculateSaveFromFields(
   abilityModField,
   proficientField,
@@ -90,30 +268,201 @@ function calculateSkillFromFields(
   event.value = calculateSkillBonus(abilityMod, prof, ex, half, profBonus);
 }
 
-function getNumberValueFromField(fieldName) {
-  const f = this.getField(fieldName);
-  if (!f) {
-    return 0;
+function calculatePassiveFromFields(
+  skillModField,
+  bonusField,
+  advantageField,
+  disadvantageField
+) {
+  const skillMod = getNumberValueFromField(skillModField);
+  const bonus = getNumberValueFromField(bonusField);
+  const adv = getBoolValueFromField(advantageField);
+  const dis = getBoolValueFromField(disadvantageField);
+  event.value = calculatePassiveScore(skillMod, bonus, adv, dis);
+}
+
+function calculateProficiencyBonusFromFields(
+  levelField,
+  multiclassLevelFields
+) {
+  let level = getNumberValueFromField(levelField);
+  for (let i = 0; i < multiclassLevelFields.length; i++) {
+    level += getNumberValueFromField(multiclassLevelFields[i]);
+  }
+  event.value = calculateProficiencyBonus(level);
+}
+
+function calculateInitiativeFromFields(
+  dexModField,
+  miscBonusFields,
+  halfProfField,
+  proficiencyBonusField
+) {
+  const dexMod = getNumberValueFromField(dexModField);
+  let miscBonus = 0;
+  for (let i = 0; i < miscBonusFields.length; i++) {
+    miscBonus += getNumberValueFromField(miscBonusFields[i]);
   }
-  const n = Number(f.value);
-  return Number.isNaN(n) ? 0 : n;
+  const half = getBoolValueFromField(halfProfField);
+  const profBonus = getNumberValueFromField(proficiencyBonusField);
+  event.value = calculateInitiative(dexMod, miscBonus, half, profBonus);
+}
+
+function calculateArmorClassFromFields(
+  baseArmorField,
+  dexModField,
+  maxDexBonusField,
+  shieldField,
+  miscBonusField
+) {
+  const baseArmor = getOptionalNumberValueFromField(baseArmorField);
+  const dexMod = getNumberValueFromField(dexModField);
+  const maxDexBonus = getOptionalNumberValueFromField(maxDexBonusField);
+  const shield = getBoolValueFromField(shieldField);
+  const miscBonus = getNumberValueFromField(miscBonusField);
+  event.value = calculateArmorClass(
+    baseArmor === undefined ? 10 : baseArmor,
+    dexMod,
+    maxDexBonus,
+    shield,
+    miscBonus
+  );
+}
+
+function calculateSpellSaveDcFromFields(
+  abilityField,
+  intModField,
+  wisModField,
+  chaModField,
+  proficiencyBonusField,
+  miscBonusField
+) {
+  const abilityMod = getSpellcastingModifierFromFields(
+    abilityField,
+    intModField,
+    wisModField,
+    chaModField
+  );
+  const profBonus = getNumberValueFromField(proficiencyBonusField);
+  const miscBonus = getNumberValueFromField(miscBonusField);
+  event.value = calculateSpellSaveDc(abilityMod, profBonus, miscBonus);
+}
+
+function calculateSpellAttackFromFields(
+  abilityField,
+  intModField,
+  wisModField,
+  chaModField,
+  proficiencyBonusField,
+  miscBonusField
+) {
+  const abilityMod = getSpellcastingModifierFromFields(
+    abilityField,
+    intModField,
+    wisModField,
+    chaModField
+  );
+  const profBonus = getNumberValueFromField(proficiencyBonusField);
+  const miscBonus = getNumberValueFromField(miscBonusField);
+  event.value = calculateSpellAttackBonus(abilityMod, profBonus, miscBonus);
+}
+
+function getSpellcastingModifierFromFields(
+  abilityField,
+  intModField,
+  wisModField,
+  chaModField
+) {
+  const modField = getSpellcastingModifierField(
+    getTextValueFromField(abilityField),
+    intModField,
+    wisModField,
+    chaModField
+  );
+  return getNumberValueFromField(modField);
+}
+
+function calculateWeaponAttackFromFields(
+  strModField,
+  dexModField,
+  finesseField,
+  rangedField,
+  proficientField,
+  proficiencyBonusField,
+  magicBonusField
+) {
+  const abilityMod = getWeaponAbilityModifier(
+    getNumberValueFromField(strModField),
+    getNumberValueFromField(dexModField),
+    getBoolValueFromField(finesseField),
+    getBoolValueFromField(rangedField)
+  );
+  const prof = getBoolValueFromField(proficientField);
+  const profBonus = getNumberValueFromField(proficiencyBonusField);
+  const magicBonus = getNumberValueFromField(magicBonusField);
+  event.value = calculateWeaponAttackBonus(
+    abilityMod,
+    prof,
+    profBonus,
+    magicBonus
+  );
+}
+
+function calculateWeaponDamageFromFields(
+  strModField,
+  dexModField,
+  finesseField,
+  rangedField,
+  magicBonusField
+) {
+  const abilityMod = getWeaponAbilityModifier(
+    getNumberValueFromField(strModField),
+    getNumberValueFromField(dexModField),
+    getBoolValueFromField(finesseField),
+    getBoolValueFromField(rangedField)
+  );
+  const magicBonus = getNumberValueFromField(magicBonusField);
+  event.value = calculateWeaponDamageBonus(abilityMod, magicBonus);
 }
 
-function getBoolValueFromField(fieldName) {
-  const f = this.getField(fieldName);
-  if (!f) {
-    return false;
+function calculateCarryingCapacityFromFields(strScoreField, sizeField) {
+  const strScore = getNumberValueFromField(strScoreField);
+  const sizeMult = getSizeCarryingMultiplier(getTextValueFromField(sizeField));
+  event.value = calculateCarryingCapacity(strScore, sizeMult);
+}
+
+function calculateEncumbranceStatusFromFields(
+  strScoreField,
+  weightFields,
+  sizeField
+) {
+  const strScore = getNumberValueFromField(strScoreField);
+  let totalWeight = 0;
+  for (let i = 0; i < weightFields.length; i++) {
+    totalWeight += getNumberValueFromField(weightFields[i]);
   }
-  const v = f.value;
-  return v !== "Off" && v !== 0 && v !== "" && v != null;
+  const sizeMult = getSizeCarryingMultiplier(getTextValueFromField(sizeField));
+  event.value = calculateEncumbranceStatus(totalWeight, strScore, sizeMult);
 }
 
 // Conditional exports for testing in Node.js (not executed in PDF environment)
 if (typeof module !== "undefined" && module.exports) {
   module.exports = {
-    calculateModifier,
     calculateSaveBonus,
     calculateSkillBonus,
+    calculatePassiveScore,
+    calculateProficiencyBonus,
+    calculateInitiative,
+    calculateArmorClass,
+    calculateSpellSaveDc,
+    calculateSpellAttackBonus,
+    getSpellcastingModifierField,
+    getWeaponAbilityModifier,
+    calculateWeaponAttackBonus,
+    calculateWeaponDamageBonus,
+    getSizeCarryingMultiplier,
+    calculateCarryingCapacity,
+    calculateEncumbranceStatus,
     getProficiencyMultiplier,
   };
 }
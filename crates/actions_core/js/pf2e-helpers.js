@@ -0,0 +1,56 @@
+/**
+ * Get the proficiency bonus granted by a Pathfinder 2e proficiency rank
+ * @param {string} rank - The rank name or initial (Untrained, Trained, Expert, Master, Legendary)
+ * @returns {number} The rank bonus before level is added (0, 2, 4, 6, or 8)
+ */
+function getProficiencyRankBonus(rank) {
+  const r = String(rank).trim().toUpperCase().slice(0, 1);
+  if (r === "T") {
+    return 2;
+  }
+  if (r === "E") {
+    return 4;
+  }
+  if (r === "M") {
+    return 6;
+  }
+  if (r === "L") {
+    return 8;
+  }
+
+  return 0;
+}
+
+/**
+ * Calculate a Pathfinder 2e modifier from ability, proficiency rank and level
+ * @param {number} abilityMod - The ability modifier
+ * @param {number} rankBonus - The proficiency rank bonus (see getProficiencyRankBonus)
+ * @param {number} level - The character level, added only when trained or better
+ * @param {number} itemBonus - Item or other bonus
+ * @returns {number} The calculated modifier
+ */
+function calculateRankedModifier(abilityMod, rankBonus, level, itemBonus) {
+  const proficiency = rankBonus > 0 ? rankBonus + level : 0;
+  return abilityMod + proficiency + itemBonus;
+}
+
+function calculateRankedModifierFromFields(
+  abilityModField,
+  rankField,
+  levelField,
+  itemBonusField
+) {
+  const abilityMod = getNumberValueFromField(abilityModField);
+  const rankBonus = getProficiencyRankBonus(getTextValueFromField(rankField));
+  const level = getNumberValueFromField(levelField);
+  const itemBonus = itemBonusField ? getNumberValueFromField(itemBonusField) : 0;
+  event.value = calculateRankedModifier(abilityMod, rankBonus, level, itemBonus);
+}
+
+// Conditional exports for testing in Node.js (not executed in PDF environment)
+if (typeof module !== "undefined" && module.exports) {
+  module.exports = {
+    getProficiencyRankBonus,
+    calculateRankedModifier,
+  };
+}
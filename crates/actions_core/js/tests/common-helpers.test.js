@@ -0,0 +1,73 @@
+import { describe, expect, it } from "vitest";
+import {
+  calculateModifier,
+  calculateSum,
+  calculateFormulaResult,
+} from "../common-helpers.js";
+
+describe("Common Helper Functions", () => {
+  describe("calculateModifier", () => {
+    it.each`
+      abilityScore | expected | description
+      ${1}         | ${-5}    | ${"ability score 1"}
+      ${2}         | ${-4}    | ${"ability score 2"}
+      ${3}         | ${-4}    | ${"ability score 3"}
+      ${4}         | ${-3}    | ${"ability score 4"}
+      ${5}         | ${-3}    | ${"ability score 5"}
+      ${6}         | ${-2}    | ${"ability score 6"}
+      ${7}         | ${-2}    | ${"ability score 7"}
+      ${8}         | ${-1}    | ${"ability score 8"}
+      ${9}         | ${-1}    | ${"ability score 9"}
+      ${10}        | ${0}     | ${"ability score 10"}
+      ${11}        | ${0}     | ${"ability score 11"}
+      ${12}        | ${1}     | ${"ability score 12"}
+      ${13}        | ${1}     | ${"ability score 13"}
+      ${14}        | ${2}     | ${"ability score 14"}
+      ${15}        | ${2}     | ${"ability score 15"}
+      ${16}        | ${3}     | ${"ability score 16"}
+      ${17}        | ${3}     | ${"ability score 17"}
+      ${18}        | ${4}     | ${"ability score 18"}
+      ${19}        | ${4}     | ${"ability score 19"}
+      ${20}        | ${5}     | ${"ability score 20"}
+      ${21}        | ${5}     | ${"ability score 21"}
+      ${22}        | ${6}     | ${"ability score 22"}
+      ${23}        | ${6}     | ${"ability score 23"}
+      ${24}        | ${7}     | ${"ability score 24"}
+      ${25}        | ${7}     | ${"ability score 25"}
+      ${26}        | ${8}     | ${"ability score 26"}
+      ${27}        | ${8}     | ${"ability score 27"}
+      ${28}        | ${9}     | ${"ability score 28"}
+      ${29}        | ${9}     | ${"ability score 29"}
+      ${30}        | ${10}    | ${"ability score 30"}
+    `(
+      "should return $expected for ability score $abilityScore ($description)",
+      ({ abilityScore, expected }) => {
+        expect(calculateModifier(abilityScore)).toBe(expected);
+      }
+    );
+  });
+
+  describe("calculateSum", () => {
+    it.each`
+      values             | expected
+      ${[]}              | ${0}
+      ${[5]}             | ${5}
+      ${[1, 2.5, 3]}     | ${6.5}
+      ${[10, -4, 0]}     | ${6}
+    `("should return $expected when values=$values", ({ values, expected }) => {
+      expect(calculateSum(values)).toBe(expected);
+    });
+  });
+
+  describe("calculateFormulaResult", () => {
+    it.each`
+      value        | expected | description
+      ${7}         | ${7}     | ${"integer result"}
+      ${-2.5}      | ${-2.5}  | ${"fractional result"}
+      ${Infinity}  | ${0}     | ${"division by zero"}
+      ${NaN}       | ${0}     | ${"zero divided by zero"}
+    `("should return $expected ($description)", ({ value, expected }) => {
+      expect(calculateFormulaResult(value)).toBe(expected);
+    });
+  });
+});
@@ -1,53 +1,24 @@
 import { describe, expect, it } from "vitest";
 import {
-  calculateModifier,
   calculateSaveBonus,
   calculateSkillBonus,
+  calculatePassiveScore,
+  calculateProficiencyBonus,
+  calculateInitiative,
+  calculateArmorClass,
+  calculateSpellSaveDc,
+  calculateSpellAttackBonus,
+  getSpellcastingModifierField,
+  getWeaponAbilityModifier,
+  calculateWeaponAttackBonus,
+  calculateWeaponDamageBonus,
+  getSizeCarryingMultiplier,
+  calculateCarryingCapacity,
+  calculateEncumbranceStatus,
   getProficiencyMultiplier,
 } from "../dnd-helpers.js";
 
 describe("D&D Helper Functions", () => {
-  describe("calculateModifier", () => {
-    it.each`
-      abilityScore | expected | description
-      ${1}         | ${-5}    | ${"ability score 1"}
-      ${2}         | ${-4}    | ${"ability score 2"}
-      ${3}         | ${-4}    | ${"ability score 3"}
-      ${4}         | ${-3}    | ${"ability score 4"}
-      ${5}         | ${-3}    | ${"ability score 5"}
-      ${6}         | ${-2}    | ${"ability score 6"}
-      ${7}         | ${-2}    | ${"ability score 7"}
-      ${8}         | ${-1}    | ${"ability score 8"}
-      ${9}         | ${-1}    | ${"ability score 9"}
-      ${10}        | ${0}     | ${"ability score 10"}
-      ${11}        | ${0}     | ${"ability score 11"}
-      ${12}        | ${1}     | ${"ability score 12"}
-      ${13}        | ${1}     | ${"ability score 13"}
-      ${14}        | ${2}     | ${"ability score 14"}
-      ${15}        | ${2}     | ${"ability score 15"}
-      ${16}        | ${3}     | ${"ability score 16"}
-      ${17}        | ${3}     | ${"ability score 17"}
-      ${18}        | ${4}     | ${"ability score 18"}
-      ${19}        | ${4}     | ${"ability score 19"}
-      ${20}        | ${5}     | ${"ability score 20"}
-      ${21}        | ${5}     | ${"ability score 21"}
-      ${22}        | ${6}     | ${"ability score 22"}
-      ${23}        | ${6}     | ${"ability score 23"}
-      ${24}        | ${7}     | ${"ability score 24"}
-      ${25}        | ${7}     | ${"ability score 25"}
-      ${26}        | ${8}     | ${"ability score 26"}
-      ${27}        | ${8}     | ${"ability score 27"}
-      ${28}        | ${9}     | ${"ability score 28"}
-      ${29}        | ${9}     | ${"ability score 29"}
-      ${30}        | ${10}    | ${"ability score 30"}
-    `(
-      "should return $expected for ability score $abilityScore ($description)",
-      ({ abilityScore, expected }) => {
-        expect(calculateModifier(abilityScore)).toBe(expected);
-      }
-    );
-  });
-
   describe("getProficiencyMultiplier", () => {
     it.each`
       proficient | expertise | half     | expected | description
@@ -129,4 +100,232 @@ describe("D&D Helper Functions", () => {
       }
     );
   });
+
+  describe("calculatePassiveScore", () => {
+    it.each`
+      skillMod | bonus | advantage | disadvantage | expected | description
+      ${3}     | ${0}  | ${false}  | ${false}     | ${13}    | ${"10 + skill modifier"}
+      ${-1}    | ${0}  | ${false}  | ${false}     | ${9}     | ${"negative skill modifier"}
+      ${3}     | ${5}  | ${false}  | ${false}     | ${18}    | ${"flat bonus (Observant feat)"}
+      ${3}     | ${0}  | ${true}   | ${false}     | ${18}    | ${"+5 with advantage"}
+      ${3}     | ${0}  | ${false}  | ${true}      | ${8}     | ${"-5 with disadvantage"}
+      ${3}     | ${0}  | ${true}   | ${true}      | ${13}    | ${"advantage and disadvantage cancel out"}
+    `(
+      "should return $expected when skillMod=$skillMod, bonus=$bonus, advantage=$advantage, disadvantage=$disadvantage ($description)",
+      ({ skillMod, bonus, advantage, disadvantage, expected }) => {
+        expect(
+          calculatePassiveScore(skillMod, bonus, advantage, disadvantage)
+        ).toBe(expected);
+      }
+    );
+  });
+
+  describe("calculateProficiencyBonus", () => {
+    it.each`
+      level | expected | description
+      ${0}  | ${2}     | ${"blank level counts as level 1"}
+      ${1}  | ${2}     | ${"level 1"}
+      ${4}  | ${2}     | ${"level 4"}
+      ${5}  | ${3}     | ${"level 5"}
+      ${8}  | ${3}     | ${"level 8"}
+      ${9}  | ${4}     | ${"level 9"}
+      ${13} | ${5}     | ${"level 13"}
+      ${17} | ${6}     | ${"level 17"}
+      ${20} | ${6}     | ${"level 20"}
+    `(
+      "should return $expected when level=$level ($description)",
+      ({ level, expected }) => {
+        expect(calculateProficiencyBonus(level)).toBe(expected);
+      }
+    );
+  });
+
+  describe("calculateInitiative", () => {
+    it.each`
+      dexMod | miscBonus | halfProf | proficiencyBonus | expected | description
+      ${2}   | ${0}      | ${false} | ${2}             | ${2}     | ${"DEX modifier only"}
+      ${2}   | ${5}      | ${false} | ${2}             | ${7}     | ${"with Alert feat"}
+      ${2}   | ${0}      | ${true}  | ${3}             | ${3}     | ${"Jack of All Trades rounds down"}
+      ${-1}  | ${0}      | ${true}  | ${4}             | ${1}     | ${"negative DEX with Jack of All Trades"}
+    `(
+      "should return $expected ($description)",
+      ({ dexMod, miscBonus, halfProf, proficiencyBonus, expected }) => {
+        expect(
+          calculateInitiative(dexMod, miscBonus, halfProf, proficiencyBonus)
+        ).toBe(expected);
+      }
+    );
+  });
+
+  describe("calculateArmorClass", () => {
+    it.each`
+      baseArmor | dexMod | maxDexBonus  | shield   | miscBonus | expected | description
+      ${10}     | ${3}   | ${undefined} | ${false} | ${0}      | ${13}    | ${"unarmored"}
+      ${14}     | ${3}   | ${2}         | ${false} | ${0}      | ${16}    | ${"medium armor caps DEX"}
+      ${16}     | ${3}   | ${0}         | ${true}  | ${0}      | ${18}    | ${"heavy armor with shield"}
+      ${12}     | ${-1}  | ${2}         | ${false} | ${1}      | ${12}    | ${"cap does not raise negative DEX"}
+    `(
+      "should return $expected ($description)",
+      ({ baseArmor, dexMod, maxDexBonus, shield, miscBonus, expected }) => {
+        expect(
+          calculateArmorClass(baseArmor, dexMod, maxDexBonus, shield, miscBonus)
+        ).toBe(expected);
+      }
+    );
+  });
+
+  describe("calculateSpellSaveDc", () => {
+    it.each`
+      abilityMod | proficiencyBonus | miscBonus | expected
+      ${3}       | ${2}             | ${0}      | ${13}
+      ${5}       | ${6}             | ${1}      | ${20}
+      ${-1}      | ${2}             | ${0}      | ${9}
+    `(
+      "should return $expected when abilityMod=$abilityMod, proficiencyBonus=$proficiencyBonus, miscBonus=$miscBonus",
+      ({ abilityMod, proficiencyBonus, miscBonus, expected }) => {
+        expect(calculateSpellSaveDc(abilityMod, proficiencyBonus, miscBonus)).toBe(
+          expected
+        );
+      }
+    );
+  });
+
+  describe("calculateSpellAttackBonus", () => {
+    it.each`
+      abilityMod | proficiencyBonus | miscBonus | expected
+      ${3}       | ${2}             | ${0}      | ${5}
+      ${5}       | ${6}             | ${1}      | ${12}
+      ${-1}      | ${2}             | ${0}      | ${1}
+    `(
+      "should return $expected when abilityMod=$abilityMod, proficiencyBonus=$proficiencyBonus, miscBonus=$miscBonus",
+      ({ abilityMod, proficiencyBonus, miscBonus, expected }) => {
+        expect(
+          calculateSpellAttackBonus(abilityMod, proficiencyBonus, miscBonus)
+        ).toBe(expected);
+      }
+    );
+  });
+
+  describe("getSpellcastingModifierField", () => {
+    it.each`
+      ability           | expected
+      ${"INT"}          | ${"INTmod"}
+      ${"wis"}          | ${"WISmod"}
+      ${" Charisma "}   | ${"CHAmod"}
+      ${""}             | ${undefined}
+      ${"STR"}          | ${undefined}
+    `("should return $expected when ability=$ability", ({ ability, expected }) => {
+      expect(
+        getSpellcastingModifierField(ability, "INTmod", "WISmod", "CHAmod")
+      ).toBe(expected);
+    });
+  });
+
+  describe("getWeaponAbilityModifier", () => {
+    it.each`
+      strMod | dexMod | finesse  | ranged   | expected | description
+      ${3}   | ${1}   | ${false} | ${false} | ${3}     | ${"melee uses STR"}
+      ${1}   | ${3}   | ${false} | ${false} | ${1}     | ${"melee ignores higher DEX"}
+      ${3}   | ${1}   | ${false} | ${true}  | ${1}     | ${"ranged uses DEX"}
+      ${1}   | ${3}   | ${true}  | ${false} | ${3}     | ${"finesse picks higher DEX"}
+      ${3}   | ${1}   | ${true}  | ${false} | ${3}     | ${"finesse picks higher STR"}
+      ${3}   | ${1}   | ${true}  | ${true}  | ${3}     | ${"finesse thrown weapon picks higher"}
+    `(
+      "should return $expected ($description)",
+      ({ strMod, dexMod, finesse, ranged, expected }) => {
+        expect(getWeaponAbilityModifier(strMod, dexMod, finesse, ranged)).toBe(
+          expected
+        );
+      }
+    );
+  });
+
+  describe("calculateWeaponAttackBonus", () => {
+    it.each`
+      abilityMod | isProficient | proficiencyBonus | magicBonus | expected
+      ${3}       | ${true}      | ${2}             | ${0}       | ${5}
+      ${3}       | ${false}     | ${2}             | ${0}       | ${3}
+      ${3}       | ${true}      | ${2}             | ${1}       | ${6}
+      ${-1}      | ${true}      | ${3}             | ${0}       | ${2}
+    `(
+      "should return $expected when abilityMod=$abilityMod, isProficient=$isProficient, proficiencyBonus=$proficiencyBonus, magicBonus=$magicBonus",
+      ({ abilityMod, isProficient, proficiencyBonus, magicBonus, expected }) => {
+        expect(
+          calculateWeaponAttackBonus(
+            abilityMod,
+            isProficient,
+            proficiencyBonus,
+            magicBonus
+          )
+        ).toBe(expected);
+      }
+    );
+  });
+
+  describe("calculateWeaponDamageBonus", () => {
+    it.each`
+      abilityMod | magicBonus | expected
+      ${3}       | ${0}       | ${3}
+      ${3}       | ${2}       | ${5}
+      ${-1}      | ${0}       | ${-1}
+    `(
+      "should return $expected when abilityMod=$abilityMod, magicBonus=$magicBonus",
+      ({ abilityMod, magicBonus, expected }) => {
+        expect(calculateWeaponDamageBonus(abilityMod, magicBonus)).toBe(
+          expected
+        );
+      }
+    );
+  });
+
+  describe("getSizeCarryingMultiplier", () => {
+    it.each`
+      size            | expected
+      ${"Tiny"}       | ${0.5}
+      ${"small"}      | ${1}
+      ${"Medium"}     | ${1}
+      ${""}           | ${1}
+      ${" Large "}    | ${2}
+      ${"HUGE"}       | ${4}
+      ${"Gargantuan"} | ${8}
+    `("should return $expected when size=$size", ({ size, expected }) => {
+      expect(getSizeCarryingMultiplier(size)).toBe(expected);
+    });
+  });
+
+  describe("calculateCarryingCapacity", () => {
+    it.each`
+      strScore | sizeMultiplier | expected
+      ${10}    | ${1}           | ${150}
+      ${15}    | ${1}           | ${225}
+      ${18}    | ${2}           | ${540}
+      ${8}     | ${0.5}         | ${60}
+    `(
+      "should return $expected when strScore=$strScore, sizeMultiplier=$sizeMultiplier",
+      ({ strScore, sizeMultiplier, expected }) => {
+        expect(calculateCarryingCapacity(strScore, sizeMultiplier)).toBe(
+          expected
+        );
+      }
+    );
+  });
+
+  describe("calculateEncumbranceStatus", () => {
+    it.each`
+      totalWeight | strScore | sizeMultiplier | expected
+      ${0}        | ${10}    | ${1}           | ${"Unencumbered"}
+      ${50}       | ${10}    | ${1}           | ${"Unencumbered"}
+      ${51}       | ${10}    | ${1}           | ${"Encumbered"}
+      ${100}      | ${10}    | ${1}           | ${"Encumbered"}
+      ${101}      | ${10}    | ${1}           | ${"Heavily Encumbered"}
+      ${101}      | ${10}    | ${2}           | ${"Encumbered"}
+    `(
+      "should return $expected when totalWeight=$totalWeight, strScore=$strScore, sizeMultiplier=$sizeMultiplier",
+      ({ totalWeight, strScore, sizeMultiplier, expected }) => {
+        expect(
+          calculateEncumbranceStatus(totalWeight, strScore, sizeMultiplier)
+        ).toBe(expected);
+      }
+    );
+  });
 });
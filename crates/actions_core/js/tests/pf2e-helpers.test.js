@@ -0,0 +1,40 @@
+import { describe, expect, it } from "vitest";
+import {
+  getProficiencyRankBonus,
+  calculateRankedModifier,
+} from "../pf2e-helpers.js";
+
+describe("Pathfinder 2e Helper Functions", () => {
+  describe("getProficiencyRankBonus", () => {
+    it.each`
+      rank           | expected
+      ${""}          | ${0}
+      ${"U"}         | ${0}
+      ${"Untrained"} | ${0}
+      ${"T"}         | ${2}
+      ${"trained"}   | ${2}
+      ${" Expert "}  | ${4}
+      ${"M"}         | ${6}
+      ${"LEGENDARY"} | ${8}
+    `("should return $expected when rank=$rank", ({ rank, expected }) => {
+      expect(getProficiencyRankBonus(rank)).toBe(expected);
+    });
+  });
+
+  describe("calculateRankedModifier", () => {
+    it.each`
+      abilityMod | rankBonus | level | itemBonus | expected | description
+      ${3}       | ${0}      | ${5}  | ${0}      | ${3}     | ${"untrained ignores level"}
+      ${3}       | ${2}      | ${5}  | ${0}      | ${10}    | ${"trained"}
+      ${4}       | ${4}      | ${7}  | ${1}      | ${16}    | ${"expert with item bonus"}
+      ${-1}      | ${8}      | ${20} | ${3}      | ${30}    | ${"legendary"}
+    `(
+      "should return $expected ($description)",
+      ({ abilityMod, rankBonus, level, itemBonus, expected }) => {
+        expect(
+          calculateRankedModifier(abilityMod, rankBonus, level, itemBonus)
+        ).toBe(expected);
+      }
+    );
+  });
+});
@@ -1,5 +1,10 @@
-use crate::error::ActionError;
-use serde::{Deserialize, Serialize};
+use crate::definition::CustomAction;
+use crate::error::{ActionError, FieldRoleProblem, FieldRoleViolation};
+use crate::formula::Formula;
+use crate::ports::driven::{FormField, FormFieldKind};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -11,6 +16,55 @@ pub struct AttachedAction {
     pub mapping: serde_json::Value,
 }
 
+/// Outcome of attaching one action as part of a batch.
+#[derive(Debug, PartialEq)]
+pub struct AttachActionResult {
+    pub action_type: String,
+    pub target_field: String,
+    /// ID of the persisted `AttachedAction`, or the reason this action was not attached.
+    pub outcome: Result<Uuid, ActionError>,
+}
+
+/// Kind of form field a role expects to be mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldKind {
+    /// A text field holding a number.
+    NumericText,
+    /// A text field holding any text, e.g. a status computed by the calculation.
+    Text,
+    /// An on/off button; radio buttons are accepted as well.
+    Checkbox,
+    /// A combo or list box; text fields are accepted as well, holding the typed option.
+    Choice,
+    /// Any kind of form field.
+    Any,
+}
+
+impl FieldKind {
+    /// Returns whether a form field of kind `actual` can fill a role expecting `self`.
+    pub fn accepts(self, actual: FormFieldKind) -> bool {
+        match self {
+            Self::NumericText | Self::Text => actual == FormFieldKind::Text,
+            Self::Checkbox => matches!(actual, FormFieldKind::Checkbox | FormFieldKind::Radio),
+            Self::Choice => matches!(actual, FormFieldKind::Choice | FormFieldKind::Text),
+            Self::Any => true,
+        }
+    }
+}
+
+impl std::fmt::Display for FieldKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NumericText => "numeric text field",
+            Self::Text => "text field",
+            Self::Checkbox => "checkbox",
+            Self::Choice => "choice field",
+            Self::Any => "form field",
+        })
+    }
+}
+
 /// Metadata about a field role in an action type.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +72,10 @@ pub struct FieldRoleMetadata {
     pub key: String,
     pub required: bool,
     pub is_target: bool,
+    /// Whether the role takes a list of fields rather than a single one.
+    pub multiple: bool,
+    /// Kind of form field the role must be mapped to.
+    pub kind: FieldKind,
 }
 
 /// Metadata describing an available action type (powers frontend UI dynamically).
@@ -28,12 +86,16 @@ pub struct ActionTypeMetadata {
     pub id: String,
     /// PascalCase label matching the serde enum variant name (e.g., "AbilityModifier").
     pub action_label: String,
+    /// Whether the inputs come from a free-form expression rather than fixed roles.
+    pub free_form: bool,
     /// Field roles defining the inputs/outputs for this action type.
     pub roles: Vec<FieldRoleMetadata>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all_fields = "camelCase")]
+/// Serialized externally tagged, e.g. `{"AbilityModifier": {...}}`; a tag that is not a
+/// built-in action label is read as a [`CustomAction`] of that type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(remote = "Self", rename_all_fields = "camelCase")]
 pub enum CalculationAction {
     AbilityModifier {
         #[serde(rename = "abilityScoreFieldName")]
@@ -55,6 +117,153 @@ pub enum CalculationAction {
         proficiency_bonus_field_name: String,
         skill_modifier_field_name: String,
     },
+    PassiveScore {
+        skill_modifier_field_name: String,
+        bonus_field_name: Option<String>,
+        advantage_field_name: Option<String>,
+        disadvantage_field_name: Option<String>,
+        passive_score_field_name: String,
+    },
+    ProficiencyBonus {
+        level_field_name: String,
+        /// Level fields of further classes, summed with `level_field_name` for multiclass characters.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        multiclass_level_field_names: Vec<String>,
+        proficiency_bonus_field_name: String,
+    },
+    Initiative {
+        dex_modifier_field_name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        misc_bonus_field_names: Vec<String>,
+        /// Checkbox adding half the proficiency bonus; requires `proficiency_bonus_field_name`.
+        jack_of_all_trades_field_name: Option<String>,
+        proficiency_bonus_field_name: Option<String>,
+        initiative_field_name: String,
+    },
+    ArmorClass {
+        base_armor_field_name: String,
+        dex_modifier_field_name: String,
+        /// Caps the DEX modifier (e.g. medium armor); a blank field means no cap.
+        max_dex_bonus_field_name: Option<String>,
+        shield_field_name: Option<String>,
+        misc_bonus_field_name: Option<String>,
+        armor_class_field_name: String,
+    },
+    SpellSaveDc {
+        /// Field selecting the spellcasting ability (`INT`, `WIS` or `CHA`), resolved at runtime
+        /// to the matching modifier field.
+        spellcasting_ability_field_name: String,
+        intelligence_modifier_field_name: String,
+        wisdom_modifier_field_name: String,
+        charisma_modifier_field_name: String,
+        proficiency_bonus_field_name: String,
+        misc_bonus_field_name: Option<String>,
+        spell_save_dc_field_name: String,
+    },
+    SpellAttackBonus {
+        spellcasting_ability_field_name: String,
+        intelligence_modifier_field_name: String,
+        wisdom_modifier_field_name: String,
+        charisma_modifier_field_name: String,
+        proficiency_bonus_field_name: String,
+        misc_bonus_field_name: Option<String>,
+        spell_attack_bonus_field_name: String,
+    },
+    WeaponAttackBonus {
+        strength_modifier_field_name: String,
+        dexterity_modifier_field_name: String,
+        /// Checkbox letting the weapon use the higher of STR and DEX.
+        finesse_field_name: Option<String>,
+        /// Checkbox making the weapon use DEX.
+        ranged_field_name: Option<String>,
+        proficiency_field_name: String,
+        proficiency_bonus_field_name: String,
+        magic_bonus_field_name: Option<String>,
+        attack_bonus_field_name: String,
+    },
+    WeaponDamageBonus {
+        strength_modifier_field_name: String,
+        dexterity_modifier_field_name: String,
+        finesse_field_name: Option<String>,
+        ranged_field_name: Option<String>,
+        magic_bonus_field_name: Option<String>,
+        damage_bonus_field_name: String,
+    },
+    CarryingCapacity {
+        strength_score_field_name: String,
+        /// Choice field holding the creature size, which scales the capacity.
+        size_field_name: Option<String>,
+        carrying_capacity_field_name: String,
+    },
+    /// Writes "Unencumbered", "Encumbered" or "Heavily Encumbered" rather than a number.
+    EncumbranceStatus {
+        strength_score_field_name: String,
+        /// Inventory weight column summed into the carried load.
+        weight_field_names: Vec<String>,
+        size_field_name: Option<String>,
+        encumbrance_status_field_name: String,
+    },
+    /// Pathfinder 2e check or DC modifier: the rank bonus plus level applies once trained.
+    ProficiencyRankModifier {
+        ability_modifier_field_name: String,
+        /// Choice field holding the rank (Untrained, Trained, Expert, Master or Legendary).
+        proficiency_rank_field_name: String,
+        level_field_name: String,
+        item_bonus_field_name: Option<String>,
+        modifier_field_name: String,
+    },
+    SumFields {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        source_field_names: Vec<String>,
+        /// Field-name pattern (`*` matches any run of characters, `?` a single one) expanded
+        /// against the sheet's form fields when the action is attached, e.g. `Wgt*`.
+        source_field_pattern: Option<String>,
+        sum_field_name: String,
+    },
+    /// Free-form arithmetic over form fields; see [`Formula`] for the syntax.
+    Formula {
+        expression: Formula,
+        target_field_name: String,
+    },
+    /// An action type declared in a definition file; see [`crate::definition`].
+    #[serde(skip)]
+    Custom(CustomAction),
+}
+
+impl Serialize for CalculationAction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Custom(custom) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&custom.action_type, &custom.fields)?;
+                map.end()
+            }
+            _ => Self::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CalculationAction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match value {
+            serde_json::Value::Object(map)
+                if map.len() == 1
+                    && map.keys().all(|label| {
+                        !Self::action_type_catalog()
+                            .iter()
+                            .any(|meta| meta.action_label == *label)
+                    }) =>
+            {
+                let (action_type, fields) = map.into_iter().next().unwrap_or_default();
+                let fields = serde_json::from_value(fields).map_err(D::Error::custom)?;
+                Ok(Self::Custom(CustomAction::new(action_type, fields)))
+            }
+            value => Self::deserialize(value).map_err(D::Error::custom),
+        }
+    }
 }
 
 fn serialize_field_name(field_name: &str) -> Result<String, ActionError> {
@@ -62,13 +271,103 @@ fn serialize_field_name(field_name: &str) -> Result<String, ActionError> {
         .map_err(|e| ActionError::InvalidAction(format!("failed to serialize field name: {}", e)))
 }
 
+/// Serializes an optional field name, passing `undefined` to the helper when it is unset.
+fn serialize_optional_field_name(field_name: Option<&str>) -> Result<String, ActionError> {
+    match field_name {
+        Some(name) => serialize_field_name(name),
+        None => Ok("undefined".to_string()),
+    }
+}
+
+/// Matches a field name against a pattern where `*` stands for any run of characters and `?`
+/// for exactly one.
+fn matches_field_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name index it currently absorbs up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Orders field names comparing digit runs by numeric value, so `Wgt2` sorts before `Wgt10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let a_end = a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len());
+            let b_end = b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len());
+            let a_digits = a[..a_end].trim_start_matches('0');
+            let b_digits = b[..b_end].trim_start_matches('0');
+            let ordering = a_digits
+                .len()
+                .cmp(&b_digits.len())
+                .then_with(|| a_digits.cmp(b_digits));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (&a[a_end..], &b[b_end..]);
+        } else {
+            if ca != cb {
+                return ca.cmp(&cb);
+            }
+            (a, b) = (&a[ca.len_utf8()..], &b[cb.len_utf8()..]);
+        }
+    }
+}
+
+/// Serializes a list of field names as a JavaScript array literal.
+fn serialize_field_names(field_names: &[String]) -> Result<String, ActionError> {
+    serde_json::to_string(field_names)
+        .map_err(|e| ActionError::InvalidAction(format!("failed to serialize field names: {}", e)))
+}
+
 impl CalculationAction {
     /// Returns the persistence label matching the serde variant name.
-    pub fn action_label(&self) -> &'static str {
+    pub fn action_label(&self) -> &str {
         match self {
             Self::AbilityModifier { .. } => "AbilityModifier",
             Self::SavingThrowModifier { .. } => "SavingThrowModifier",
             Self::SkillModifier { .. } => "SkillModifier",
+            Self::PassiveScore { .. } => "PassiveScore",
+            Self::ProficiencyBonus { .. } => "ProficiencyBonus",
+            Self::Initiative { .. } => "Initiative",
+            Self::ArmorClass { .. } => "ArmorClass",
+            Self::SpellSaveDc { .. } => "SpellSaveDc",
+            Self::SpellAttackBonus { .. } => "SpellAttackBonus",
+            Self::WeaponAttackBonus { .. } => "WeaponAttackBonus",
+            Self::WeaponDamageBonus { .. } => "WeaponDamageBonus",
+            Self::CarryingCapacity { .. } => "CarryingCapacity",
+            Self::EncumbranceStatus { .. } => "EncumbranceStatus",
+            Self::ProficiencyRankModifier { .. } => "ProficiencyRankModifier",
+            Self::SumFields { .. } => "SumFields",
+            Self::Formula { .. } => "Formula",
+            Self::Custom(custom) => &custom.action_type,
         }
     }
 
@@ -87,6 +386,495 @@ impl CalculationAction {
                 skill_modifier_field_name,
                 ..
             } => skill_modifier_field_name,
+            Self::PassiveScore {
+                passive_score_field_name,
+                ..
+            } => passive_score_field_name,
+            Self::ProficiencyBonus {
+                proficiency_bonus_field_name,
+                ..
+            } => proficiency_bonus_field_name,
+            Self::Initiative {
+                initiative_field_name,
+                ..
+            } => initiative_field_name,
+            Self::ArmorClass {
+                armor_class_field_name,
+                ..
+            } => armor_class_field_name,
+            Self::SpellSaveDc {
+                spell_save_dc_field_name,
+                ..
+            } => spell_save_dc_field_name,
+            Self::SpellAttackBonus {
+                spell_attack_bonus_field_name,
+                ..
+            } => spell_attack_bonus_field_name,
+            Self::WeaponAttackBonus {
+                attack_bonus_field_name,
+                ..
+            } => attack_bonus_field_name,
+            Self::WeaponDamageBonus {
+                damage_bonus_field_name,
+                ..
+            } => damage_bonus_field_name,
+            Self::CarryingCapacity {
+                carrying_capacity_field_name,
+                ..
+            } => carrying_capacity_field_name,
+            Self::EncumbranceStatus {
+                encumbrance_status_field_name,
+                ..
+            } => encumbrance_status_field_name,
+            Self::ProficiencyRankModifier {
+                modifier_field_name,
+                ..
+            } => modifier_field_name,
+            Self::SumFields { sum_field_name, .. } => sum_field_name,
+            Self::Formula {
+                target_field_name, ..
+            } => target_field_name,
+            Self::Custom(custom) => custom.target_field(),
+        }
+    }
+
+    /// Returns each mapped field keyed by its catalog role, skipping unset optional roles.
+    pub fn field_roles(&self) -> Vec<(&str, &str)> {
+        match self {
+            Self::AbilityModifier {
+                score_field_name,
+                modifier_field_name,
+            } => vec![
+                ("abilityScoreFieldName", score_field_name),
+                ("abilityModifierFieldName", modifier_field_name),
+            ],
+            Self::SavingThrowModifier {
+                ability_modifier_field_name,
+                proficiency_field_name,
+                proficiency_bonus_field_name,
+                saving_throw_modifier_field_name,
+            } => vec![
+                ("abilityModifierFieldName", ability_modifier_field_name),
+                ("proficiencyBonusFieldName", proficiency_bonus_field_name),
+                ("proficiencyFieldName", proficiency_field_name),
+                (
+                    "savingThrowModifierFieldName",
+                    saving_throw_modifier_field_name,
+                ),
+            ],
+            Self::SkillModifier {
+                ability_modifier_field_name,
+                proficiency_field_name,
+                expertise_field_name,
+                half_prof_field_name,
+                proficiency_bonus_field_name,
+                skill_modifier_field_name,
+            } => {
+                let mut roles = vec![
+                    (
+                        "abilityModifierFieldName",
+                        ability_modifier_field_name.as_str(),
+                    ),
+                    ("proficiencyBonusFieldName", proficiency_bonus_field_name),
+                    ("proficiencyFieldName", proficiency_field_name),
+                    ("skillModifierFieldName", skill_modifier_field_name),
+                ];
+                if let Some(name) = expertise_field_name {
+                    roles.push(("expertiseFieldName", name));
+                }
+                if let Some(name) = half_prof_field_name {
+                    roles.push(("halfProfFieldName", name));
+                }
+                roles
+            }
+            Self::PassiveScore {
+                skill_modifier_field_name,
+                bonus_field_name,
+                advantage_field_name,
+                disadvantage_field_name,
+                passive_score_field_name,
+            } => {
+                let mut roles = vec![
+                    ("skillModifierFieldName", skill_modifier_field_name.as_str()),
+                    ("passiveScoreFieldName", passive_score_field_name),
+                ];
+                if let Some(name) = bonus_field_name {
+                    roles.push(("bonusFieldName", name));
+                }
+                if let Some(name) = advantage_field_name {
+                    roles.push(("advantageFieldName", name));
+                }
+                if let Some(name) = disadvantage_field_name {
+                    roles.push(("disadvantageFieldName", name));
+                }
+                roles
+            }
+            Self::ProficiencyBonus {
+                level_field_name,
+                multiclass_level_field_names,
+                proficiency_bonus_field_name,
+            } => {
+                let mut roles = vec![
+                    ("levelFieldName", level_field_name.as_str()),
+                    ("proficiencyBonusFieldName", proficiency_bonus_field_name),
+                ];
+                roles.extend(
+                    multiclass_level_field_names
+                        .iter()
+                        .map(|name| ("multiclassLevelFieldNames", name.as_str())),
+                );
+                roles
+            }
+            Self::Initiative {
+                dex_modifier_field_name,
+                misc_bonus_field_names,
+                jack_of_all_trades_field_name,
+                proficiency_bonus_field_name,
+                initiative_field_name,
+            } => {
+                let mut roles = vec![
+                    ("dexModifierFieldName", dex_modifier_field_name.as_str()),
+                    ("initiativeFieldName", initiative_field_name),
+                ];
+                roles.extend(
+                    misc_bonus_field_names
+                        .iter()
+                        .map(|name| ("miscBonusFieldNames", name.as_str())),
+                );
+                if let Some(name) = jack_of_all_trades_field_name {
+                    roles.push(("jackOfAllTradesFieldName", name));
+                }
+                if let Some(name) = proficiency_bonus_field_name {
+                    roles.push(("proficiencyBonusFieldName", name));
+                }
+                roles
+            }
+            Self::ArmorClass {
+                base_armor_field_name,
+                dex_modifier_field_name,
+                max_dex_bonus_field_name,
+                shield_field_name,
+                misc_bonus_field_name,
+                armor_class_field_name,
+            } => {
+                let mut roles = vec![
+                    ("baseArmorFieldName", base_armor_field_name.as_str()),
+                    ("dexModifierFieldName", dex_modifier_field_name),
+                    ("armorClassFieldName", armor_class_field_name),
+                ];
+                if let Some(name) = max_dex_bonus_field_name {
+                    roles.push(("maxDexBonusFieldName", name));
+                }
+                if let Some(name) = shield_field_name {
+                    roles.push(("shieldFieldName", name));
+                }
+                if let Some(name) = misc_bonus_field_name {
+                    roles.push(("miscBonusFieldName", name));
+                }
+                roles
+            }
+            Self::SpellSaveDc {
+                spellcasting_ability_field_name,
+                intelligence_modifier_field_name,
+                wisdom_modifier_field_name,
+                charisma_modifier_field_name,
+                proficiency_bonus_field_name,
+                misc_bonus_field_name,
+                ..
+            }
+            | Self::SpellAttackBonus {
+                spellcasting_ability_field_name,
+                intelligence_modifier_field_name,
+                wisdom_modifier_field_name,
+                charisma_modifier_field_name,
+                proficiency_bonus_field_name,
+                misc_bonus_field_name,
+                ..
+            } => {
+                let target_role = if matches!(self, Self::SpellSaveDc { .. }) {
+                    "spellSaveDcFieldName"
+                } else {
+                    "spellAttackBonusFieldName"
+                };
+                let mut roles = vec![
+                    (
+                        "spellcastingAbilityFieldName",
+                        spellcasting_ability_field_name.as_str(),
+                    ),
+                    (
+                        "intelligenceModifierFieldName",
+                        intelligence_modifier_field_name,
+                    ),
+                    ("wisdomModifierFieldName", wisdom_modifier_field_name),
+                    ("charismaModifierFieldName", charisma_modifier_field_name),
+                    ("proficiencyBonusFieldName", proficiency_bonus_field_name),
+                    (target_role, self.target_field()),
+                ];
+                if let Some(name) = misc_bonus_field_name {
+                    roles.push(("miscBonusFieldName", name));
+                }
+                roles
+            }
+            Self::WeaponAttackBonus {
+                strength_modifier_field_name,
+                dexterity_modifier_field_name,
+                finesse_field_name,
+                ranged_field_name,
+                proficiency_field_name,
+                proficiency_bonus_field_name,
+                magic_bonus_field_name,
+                attack_bonus_field_name,
+            } => {
+                let mut roles = vec![
+                    (
+                        "strengthModifierFieldName",
+                        strength_modifier_field_name.as_str(),
+                    ),
+                    ("dexterityModifierFieldName", dexterity_modifier_field_name),
+                    ("proficiencyFieldName", proficiency_field_name),
+                    ("proficiencyBonusFieldName", proficiency_bonus_field_name),
+                    ("attackBonusFieldName", attack_bonus_field_name),
+                ];
+                if let Some(name) = finesse_field_name {
+                    roles.push(("finesseFieldName", name));
+                }
+                if let Some(name) = ranged_field_name {
+                    roles.push(("rangedFieldName", name));
+                }
+                if let Some(name) = magic_bonus_field_name {
+                    roles.push(("magicBonusFieldName", name));
+                }
+                roles
+            }
+            Self::WeaponDamageBonus {
+                strength_modifier_field_name,
+                dexterity_modifier_field_name,
+                finesse_field_name,
+                ranged_field_name,
+                magic_bonus_field_name,
+                damage_bonus_field_name,
+            } => {
+                let mut roles = vec![
+                    (
+                        "strengthModifierFieldName",
+                        strength_modifier_field_name.as_str(),
+                    ),
+                    ("dexterityModifierFieldName", dexterity_modifier_field_name),
+                    ("damageBonusFieldName", damage_bonus_field_name),
+                ];
+                if let Some(name) = finesse_field_name {
+                    roles.push(("finesseFieldName", name));
+                }
+                if let Some(name) = ranged_field_name {
+                    roles.push(("rangedFieldName", name));
+                }
+                if let Some(name) = magic_bonus_field_name {
+                    roles.push(("magicBonusFieldName", name));
+                }
+                roles
+            }
+            Self::CarryingCapacity {
+                strength_score_field_name,
+                size_field_name,
+                carrying_capacity_field_name,
+            } => {
+                let mut roles = vec![
+                    ("strengthScoreFieldName", strength_score_field_name.as_str()),
+                    ("carryingCapacityFieldName", carrying_capacity_field_name),
+                ];
+                if let Some(name) = size_field_name {
+                    roles.push(("sizeFieldName", name));
+                }
+                roles
+            }
+            Self::EncumbranceStatus {
+                strength_score_field_name,
+                weight_field_names,
+                size_field_name,
+                encumbrance_status_field_name,
+            } => {
+                let mut roles = vec![
+                    ("strengthScoreFieldName", strength_score_field_name.as_str()),
+                    ("encumbranceStatusFieldName", encumbrance_status_field_name),
+                ];
+                roles.extend(
+                    weight_field_names
+                        .iter()
+                        .map(|name| ("weightFieldNames", name.as_str())),
+                );
+                if let Some(name) = size_field_name {
+                    roles.push(("sizeFieldName", name));
+                }
+                roles
+            }
+            Self::ProficiencyRankModifier {
+                ability_modifier_field_name,
+                proficiency_rank_field_name,
+                level_field_name,
+                item_bonus_field_name,
+                modifier_field_name,
+            } => {
+                let mut roles = vec![
+                    (
+                        "abilityModifierFieldName",
+                        ability_modifier_field_name.as_str(),
+                    ),
+                    ("proficiencyRankFieldName", proficiency_rank_field_name),
+                    ("levelFieldName", level_field_name),
+                    ("modifierFieldName", modifier_field_name),
+                ];
+                if let Some(name) = item_bonus_field_name {
+                    roles.push(("itemBonusFieldName", name));
+                }
+                roles
+            }
+            Self::SumFields {
+                source_field_names,
+                sum_field_name,
+                ..
+            } => {
+                let mut roles = vec![("sumFieldName", sum_field_name.as_str())];
+                roles.extend(
+                    source_field_names
+                        .iter()
+                        .map(|name| ("sourceFieldNames", name.as_str())),
+                );
+                roles
+            }
+            Self::Formula {
+                expression,
+                target_field_name,
+            } => {
+                let mut roles = vec![("targetFieldName", target_field_name.as_str())];
+                roles.extend(
+                    expression
+                        .field_references()
+                        .into_iter()
+                        .map(|name| ("expression", name)),
+                );
+                roles
+            }
+            Self::Custom(custom) => custom
+                .fields
+                .iter()
+                .flat_map(|(role, names)| {
+                    names
+                        .as_slice()
+                        .iter()
+                        .map(move |name| (role.as_str(), name.as_str()))
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the fields the calculation reads, i.e. every mapped field but the target.
+    pub fn input_fields(&self) -> Vec<&str> {
+        let target_role = match self {
+            Self::AbilityModifier { .. } => "abilityModifierFieldName",
+            Self::SavingThrowModifier { .. } => "savingThrowModifierFieldName",
+            Self::SkillModifier { .. } => "skillModifierFieldName",
+            Self::PassiveScore { .. } => "passiveScoreFieldName",
+            Self::ProficiencyBonus { .. } => "proficiencyBonusFieldName",
+            Self::Initiative { .. } => "initiativeFieldName",
+            Self::ArmorClass { .. } => "armorClassFieldName",
+            Self::SpellSaveDc { .. } => "spellSaveDcFieldName",
+            Self::SpellAttackBonus { .. } => "spellAttackBonusFieldName",
+            Self::WeaponAttackBonus { .. } => "attackBonusFieldName",
+            Self::WeaponDamageBonus { .. } => "damageBonusFieldName",
+            Self::CarryingCapacity { .. } => "carryingCapacityFieldName",
+            Self::EncumbranceStatus { .. } => "encumbranceStatusFieldName",
+            Self::ProficiencyRankModifier { .. } => "modifierFieldName",
+            Self::SumFields { .. } => "sumFieldName",
+            Self::Formula { .. } => "targetFieldName",
+            Self::Custom(custom) => custom.target_role().unwrap_or_default(),
+        };
+        self.field_roles()
+            .into_iter()
+            .filter(|(role, _)| *role != target_role)
+            .map(|(_, field_name)| field_name)
+            .collect()
+    }
+
+    /// Resolves field-name patterns against the sheet's form fields, appending the matches in
+    /// natural order (`Wgt2` before `Wgt10`) so the stored mapping lists concrete fields.
+    pub fn expand_field_patterns(&mut self, form_fields: &[FormField]) -> Result<(), ActionError> {
+        let Self::SumFields {
+            source_field_names,
+            source_field_pattern: Some(pattern),
+            sum_field_name,
+        } = self
+        else {
+            return Ok(());
+        };
+
+        let mut matches: Vec<&str> = form_fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .filter(|name| matches_field_pattern(pattern, name) && name != sum_field_name)
+            .collect();
+        if matches.is_empty() {
+            return Err(ActionError::InvalidFieldMapping(vec![FieldRoleViolation {
+                role: "sourceFieldPattern".to_string(),
+                field_name: pattern.clone(),
+                problem: FieldRoleProblem::Missing,
+            }]));
+        }
+
+        matches.sort_by(|a, b| natural_cmp(a, b));
+        for name in matches {
+            if !source_field_names.iter().any(|source| source == name) {
+                source_field_names.push(name.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks every mapped field against the sheet's form fields, reporting each role whose
+    /// field is missing or not of the kind the catalog expects.
+    pub fn validate_fields(&self, form_fields: &[FormField]) -> Result<(), ActionError> {
+        let catalog = match self {
+            Self::Custom(custom) => custom
+                .definition()
+                .map(|definition| vec![definition.metadata()])
+                .unwrap_or_default(),
+            _ => Self::action_type_catalog(),
+        };
+        let roles = catalog
+            .iter()
+            .find(|metadata| metadata.action_label == self.action_label())
+            .map(|metadata| metadata.roles.as_slice())
+            .unwrap_or_default();
+
+        let violations: Vec<FieldRoleViolation> = self
+            .field_roles()
+            .into_iter()
+            .filter_map(|(role, field_name)| {
+                let expected = roles
+                    .iter()
+                    .find(|metadata| metadata.key == role)
+                    .map_or(FieldKind::Any, |metadata| metadata.kind);
+
+                let problem = match form_fields.iter().find(|field| field.name == field_name) {
+                    None => FieldRoleProblem::Missing,
+                    Some(field) if !expected.accepts(field.kind) => FieldRoleProblem::WrongKind {
+                        expected,
+                        actual: field.kind,
+                    },
+                    Some(_) => return None,
+                };
+
+                Some(FieldRoleViolation {
+                    role: role.to_string(),
+                    field_name: field_name.to_string(),
+                    problem,
+                })
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ActionError::InvalidFieldMapping(violations))
         }
     }
 
@@ -123,20 +911,223 @@ impl CalculationAction {
             } => {
                 let ability_mod = serialize_field_name(ability_modifier_field_name)?;
                 let proficiency = serialize_field_name(proficiency_field_name)?;
-                let expertise = match expertise_field_name {
-                    Some(name) => serialize_field_name(name)?,
-                    None => "undefined".to_string(),
-                };
-                let half_prof = match half_prof_field_name {
-                    Some(name) => serialize_field_name(name)?,
-                    None => "undefined".to_string(),
-                };
+                let expertise = serialize_optional_field_name(expertise_field_name.as_deref())?;
+                let half_prof = serialize_optional_field_name(half_prof_field_name.as_deref())?;
                 let prof_bonus = serialize_field_name(proficiency_bonus_field_name)?;
                 Ok(format!(
                     "calculateSkillFromFields({}, {}, {}, {}, {});",
                     ability_mod, proficiency, expertise, half_prof, prof_bonus
                 ))
             }
+            Self::PassiveScore {
+                skill_modifier_field_name,
+                bonus_field_name,
+                advantage_field_name,
+                disadvantage_field_name,
+                ..
+            } => {
+                let skill_mod = serialize_field_name(skill_modifier_field_name)?;
+                let bonus = serialize_optional_field_name(bonus_field_name.as_deref())?;
+                let advantage = serialize_optional_field_name(advantage_field_name.as_deref())?;
+                let disadvantage =
+                    serialize_optional_field_name(disadvantage_field_name.as_deref())?;
+                Ok(format!(
+                    "calculatePassiveFromFields({}, {}, {}, {});",
+                    skill_mod, bonus, advantage, disadvantage
+                ))
+            }
+            Self::ProficiencyBonus {
+                level_field_name,
+                multiclass_level_field_names,
+                ..
+            } => {
+                let level = serialize_field_name(level_field_name)?;
+                let multiclass_levels = serialize_field_names(multiclass_level_field_names)?;
+                Ok(format!(
+                    "calculateProficiencyBonusFromFields({}, {});",
+                    level, multiclass_levels
+                ))
+            }
+            Self::Initiative {
+                dex_modifier_field_name,
+                misc_bonus_field_names,
+                jack_of_all_trades_field_name,
+                proficiency_bonus_field_name,
+                ..
+            } => {
+                if jack_of_all_trades_field_name.is_some() && proficiency_bonus_field_name.is_none()
+                {
+                    return Err(ActionError::InvalidAction(
+                        "Jack of All Trades needs a proficiency bonus field".to_string(),
+                    ));
+                }
+                let dex_mod = serialize_field_name(dex_modifier_field_name)?;
+                let misc_bonuses = serialize_field_names(misc_bonus_field_names)?;
+                let jack_of_all_trades =
+                    serialize_optional_field_name(jack_of_all_trades_field_name.as_deref())?;
+                let prof_bonus =
+                    serialize_optional_field_name(proficiency_bonus_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateInitiativeFromFields({}, {}, {}, {});",
+                    dex_mod, misc_bonuses, jack_of_all_trades, prof_bonus
+                ))
+            }
+            Self::ArmorClass {
+                base_armor_field_name,
+                dex_modifier_field_name,
+                max_dex_bonus_field_name,
+                shield_field_name,
+                misc_bonus_field_name,
+                ..
+            } => {
+                let base_armor = serialize_field_name(base_armor_field_name)?;
+                let dex_mod = serialize_field_name(dex_modifier_field_name)?;
+                let max_dex_bonus =
+                    serialize_optional_field_name(max_dex_bonus_field_name.as_deref())?;
+                let shield = serialize_optional_field_name(shield_field_name.as_deref())?;
+                let misc_bonus = serialize_optional_field_name(misc_bonus_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateArmorClassFromFields({}, {}, {}, {}, {});",
+                    base_armor, dex_mod, max_dex_bonus, shield, misc_bonus
+                ))
+            }
+            Self::SpellSaveDc {
+                spellcasting_ability_field_name,
+                intelligence_modifier_field_name,
+                wisdom_modifier_field_name,
+                charisma_modifier_field_name,
+                proficiency_bonus_field_name,
+                misc_bonus_field_name,
+                ..
+            }
+            | Self::SpellAttackBonus {
+                spellcasting_ability_field_name,
+                intelligence_modifier_field_name,
+                wisdom_modifier_field_name,
+                charisma_modifier_field_name,
+                proficiency_bonus_field_name,
+                misc_bonus_field_name,
+                ..
+            } => {
+                let helper = if matches!(self, Self::SpellSaveDc { .. }) {
+                    "calculateSpellSaveDcFromFields"
+                } else {
+                    "calculateSpellAttackFromFields"
+                };
+                let ability = serialize_field_name(spellcasting_ability_field_name)?;
+                let int_mod = serialize_field_name(intelligence_modifier_field_name)?;
+                let wis_mod = serialize_field_name(wisdom_modifier_field_name)?;
+                let cha_mod = serialize_field_name(charisma_modifier_field_name)?;
+                let prof_bonus = serialize_field_name(proficiency_bonus_field_name)?;
+                let misc_bonus = serialize_optional_field_name(misc_bonus_field_name.as_deref())?;
+                Ok(format!(
+                    "{}({}, {}, {}, {}, {}, {});",
+                    helper, ability, int_mod, wis_mod, cha_mod, prof_bonus, misc_bonus
+                ))
+            }
+            Self::WeaponAttackBonus {
+                strength_modifier_field_name,
+                dexterity_modifier_field_name,
+                finesse_field_name,
+                ranged_field_name,
+                proficiency_field_name,
+                proficiency_bonus_field_name,
+                magic_bonus_field_name,
+                ..
+            } => {
+                let str_mod = serialize_field_name(strength_modifier_field_name)?;
+                let dex_mod = serialize_field_name(dexterity_modifier_field_name)?;
+                let finesse = serialize_optional_field_name(finesse_field_name.as_deref())?;
+                let ranged = serialize_optional_field_name(ranged_field_name.as_deref())?;
+                let proficiency = serialize_field_name(proficiency_field_name)?;
+                let prof_bonus = serialize_field_name(proficiency_bonus_field_name)?;
+                let magic_bonus = serialize_optional_field_name(magic_bonus_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateWeaponAttackFromFields({}, {}, {}, {}, {}, {}, {});",
+                    str_mod, dex_mod, finesse, ranged, proficiency, prof_bonus, magic_bonus
+                ))
+            }
+            Self::WeaponDamageBonus {
+                strength_modifier_field_name,
+                dexterity_modifier_field_name,
+                finesse_field_name,
+                ranged_field_name,
+                magic_bonus_field_name,
+                ..
+            } => {
+                let str_mod = serialize_field_name(strength_modifier_field_name)?;
+                let dex_mod = serialize_field_name(dexterity_modifier_field_name)?;
+                let finesse = serialize_optional_field_name(finesse_field_name.as_deref())?;
+                let ranged = serialize_optional_field_name(ranged_field_name.as_deref())?;
+                let magic_bonus = serialize_optional_field_name(magic_bonus_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateWeaponDamageFromFields({}, {}, {}, {}, {});",
+                    str_mod, dex_mod, finesse, ranged, magic_bonus
+                ))
+            }
+            Self::CarryingCapacity {
+                strength_score_field_name,
+                size_field_name,
+                ..
+            } => {
+                let str_score = serialize_field_name(strength_score_field_name)?;
+                let size = serialize_optional_field_name(size_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateCarryingCapacityFromFields({}, {});",
+                    str_score, size
+                ))
+            }
+            Self::EncumbranceStatus {
+                strength_score_field_name,
+                weight_field_names,
+                size_field_name,
+                ..
+            } => {
+                if weight_field_names.is_empty() {
+                    return Err(ActionError::InvalidAction(
+                        "encumbrance status needs at least one weight field".to_string(),
+                    ));
+                }
+                let str_score = serialize_field_name(strength_score_field_name)?;
+                let weights = serialize_field_names(weight_field_names)?;
+                let size = serialize_optional_field_name(size_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateEncumbranceStatusFromFields({}, {}, {});",
+                    str_score, weights, size
+                ))
+            }
+            Self::ProficiencyRankModifier {
+                ability_modifier_field_name,
+                proficiency_rank_field_name,
+                level_field_name,
+                item_bonus_field_name,
+                ..
+            } => {
+                let ability_mod = serialize_field_name(ability_modifier_field_name)?;
+                let rank = serialize_field_name(proficiency_rank_field_name)?;
+                let level = serialize_field_name(level_field_name)?;
+                let item_bonus = serialize_optional_field_name(item_bonus_field_name.as_deref())?;
+                Ok(format!(
+                    "calculateRankedModifierFromFields({}, {}, {}, {});",
+                    ability_mod, rank, level, item_bonus
+                ))
+            }
+            Self::SumFields {
+                source_field_names, ..
+            } => {
+                if source_field_names.is_empty() {
+                    return Err(ActionError::InvalidAction(
+                        "sum needs at least one source field".to_string(),
+                    ));
+                }
+                let sources = serialize_field_names(source_field_names)?;
+                Ok(format!("calculateSumFromFields({});", sources))
+            }
+            Self::Formula { expression, .. } => Ok(format!(
+                "event.value = calculateFormulaResult({});",
+                expression.expression().to_js()
+            )),
+            Self::Custom(custom) => custom.generate_js(),
         }
     }
 
@@ -147,135 +1138,929 @@ impl CalculationAction {
             ActionTypeMetadata {
                 id: "ability-modifier".to_string(),
                 action_label: "AbilityModifier".to_string(),
+                free_form: false,
                 roles: vec![
                     FieldRoleMetadata {
                         key: "abilityScoreFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                     FieldRoleMetadata {
                         key: "abilityModifierFieldName".to_string(),
                         required: true,
                         is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                 ],
             },
             ActionTypeMetadata {
                 id: "skill-modifier".to_string(),
                 action_label: "SkillModifier".to_string(),
+                free_form: false,
                 roles: vec![
                     FieldRoleMetadata {
                         key: "abilityModifierFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                     FieldRoleMetadata {
                         key: "proficiencyBonusFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                     FieldRoleMetadata {
                         key: "proficiencyFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
                     },
                     FieldRoleMetadata {
                         key: "skillModifierFieldName".to_string(),
                         required: true,
                         is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                     FieldRoleMetadata {
                         key: "expertiseFieldName".to_string(),
                         required: false,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
                     },
                     FieldRoleMetadata {
                         key: "halfProfFieldName".to_string(),
                         required: false,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
                     },
                 ],
             },
             ActionTypeMetadata {
                 id: "saving-throw-modifier".to_string(),
                 action_label: "SavingThrowModifier".to_string(),
+                free_form: false,
                 roles: vec![
                     FieldRoleMetadata {
                         key: "abilityModifierFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                     FieldRoleMetadata {
                         key: "proficiencyBonusFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                     FieldRoleMetadata {
                         key: "proficiencyFieldName".to_string(),
                         required: true,
                         is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
                     },
                     FieldRoleMetadata {
                         key: "savingThrowModifierFieldName".to_string(),
                         required: true,
                         is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
                     },
                 ],
             },
-        ]
-    }
-
-    pub fn ability_modifier<S1: Into<String>, S2: Into<String>>(
-        score_field_name: S1,
-        modifier_field_name: S2,
-    ) -> Self {
-        Self::AbilityModifier {
-            score_field_name: score_field_name.into(),
-            modifier_field_name: modifier_field_name.into(),
-        }
-    }
-
-    pub fn saving_throw_modifier<
-        S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<String>,
-        S4: Into<String>,
-    >(
-        ability_modifier_field_name: S1,
-        proficiency_field_name: S2,
-        proficiency_bonus_field_name: S3,
-        saving_throw_modifier_field_name: S4,
-    ) -> Self {
-        Self::SavingThrowModifier {
-            ability_modifier_field_name: ability_modifier_field_name.into(),
-            proficiency_field_name: proficiency_field_name.into(),
-            proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
-            saving_throw_modifier_field_name: saving_throw_modifier_field_name.into(),
-        }
+            ActionTypeMetadata {
+                id: "passive-score".to_string(),
+                action_label: "PassiveScore".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "skillModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "passiveScoreFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "bonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "advantageFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "disadvantageFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "proficiency-bonus".to_string(),
+                action_label: "ProficiencyBonus".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "levelFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyBonusFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "multiclassLevelFieldNames".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: true,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "initiative".to_string(),
+                action_label: "Initiative".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "dexModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "initiativeFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "miscBonusFieldNames".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: true,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "jackOfAllTradesFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "armor-class".to_string(),
+                action_label: "ArmorClass".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "baseArmorFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "dexModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "armorClassFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "maxDexBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "shieldFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "miscBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "spell-save-dc".to_string(),
+                action_label: "SpellSaveDc".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "spellcastingAbilityFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Choice,
+                    },
+                    FieldRoleMetadata {
+                        key: "intelligenceModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "wisdomModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "charismaModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyBonusFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "spellSaveDcFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "miscBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "spell-attack-bonus".to_string(),
+                action_label: "SpellAttackBonus".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "spellcastingAbilityFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Choice,
+                    },
+                    FieldRoleMetadata {
+                        key: "intelligenceModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "wisdomModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "charismaModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyBonusFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "spellAttackBonusFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "miscBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "weapon-attack-bonus".to_string(),
+                action_label: "WeaponAttackBonus".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "strengthModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "dexterityModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyBonusFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "attackBonusFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "finesseFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "rangedFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "magicBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "weapon-damage-bonus".to_string(),
+                action_label: "WeaponDamageBonus".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "strengthModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "dexterityModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "damageBonusFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "finesseFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "rangedFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Checkbox,
+                    },
+                    FieldRoleMetadata {
+                        key: "magicBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "carrying-capacity".to_string(),
+                action_label: "CarryingCapacity".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "strengthScoreFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "carryingCapacityFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "sizeFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Choice,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "encumbrance-status".to_string(),
+                action_label: "EncumbranceStatus".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "strengthScoreFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "weightFieldNames".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: true,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "encumbranceStatusFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::Text,
+                    },
+                    FieldRoleMetadata {
+                        key: "sizeFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Choice,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "proficiency-rank-modifier".to_string(),
+                action_label: "ProficiencyRankModifier".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "abilityModifierFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "proficiencyRankFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::Choice,
+                    },
+                    FieldRoleMetadata {
+                        key: "levelFieldName".to_string(),
+                        required: true,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "modifierFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "itemBonusFieldName".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "sum-fields".to_string(),
+                action_label: "SumFields".to_string(),
+                free_form: false,
+                roles: vec![
+                    FieldRoleMetadata {
+                        key: "sumFieldName".to_string(),
+                        required: true,
+                        is_target: true,
+                        multiple: false,
+                        kind: FieldKind::NumericText,
+                    },
+                    FieldRoleMetadata {
+                        key: "sourceFieldNames".to_string(),
+                        required: false,
+                        is_target: false,
+                        multiple: true,
+                        kind: FieldKind::NumericText,
+                    },
+                ],
+            },
+            ActionTypeMetadata {
+                id: "formula".to_string(),
+                action_label: "Formula".to_string(),
+                free_form: true,
+                roles: vec![FieldRoleMetadata {
+                    key: "targetFieldName".to_string(),
+                    required: true,
+                    is_target: true,
+                    multiple: false,
+                    kind: FieldKind::NumericText,
+                }],
+            },
+        ]
+    }
+
+    pub fn ability_modifier<S1: Into<String>, S2: Into<String>>(
+        score_field_name: S1,
+        modifier_field_name: S2,
+    ) -> Self {
+        Self::AbilityModifier {
+            score_field_name: score_field_name.into(),
+            modifier_field_name: modifier_field_name.into(),
+        }
+    }
+
+    pub fn saving_throw_modifier<
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<String>,
+    >(
+        ability_modifier_field_name: S1,
+        proficiency_field_name: S2,
+        proficiency_bonus_field_name: S3,
+        saving_throw_modifier_field_name: S4,
+    ) -> Self {
+        Self::SavingThrowModifier {
+            ability_modifier_field_name: ability_modifier_field_name.into(),
+            proficiency_field_name: proficiency_field_name.into(),
+            proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
+            saving_throw_modifier_field_name: saving_throw_modifier_field_name.into(),
+        }
+    }
+
+    pub fn skill_modifier<
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<String>,
+        S5: Into<String>,
+        S6: Into<String>,
+    >(
+        ability_modifier_field_name: S1,
+        proficiency_field_name: S2,
+        expertise_field_name: Option<S5>,
+        half_prof_field_name: Option<S6>,
+        proficiency_bonus_field_name: S3,
+        skill_modifier_field_name: S4,
+    ) -> Self {
+        Self::SkillModifier {
+            ability_modifier_field_name: ability_modifier_field_name.into(),
+            proficiency_field_name: proficiency_field_name.into(),
+            expertise_field_name: expertise_field_name.map(|s| s.into()),
+            half_prof_field_name: half_prof_field_name.map(|s| s.into()),
+            proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
+            skill_modifier_field_name: skill_modifier_field_name.into(),
+        }
+    }
+
+    pub fn passive_score<S1: Into<String>, S2: Into<String>, S3: Into<String>>(
+        skill_modifier_field_name: S1,
+        bonus_field_name: Option<S3>,
+        advantage_field_name: Option<S3>,
+        disadvantage_field_name: Option<S3>,
+        passive_score_field_name: S2,
+    ) -> Self {
+        Self::PassiveScore {
+            skill_modifier_field_name: skill_modifier_field_name.into(),
+            bonus_field_name: bonus_field_name.map(|s| s.into()),
+            advantage_field_name: advantage_field_name.map(|s| s.into()),
+            disadvantage_field_name: disadvantage_field_name.map(|s| s.into()),
+            passive_score_field_name: passive_score_field_name.into(),
+        }
+    }
+
+    pub fn proficiency_bonus<S1: Into<String>, S2: Into<String>, S3: Into<String>>(
+        level_field_name: S1,
+        multiclass_level_field_names: Vec<S3>,
+        proficiency_bonus_field_name: S2,
+    ) -> Self {
+        Self::ProficiencyBonus {
+            level_field_name: level_field_name.into(),
+            multiclass_level_field_names: multiclass_level_field_names
+                .into_iter()
+                .map(|s| s.into())
+                .collect(),
+            proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
+        }
+    }
+
+    pub fn initiative<S1: Into<String>, S2: Into<String>, S3: Into<String>>(
+        dex_modifier_field_name: S1,
+        misc_bonus_field_names: Vec<S3>,
+        jack_of_all_trades_field_name: Option<S3>,
+        proficiency_bonus_field_name: Option<S3>,
+        initiative_field_name: S2,
+    ) -> Self {
+        Self::Initiative {
+            dex_modifier_field_name: dex_modifier_field_name.into(),
+            misc_bonus_field_names: misc_bonus_field_names
+                .into_iter()
+                .map(|s| s.into())
+                .collect(),
+            jack_of_all_trades_field_name: jack_of_all_trades_field_name.map(|s| s.into()),
+            proficiency_bonus_field_name: proficiency_bonus_field_name.map(|s| s.into()),
+            initiative_field_name: initiative_field_name.into(),
+        }
+    }
+
+    pub fn armor_class<S1: Into<String>, S2: Into<String>, S3: Into<String>, S4: Into<String>>(
+        base_armor_field_name: S1,
+        dex_modifier_field_name: S2,
+        max_dex_bonus_field_name: Option<S4>,
+        shield_field_name: Option<S4>,
+        misc_bonus_field_name: Option<S4>,
+        armor_class_field_name: S3,
+    ) -> Self {
+        Self::ArmorClass {
+            base_armor_field_name: base_armor_field_name.into(),
+            dex_modifier_field_name: dex_modifier_field_name.into(),
+            max_dex_bonus_field_name: max_dex_bonus_field_name.map(|s| s.into()),
+            shield_field_name: shield_field_name.map(|s| s.into()),
+            misc_bonus_field_name: misc_bonus_field_name.map(|s| s.into()),
+            armor_class_field_name: armor_class_field_name.into(),
+        }
     }
 
-    pub fn skill_modifier<
+    pub fn spell_save_dc<
         S1: Into<String>,
         S2: Into<String>,
         S3: Into<String>,
         S4: Into<String>,
         S5: Into<String>,
         S6: Into<String>,
+        S7: Into<String>,
     >(
-        ability_modifier_field_name: S1,
-        proficiency_field_name: S2,
-        expertise_field_name: Option<S5>,
-        half_prof_field_name: Option<S6>,
-        proficiency_bonus_field_name: S3,
-        skill_modifier_field_name: S4,
+        spellcasting_ability_field_name: S1,
+        intelligence_modifier_field_name: S2,
+        wisdom_modifier_field_name: S3,
+        charisma_modifier_field_name: S4,
+        proficiency_bonus_field_name: S5,
+        misc_bonus_field_name: Option<S7>,
+        spell_save_dc_field_name: S6,
     ) -> Self {
-        Self::SkillModifier {
-            ability_modifier_field_name: ability_modifier_field_name.into(),
+        Self::SpellSaveDc {
+            spellcasting_ability_field_name: spellcasting_ability_field_name.into(),
+            intelligence_modifier_field_name: intelligence_modifier_field_name.into(),
+            wisdom_modifier_field_name: wisdom_modifier_field_name.into(),
+            charisma_modifier_field_name: charisma_modifier_field_name.into(),
+            proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
+            misc_bonus_field_name: misc_bonus_field_name.map(|s| s.into()),
+            spell_save_dc_field_name: spell_save_dc_field_name.into(),
+        }
+    }
+
+    pub fn spell_attack_bonus<
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<String>,
+        S5: Into<String>,
+        S6: Into<String>,
+        S7: Into<String>,
+    >(
+        spellcasting_ability_field_name: S1,
+        intelligence_modifier_field_name: S2,
+        wisdom_modifier_field_name: S3,
+        charisma_modifier_field_name: S4,
+        proficiency_bonus_field_name: S5,
+        misc_bonus_field_name: Option<S7>,
+        spell_attack_bonus_field_name: S6,
+    ) -> Self {
+        Self::SpellAttackBonus {
+            spellcasting_ability_field_name: spellcasting_ability_field_name.into(),
+            intelligence_modifier_field_name: intelligence_modifier_field_name.into(),
+            wisdom_modifier_field_name: wisdom_modifier_field_name.into(),
+            charisma_modifier_field_name: charisma_modifier_field_name.into(),
+            proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
+            misc_bonus_field_name: misc_bonus_field_name.map(|s| s.into()),
+            spell_attack_bonus_field_name: spell_attack_bonus_field_name.into(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn weapon_attack_bonus<
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<String>,
+        S5: Into<String>,
+        S6: Into<String>,
+    >(
+        strength_modifier_field_name: S1,
+        dexterity_modifier_field_name: S2,
+        finesse_field_name: Option<S6>,
+        ranged_field_name: Option<S6>,
+        proficiency_field_name: S3,
+        proficiency_bonus_field_name: S4,
+        magic_bonus_field_name: Option<S6>,
+        attack_bonus_field_name: S5,
+    ) -> Self {
+        Self::WeaponAttackBonus {
+            strength_modifier_field_name: strength_modifier_field_name.into(),
+            dexterity_modifier_field_name: dexterity_modifier_field_name.into(),
+            finesse_field_name: finesse_field_name.map(|s| s.into()),
+            ranged_field_name: ranged_field_name.map(|s| s.into()),
             proficiency_field_name: proficiency_field_name.into(),
-            expertise_field_name: expertise_field_name.map(|s| s.into()),
-            half_prof_field_name: half_prof_field_name.map(|s| s.into()),
             proficiency_bonus_field_name: proficiency_bonus_field_name.into(),
-            skill_modifier_field_name: skill_modifier_field_name.into(),
+            magic_bonus_field_name: magic_bonus_field_name.map(|s| s.into()),
+            attack_bonus_field_name: attack_bonus_field_name.into(),
+        }
+    }
+
+    pub fn weapon_damage_bonus<
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<String>,
+    >(
+        strength_modifier_field_name: S1,
+        dexterity_modifier_field_name: S2,
+        finesse_field_name: Option<S4>,
+        ranged_field_name: Option<S4>,
+        magic_bonus_field_name: Option<S4>,
+        damage_bonus_field_name: S3,
+    ) -> Self {
+        Self::WeaponDamageBonus {
+            strength_modifier_field_name: strength_modifier_field_name.into(),
+            dexterity_modifier_field_name: dexterity_modifier_field_name.into(),
+            finesse_field_name: finesse_field_name.map(|s| s.into()),
+            ranged_field_name: ranged_field_name.map(|s| s.into()),
+            magic_bonus_field_name: magic_bonus_field_name.map(|s| s.into()),
+            damage_bonus_field_name: damage_bonus_field_name.into(),
+        }
+    }
+
+    pub fn carrying_capacity<S1: Into<String>, S2: Into<String>, S3: Into<String>>(
+        strength_score_field_name: S1,
+        size_field_name: Option<S3>,
+        carrying_capacity_field_name: S2,
+    ) -> Self {
+        Self::CarryingCapacity {
+            strength_score_field_name: strength_score_field_name.into(),
+            size_field_name: size_field_name.map(|s| s.into()),
+            carrying_capacity_field_name: carrying_capacity_field_name.into(),
+        }
+    }
+
+    pub fn encumbrance_status<S1: Into<String>, S2: Into<String>, S3: Into<String>>(
+        strength_score_field_name: S1,
+        weight_field_names: Vec<S3>,
+        size_field_name: Option<S3>,
+        encumbrance_status_field_name: S2,
+    ) -> Self {
+        Self::EncumbranceStatus {
+            strength_score_field_name: strength_score_field_name.into(),
+            weight_field_names: weight_field_names.into_iter().map(|s| s.into()).collect(),
+            size_field_name: size_field_name.map(|s| s.into()),
+            encumbrance_status_field_name: encumbrance_status_field_name.into(),
+        }
+    }
+
+    pub fn proficiency_rank_modifier<
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<String>,
+        S5: Into<String>,
+    >(
+        ability_modifier_field_name: S1,
+        proficiency_rank_field_name: S2,
+        level_field_name: S3,
+        item_bonus_field_name: Option<S5>,
+        modifier_field_name: S4,
+    ) -> Self {
+        Self::ProficiencyRankModifier {
+            ability_modifier_field_name: ability_modifier_field_name.into(),
+            proficiency_rank_field_name: proficiency_rank_field_name.into(),
+            level_field_name: level_field_name.into(),
+            item_bonus_field_name: item_bonus_field_name.map(|s| s.into()),
+            modifier_field_name: modifier_field_name.into(),
+        }
+    }
+
+    pub fn sum_fields<S1: Into<String>, S2: Into<String>>(
+        source_field_names: Vec<S2>,
+        source_field_pattern: Option<S2>,
+        sum_field_name: S1,
+    ) -> Self {
+        Self::SumFields {
+            source_field_names: source_field_names.into_iter().map(|s| s.into()).collect(),
+            source_field_pattern: source_field_pattern.map(|s| s.into()),
+            sum_field_name: sum_field_name.into(),
+        }
+    }
+
+    pub fn formula<S: Into<String>>(expression: Formula, target_field_name: S) -> Self {
+        Self::Formula {
+            expression,
+            target_field_name: target_field_name.into(),
         }
     }
 }
@@ -304,6 +2089,13 @@ mod tests {
         assert_eq!(js, r#"calculateModifierFromScore("STR");"#);
     }
 
+    #[test]
+    fn test_generate_js_uses_fully_qualified_field_names() {
+        let action = CalculationAction::ability_modifier("Abilities.STR", "Skills.Athletics");
+        let js = action.generate_js().unwrap();
+        assert_eq!(js, r#"calculateModifierFromScore("Abilities.STR");"#);
+    }
+
     #[test]
     fn test_saving_throw_modifier_generate_js() {
         let action = CalculationAction::saving_throw_modifier(
@@ -353,13 +2145,431 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_passive_score_generate_js_with_optional_fields() {
+        let action =
+            CalculationAction::passive_score("Perception", None::<&str>, None, None, "Passive");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculatePassiveFromFields("Perception", undefined, undefined, undefined);"#
+        );
+    }
+
+    #[test]
+    fn test_passive_score_generate_js_with_all_fields() {
+        let action = CalculationAction::passive_score(
+            "Perception",
+            Some("Observant"),
+            Some("Perception Adv"),
+            Some("Perception Dis"),
+            "Passive",
+        );
+        assert_eq!(action.action_label(), "PassiveScore");
+        assert_eq!(action.target_field(), "Passive");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculatePassiveFromFields("Perception", "Observant", "Perception Adv", "Perception Dis");"#
+        );
+    }
+
+    #[test]
+    fn test_proficiency_bonus_generate_js() {
+        let action =
+            CalculationAction::proficiency_bonus("Level", Vec::<String>::new(), "ProfBonus");
+        assert_eq!(action.action_label(), "ProficiencyBonus");
+        assert_eq!(action.target_field(), "ProfBonus");
+        let js = action.generate_js().unwrap();
+        assert_eq!(js, r#"calculateProficiencyBonusFromFields("Level", []);"#);
+    }
+
+    #[test]
+    fn test_proficiency_bonus_generate_js_sums_multiclass_levels() {
+        let action =
+            CalculationAction::proficiency_bonus("Level", vec!["Level 2", "Level 3"], "ProfBonus");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateProficiencyBonusFromFields("Level", ["Level 2","Level 3"]);"#
+        );
+        assert_eq!(action.input_fields(), vec!["Level", "Level 2", "Level 3"]);
+    }
+
+    #[test]
+    fn test_proficiency_bonus_deserializes_without_multiclass_levels() {
+        let action: CalculationAction = serde_json::from_value(serde_json::json!({
+            "ProficiencyBonus": {"levelFieldName": "Level", "proficiencyBonusFieldName": "ProfBonus"}
+        }))
+        .unwrap();
+        assert_eq!(action.input_fields(), vec!["Level"]);
+    }
+
+    #[test]
+    fn test_initiative_generate_js() {
+        let action = CalculationAction::initiative(
+            "DEXmod",
+            vec!["Alert"],
+            Some("Jack"),
+            Some("ProfBonus"),
+            "Initiative",
+        );
+        assert_eq!(action.action_label(), "Initiative");
+        assert_eq!(action.target_field(), "Initiative");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateInitiativeFromFields("DEXmod", ["Alert"], "Jack", "ProfBonus");"#
+        );
+    }
+
+    #[test]
+    fn test_initiative_generate_js_rejects_jack_of_all_trades_without_proficiency_bonus() {
+        let action =
+            CalculationAction::initiative("DEXmod", Vec::new(), Some("Jack"), None, "Initiative");
+        let result = action.generate_js();
+        assert!(matches!(result, Err(ActionError::InvalidAction(_))));
+    }
+
+    #[test]
+    fn test_armor_class_generate_js_with_optional_fields() {
+        let action =
+            CalculationAction::armor_class("Armor", "DEXmod", None::<String>, None, None, "AC");
+        assert_eq!(action.action_label(), "ArmorClass");
+        assert_eq!(action.target_field(), "AC");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateArmorClassFromFields("Armor", "DEXmod", undefined, undefined, undefined);"#
+        );
+        assert_eq!(action.input_fields(), vec!["Armor", "DEXmod"]);
+    }
+
+    #[test]
+    fn test_spell_save_dc_generate_js() {
+        let action = CalculationAction::spell_save_dc(
+            "SpellcastingAbility",
+            "INTmod",
+            "WISmod",
+            "CHAmod",
+            "ProfBonus",
+            None::<String>,
+            "SpellSaveDC",
+        );
+        assert_eq!(action.action_label(), "SpellSaveDc");
+        assert_eq!(action.target_field(), "SpellSaveDC");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateSpellSaveDcFromFields("SpellcastingAbility", "INTmod", "WISmod", "CHAmod", "ProfBonus", undefined);"#
+        );
+    }
+
+    #[test]
+    fn test_spell_attack_bonus_generate_js() {
+        let action = CalculationAction::spell_attack_bonus(
+            "SpellcastingAbility",
+            "INTmod",
+            "WISmod",
+            "CHAmod",
+            "ProfBonus",
+            Some("SpellMisc"),
+            "SpellAtkBonus",
+        );
+        assert_eq!(action.target_field(), "SpellAtkBonus");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateSpellAttackFromFields("SpellcastingAbility", "INTmod", "WISmod", "CHAmod", "ProfBonus", "SpellMisc");"#
+        );
+        assert_eq!(
+            action.input_fields(),
+            vec![
+                "SpellcastingAbility",
+                "INTmod",
+                "WISmod",
+                "CHAmod",
+                "ProfBonus",
+                "SpellMisc"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_fields_accepts_choice_or_text_spellcasting_ability() {
+        let action = CalculationAction::spell_save_dc(
+            "SpellcastingAbility",
+            "INTmod",
+            "WISmod",
+            "CHAmod",
+            "ProfBonus",
+            None::<String>,
+            "SpellSaveDC",
+        );
+        let mut fields = vec![
+            FormField::new("SpellcastingAbility", FormFieldKind::Choice),
+            FormField::new("INTmod", FormFieldKind::Text),
+            FormField::new("WISmod", FormFieldKind::Text),
+            FormField::new("CHAmod", FormFieldKind::Text),
+            FormField::new("ProfBonus", FormFieldKind::Text),
+            FormField::new("SpellSaveDC", FormFieldKind::Text),
+        ];
+        assert_eq!(action.validate_fields(&fields), Ok(()));
+
+        fields[0] = FormField::new("SpellcastingAbility", FormFieldKind::Text);
+        assert_eq!(action.validate_fields(&fields), Ok(()));
+
+        fields[0] = FormField::new("SpellcastingAbility", FormFieldKind::Checkbox);
+        assert!(action.validate_fields(&fields).is_err());
+    }
+
+    #[test]
+    fn test_weapon_attack_bonus_generate_js() {
+        let action = CalculationAction::weapon_attack_bonus(
+            "STRmod",
+            "DEXmod",
+            Some("Wpn1 Finesse"),
+            None,
+            "Wpn1 Prof",
+            "ProfBonus",
+            Some("Wpn1 Magic"),
+            "Wpn1 AtkBonus",
+        );
+        assert_eq!(action.action_label(), "WeaponAttackBonus");
+        assert_eq!(action.target_field(), "Wpn1 AtkBonus");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateWeaponAttackFromFields("STRmod", "DEXmod", "Wpn1 Finesse", undefined, "Wpn1 Prof", "ProfBonus", "Wpn1 Magic");"#
+        );
+    }
+
+    #[test]
+    fn test_weapon_damage_bonus_generate_js() {
+        let action = CalculationAction::weapon_damage_bonus(
+            "STRmod",
+            "DEXmod",
+            None,
+            Some("Wpn1 Ranged"),
+            None,
+            "Wpn1 Damage",
+        );
+        assert_eq!(action.action_label(), "WeaponDamageBonus");
+        assert_eq!(action.target_field(), "Wpn1 Damage");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateWeaponDamageFromFields("STRmod", "DEXmod", undefined, "Wpn1 Ranged", undefined);"#
+        );
+        assert_eq!(
+            action.input_fields(),
+            vec!["STRmod", "DEXmod", "Wpn1 Ranged"]
+        );
+    }
+
+    #[test]
+    fn test_carrying_capacity_generate_js() {
+        let action = CalculationAction::carrying_capacity("STR", Some("Size"), "Capacity");
+        assert_eq!(action.action_label(), "CarryingCapacity");
+        assert_eq!(action.target_field(), "Capacity");
+        let js = action.generate_js().unwrap();
+        assert_eq!(js, r#"calculateCarryingCapacityFromFields("STR", "Size");"#);
+    }
+
+    #[test]
+    fn test_encumbrance_status_generate_js() {
+        let action =
+            CalculationAction::encumbrance_status("STR", vec!["Wgt1", "Wgt2"], None, "Encumbrance");
+        assert_eq!(action.action_label(), "EncumbranceStatus");
+        assert_eq!(action.target_field(), "Encumbrance");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateEncumbranceStatusFromFields("STR", ["Wgt1","Wgt2"], undefined);"#
+        );
+        assert_eq!(action.input_fields(), vec!["STR", "Wgt1", "Wgt2"]);
+    }
+
+    #[test]
+    fn test_proficiency_rank_modifier_generate_js() {
+        let action = CalculationAction::proficiency_rank_modifier(
+            "DEX_MOD",
+            "Acrobatics Rank",
+            "Level",
+            Some("Acrobatics Item"),
+            "Acrobatics",
+        );
+        assert_eq!(action.action_label(), "ProficiencyRankModifier");
+        assert_eq!(action.target_field(), "Acrobatics");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"calculateRankedModifierFromFields("DEX_MOD", "Acrobatics Rank", "Level", "Acrobatics Item");"#
+        );
+        assert_eq!(
+            action.input_fields(),
+            vec!["DEX_MOD", "Acrobatics Rank", "Level", "Acrobatics Item"]
+        );
+    }
+
+    #[test]
+    fn test_encumbrance_status_generate_js_rejects_missing_weight_fields() {
+        let action =
+            CalculationAction::encumbrance_status("STR", Vec::<&str>::new(), None, "Encumbrance");
+        let result = action.generate_js();
+        assert!(matches!(result, Err(ActionError::InvalidAction(_))));
+    }
+
+    #[test]
+    fn test_validate_fields_requires_text_field_for_text_output() {
+        let action =
+            CalculationAction::encumbrance_status("STR", vec!["Wgt1"], None, "Encumbrance");
+        let fields = vec![
+            FormField::new("STR", FormFieldKind::Text),
+            FormField::new("Wgt1", FormFieldKind::Text),
+            FormField::new("Encumbrance", FormFieldKind::Choice),
+        ];
+        assert_eq!(
+            action.validate_fields(&fields),
+            Err(ActionError::InvalidFieldMapping(vec![FieldRoleViolation {
+                role: "encumbranceStatusFieldName".to_string(),
+                field_name: "Encumbrance".to_string(),
+                problem: FieldRoleProblem::WrongKind {
+                    expected: FieldKind::Text,
+                    actual: FormFieldKind::Choice,
+                },
+            }]))
+        );
+    }
+
+    #[test]
+    fn test_sum_fields_generate_js() {
+        let action = CalculationAction::sum_fields(vec!["CP", "SP"], None, "Coins");
+        assert_eq!(action.action_label(), "SumFields");
+        assert_eq!(action.target_field(), "Coins");
+        let js = action.generate_js().unwrap();
+        assert_eq!(js, r#"calculateSumFromFields(["CP","SP"]);"#);
+    }
+
+    #[test]
+    fn test_sum_fields_generate_js_rejects_unexpanded_pattern() {
+        let action = CalculationAction::sum_fields(Vec::new(), Some("Wgt*"), "Total");
+        let result = action.generate_js();
+        assert!(matches!(result, Err(ActionError::InvalidAction(_))));
+    }
+
+    #[test]
+    fn test_expand_field_patterns_appends_matches_in_natural_order() {
+        let mut action = CalculationAction::sum_fields(vec!["Wgt3"], Some("Wgt*"), "WgtTotal");
+        let fields = vec![
+            FormField::new("Wgt10", FormFieldKind::Text),
+            FormField::new("Wgt2", FormFieldKind::Text),
+            FormField::new("WgtTotal", FormFieldKind::Text),
+            FormField::new("Wgt3", FormFieldKind::Text),
+            FormField::new("Name", FormFieldKind::Text),
+            FormField::new("Wgt1", FormFieldKind::Text),
+        ];
+
+        action.expand_field_patterns(&fields).unwrap();
+
+        assert_eq!(action.input_fields(), vec!["Wgt3", "Wgt1", "Wgt2", "Wgt10"]);
+    }
+
+    #[test]
+    fn test_expand_field_patterns_reports_pattern_without_matches() {
+        let mut action = CalculationAction::sum_fields(Vec::new(), Some("Coin ??"), "Coins");
+        let fields = vec![FormField::new("Coin GPX", FormFieldKind::Text)];
+
+        let result = action.expand_field_patterns(&fields);
+
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidFieldMapping(vec![FieldRoleViolation {
+                role: "sourceFieldPattern".to_string(),
+                field_name: "Coin ??".to_string(),
+                problem: FieldRoleProblem::Missing,
+            }]))
+        );
+    }
+
+    #[test]
+    fn test_matches_field_pattern() {
+        let cases = [
+            ("Wgt*", "Wgt12", true),
+            ("Wgt*", "Wgt", true),
+            ("Wgt*", "Name", false),
+            ("*Wgt", "Item Wgt", true),
+            ("Wpn? AtkBonus", "Wpn1 AtkBonus", true),
+            ("Wpn? AtkBonus", "Wpn12 AtkBonus", false),
+            ("Eq*.Wgt*", "Eq.A.Wgt1", true),
+            ("Wgt", "Wgt1", false),
+        ];
+
+        for (pattern, name, expected) in cases {
+            assert_eq!(
+                matches_field_pattern(pattern, name),
+                expected,
+                "{} ~ {}",
+                pattern,
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_formula_generate_js() {
+        let action = CalculationAction::formula(
+            Formula::parse("{STR} * 15 + {Powerful Build}").unwrap(),
+            "Capacity",
+        );
+        assert_eq!(action.action_label(), "Formula");
+        assert_eq!(action.target_field(), "Capacity");
+        let js = action.generate_js().unwrap();
+        assert_eq!(
+            js,
+            r#"event.value = calculateFormulaResult(((getFormulaValueFromField("STR") * 15) + getFormulaValueFromField("Powerful Build")));"#
+        );
+        assert_eq!(action.input_fields(), vec!["STR", "Powerful Build"]);
+    }
+
+    #[test]
+    fn test_formula_rejects_invalid_expression_on_deserialize() {
+        let result = serde_json::from_value::<CalculationAction>(serde_json::json!({
+            "Formula": {"expression": "{STR} ** 2", "targetFieldName": "Capacity"}
+        }));
+        let error = result.unwrap_err().to_string();
+        assert!(
+            error.contains("invalid formula at position 7: expected a value"),
+            "{}",
+            error
+        );
+    }
+
     #[test]
     fn test_action_type_catalog_has_all_variants() {
         let catalog = CalculationAction::action_type_catalog();
-        assert_eq!(catalog.len(), 3);
+        assert_eq!(catalog.len(), 16);
         assert_eq!(catalog[0].id, "ability-modifier");
         assert_eq!(catalog[1].id, "skill-modifier");
         assert_eq!(catalog[2].id, "saving-throw-modifier");
+        assert_eq!(catalog[3].id, "passive-score");
+        assert_eq!(catalog[4].id, "proficiency-bonus");
+        assert_eq!(catalog[5].id, "initiative");
+        assert_eq!(catalog[6].id, "armor-class");
+        assert_eq!(catalog[7].id, "spell-save-dc");
+        assert_eq!(catalog[8].id, "spell-attack-bonus");
+        assert_eq!(catalog[9].id, "weapon-attack-bonus");
+        assert_eq!(catalog[10].id, "weapon-damage-bonus");
+        assert_eq!(catalog[11].id, "carrying-capacity");
+        assert_eq!(catalog[12].id, "encumbrance-status");
+        assert_eq!(catalog[13].id, "proficiency-rank-modifier");
+        assert_eq!(catalog[14].id, "sum-fields");
+        assert_eq!(catalog[15].id, "formula");
+        assert!(
+            catalog
+                .iter()
+                .all(|meta| meta.free_form == (meta.id == "formula"))
+        );
     }
 
     #[test]
@@ -373,4 +2583,71 @@ mod tests {
         assert_eq!(ability_mod.roles[1].key, "abilityModifierFieldName");
         assert!(ability_mod.roles[1].is_target);
     }
+
+    #[test]
+    fn test_action_type_catalog_declares_role_kinds() {
+        let catalog = CalculationAction::action_type_catalog();
+        let skill_mod = &catalog[1];
+        let kind_of = |key: &str| {
+            skill_mod
+                .roles
+                .iter()
+                .find(|role| role.key == key)
+                .map(|role| role.kind)
+        };
+        assert_eq!(
+            kind_of("abilityModifierFieldName"),
+            Some(FieldKind::NumericText)
+        );
+        assert_eq!(kind_of("proficiencyFieldName"), Some(FieldKind::Checkbox));
+        assert_eq!(kind_of("halfProfFieldName"), Some(FieldKind::Checkbox));
+        assert_eq!(
+            kind_of("skillModifierFieldName"),
+            Some(FieldKind::NumericText)
+        );
+    }
+
+    #[test]
+    fn test_validate_fields_accepts_matching_sheet() {
+        let action = CalculationAction::skill_modifier(
+            "DEXmod",
+            "Acrobatics Prof",
+            None::<&str>,
+            Some("Acrobatics Half"),
+            "ProfBonus",
+            "Acrobatics",
+        );
+        let fields = vec![
+            FormField::new("DEXmod", FormFieldKind::Text),
+            FormField::new("Acrobatics Prof", FormFieldKind::Checkbox),
+            FormField::new("Acrobatics Half", FormFieldKind::Radio),
+            FormField::new("ProfBonus", FormFieldKind::Text),
+            FormField::new("Acrobatics", FormFieldKind::Text),
+        ];
+        assert_eq!(action.validate_fields(&fields), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_fields_reports_each_offending_role() {
+        let action = CalculationAction::ability_modifier("STR", "STRmod");
+        let fields = vec![FormField::new("STRmod", FormFieldKind::Checkbox)];
+        assert_eq!(
+            action.validate_fields(&fields),
+            Err(ActionError::InvalidFieldMapping(vec![
+                FieldRoleViolation {
+                    role: "abilityScoreFieldName".to_string(),
+                    field_name: "STR".to_string(),
+                    problem: FieldRoleProblem::Missing,
+                },
+                FieldRoleViolation {
+                    role: "abilityModifierFieldName".to_string(),
+                    field_name: "STRmod".to_string(),
+                    problem: FieldRoleProblem::WrongKind {
+                        expected: FieldKind::NumericText,
+                        actual: FormFieldKind::Checkbox,
+                    },
+                },
+            ]))
+        );
+    }
 }
@@ -0,0 +1,618 @@
+use crate::action::{ActionTypeMetadata, CalculationAction, FieldKind, FieldRoleMetadata};
+use crate::error::ActionError;
+use crate::system::{GameSystem, find_game_system};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// An action type declared in a YAML or JSON file rather than compiled into
+/// [`CalculationAction`], so house rules can ship without a release:
+///
+/// ```yaml
+/// id: hero-points
+/// label: HeroPoints
+/// systems: [dnd5e]
+/// roles:
+///   - key: levelFieldName
+///     kind: numericText
+///   - key: heroPointsFieldName
+///     kind: numericText
+///     target: true
+/// script: calculateHeroPointsFromFields({{levelFieldName}});
+/// helpers: |
+///   function calculateHeroPointsFromFields(levelField) {
+///     event.value = 5 + Math.floor(getNumberValueFromField(levelField) / 2);
+///   }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ActionDefinition {
+    /// Kebab-case catalog id, also the key of the action's translations.
+    pub id: String,
+    /// PascalCase label keying the action in requests and stored mappings.
+    pub label: String,
+    /// Game systems offering the action; every system when empty.
+    #[serde(default)]
+    pub systems: Vec<String>,
+    pub roles: Vec<RoleDefinition>,
+    /// Calculation script. Each `{{roleKey}}` is replaced with the mapped field name, a JSON
+    /// array of names for `multiple` roles, or `undefined` for an unmapped optional role.
+    pub script: String,
+    /// Document-level JS the script calls, installed alongside the game system's helpers.
+    #[serde(default)]
+    pub helpers: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RoleDefinition {
+    pub key: String,
+    pub kind: FieldKind,
+    #[serde(default = "default_required")]
+    pub required: bool,
+    #[serde(default)]
+    pub target: bool,
+    #[serde(default)]
+    pub multiple: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl ActionDefinition {
+    pub fn from_yaml(source: &str) -> Result<Self, ActionError> {
+        serde_yaml::from_str(source)
+            .map_err(|e| ActionError::InvalidActionDefinition(e.to_string()))
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, ActionError> {
+        serde_json::from_str(source)
+            .map_err(|e| ActionError::InvalidActionDefinition(e.to_string()))
+    }
+
+    /// Checks the definition is usable and does not shadow a built-in action type.
+    pub fn validate(&self) -> Result<(), ActionError> {
+        let invalid = |message: String| {
+            ActionError::InvalidActionDefinition(format!("{}: {}", self.id, message))
+        };
+
+        if self.id.is_empty()
+            || !self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(invalid("id must be kebab-case".to_string()));
+        }
+        if !self.label.starts_with(|c: char| c.is_ascii_uppercase())
+            || !self.label.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(invalid(format!(
+                "label '{}' must be PascalCase",
+                self.label
+            )));
+        }
+        if CalculationAction::action_type_catalog()
+            .iter()
+            .any(|meta| meta.id == self.id || meta.action_label == self.label)
+        {
+            return Err(invalid("shadows a built-in action type".to_string()));
+        }
+        for system in &self.systems {
+            find_game_system(system).map_err(|e| invalid(e.to_string()))?;
+        }
+
+        for (index, role) in self.roles.iter().enumerate() {
+            if !role.key.starts_with(|c: char| c.is_ascii_alphabetic())
+                || !role.key.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                return Err(invalid(format!(
+                    "role key '{}' must be alphanumeric",
+                    role.key
+                )));
+            }
+            if self.roles[..index]
+                .iter()
+                .any(|other| other.key == role.key)
+            {
+                return Err(invalid(format!("role '{}' is declared twice", role.key)));
+            }
+        }
+        match self
+            .roles
+            .iter()
+            .filter(|role| role.target)
+            .collect::<Vec<_>>()[..]
+        {
+            [target] if target.required && !target.multiple => {}
+            [target] => {
+                return Err(invalid(format!(
+                    "target role '{}' must be required and take a single field",
+                    target.key
+                )));
+            }
+            _ => return Err(invalid("exactly one role must be the target".to_string())),
+        }
+
+        if self.script.trim().is_empty() {
+            return Err(invalid("script is empty".to_string()));
+        }
+        render_placeholders(&self.script, |key| {
+            if self.roles.iter().any(|role| role.key == key) {
+                Ok(String::new())
+            } else {
+                Err(format!("script references unknown role '{}'", key))
+            }
+        })
+        .map_err(invalid)?;
+
+        Ok(())
+    }
+
+    pub fn applies_to(&self, system: &dyn GameSystem) -> bool {
+        self.systems.is_empty() || self.systems.iter().any(|id| id == system.id())
+    }
+
+    pub fn target_role(&self) -> Option<&RoleDefinition> {
+        self.roles.iter().find(|role| role.target)
+    }
+
+    /// Returns the catalog entry of the action type, listing roles in declaration order.
+    pub fn metadata(&self) -> ActionTypeMetadata {
+        ActionTypeMetadata {
+            id: self.id.clone(),
+            action_label: self.label.clone(),
+            free_form: false,
+            roles: self
+                .roles
+                .iter()
+                .map(|role| FieldRoleMetadata {
+                    key: role.key.clone(),
+                    required: role.required,
+                    is_target: role.target,
+                    multiple: role.multiple,
+                    kind: role.kind,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Replaces every `{{key}}` in `script` with `resolve(key)`.
+fn render_placeholders(
+    script: &str,
+    mut resolve: impl FnMut(&str) -> Result<String, String>,
+) -> Result<String, String> {
+    let mut rendered = String::with_capacity(script.len());
+    let mut rest = script;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "script has an unclosed '{{' placeholder".to_string())?;
+        rendered.push_str(&resolve(after[..end].trim())?);
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// The field name, or names for a `multiple` role, mapped to a role of a [`CustomAction`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FieldNames {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl FieldNames {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(name) => std::slice::from_ref(name),
+            Self::Many(names) => names,
+        }
+    }
+}
+
+/// An action of a type declared by an [`ActionDefinition`], mapping its role keys to fields.
+///
+/// The definition is resolved by [`ActionDefinitions::bind`]; until then the action has no
+/// target field and cannot generate its script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAction {
+    /// Label of the definition.
+    pub action_type: String,
+    pub fields: BTreeMap<String, FieldNames>,
+    definition: Option<Arc<ActionDefinition>>,
+}
+
+impl CustomAction {
+    pub fn new(action_type: impl Into<String>, fields: BTreeMap<String, FieldNames>) -> Self {
+        Self {
+            action_type: action_type.into(),
+            fields,
+            definition: None,
+        }
+    }
+
+    pub fn definition(&self) -> Option<&ActionDefinition> {
+        self.definition.as_deref()
+    }
+
+    pub fn target_role(&self) -> Option<&str> {
+        self.definition()
+            .and_then(ActionDefinition::target_role)
+            .map(|role| role.key.as_str())
+    }
+
+    pub fn target_field(&self) -> &str {
+        self.target_role()
+            .and_then(|role| self.fields.get(role))
+            .and_then(|names| names.as_slice().first())
+            .map_or("", String::as_str)
+    }
+
+    pub fn generate_js(&self) -> Result<String, ActionError> {
+        let definition = self.definition().ok_or_else(|| {
+            ActionError::InvalidAction(format!("unknown action type: {}", self.action_type))
+        })?;
+
+        render_placeholders(&definition.script, |key| {
+            let multiple = definition
+                .roles
+                .iter()
+                .any(|role| role.key == key && role.multiple);
+            let rendered = match (self.fields.get(key), multiple) {
+                (Some(names), true) => serde_json::to_string(names.as_slice()),
+                (Some(names), false) => serde_json::to_string(&names.as_slice()[0]),
+                (None, true) => Ok("[]".to_string()),
+                (None, false) => Ok("undefined".to_string()),
+            };
+            rendered.map_err(|e| format!("failed to serialize field name: {}", e))
+        })
+        .map_err(ActionError::InvalidAction)
+    }
+
+    /// Attaches `definition`, checking the mapped roles against the ones it declares.
+    fn bind(&mut self, definition: Arc<ActionDefinition>) -> Result<(), ActionError> {
+        for (key, names) in &self.fields {
+            let role = definition
+                .roles
+                .iter()
+                .find(|role| role.key == *key)
+                .ok_or_else(|| {
+                    ActionError::InvalidAction(format!(
+                        "{} has no role '{}'",
+                        self.action_type, key
+                    ))
+                })?;
+            if names.as_slice().is_empty() || (!role.multiple && names.as_slice().len() > 1) {
+                return Err(ActionError::InvalidAction(format!(
+                    "role '{}' of {} takes {}",
+                    key,
+                    self.action_type,
+                    if role.multiple {
+                        "at least one field"
+                    } else {
+                        "a single field"
+                    }
+                )));
+            }
+        }
+        if let Some(role) = definition
+            .roles
+            .iter()
+            .find(|role| role.required && !self.fields.contains_key(&role.key))
+        {
+            return Err(ActionError::InvalidAction(format!(
+                "{} is missing required role '{}'",
+                self.action_type, role.key
+            )));
+        }
+
+        self.definition = Some(definition);
+        Ok(())
+    }
+}
+
+/// The action definitions loaded at startup, merged into every game system's catalog.
+#[derive(Debug, Clone, Default)]
+pub struct ActionDefinitions {
+    definitions: Vec<Arc<ActionDefinition>>,
+}
+
+impl ActionDefinitions {
+    /// Validates `definitions`, rejecting two definitions with the same id or label.
+    pub fn new(definitions: Vec<ActionDefinition>) -> Result<Self, ActionError> {
+        let mut loaded: Vec<Arc<ActionDefinition>> = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            definition.validate()?;
+            if loaded
+                .iter()
+                .any(|other| other.id == definition.id || other.label == definition.label)
+            {
+                return Err(ActionError::InvalidActionDefinition(format!(
+                    "{}: declared more than once",
+                    definition.id
+                )));
+            }
+            loaded.push(Arc::new(definition));
+        }
+        Ok(Self {
+            definitions: loaded,
+        })
+    }
+
+    /// Loads every `.yaml`, `.yml` and `.json` file in `dir`, in file name order.
+    pub fn load_dir(dir: &Path) -> Result<Self, ActionError> {
+        let read_error = |path: &Path, e: std::io::Error| {
+            ActionError::InvalidActionDefinition(format!("{}: {}", path.display(), e))
+        };
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| read_error(dir, e))? {
+            let path = entry.map_err(|e| read_error(dir, e))?.path();
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut definitions = Vec::new();
+        for path in paths {
+            let parse = match path.extension().and_then(|extension| extension.to_str()) {
+                Some("yaml" | "yml") => ActionDefinition::from_yaml,
+                Some("json") => ActionDefinition::from_json,
+                _ => continue,
+            };
+            let source = fs::read_to_string(&path).map_err(|e| read_error(&path, e))?;
+            let definition = parse(&source).map_err(|e| match e {
+                ActionError::InvalidActionDefinition(message) => {
+                    ActionError::InvalidActionDefinition(format!("{}: {}", path.display(), message))
+                }
+                e => e,
+            })?;
+            definitions.push(definition);
+        }
+
+        Self::new(definitions)
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    pub fn find(&self, label: &str) -> Option<&ActionDefinition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.label == label)
+            .map(Arc::as_ref)
+    }
+
+    /// Whether `system` offers the action type, built in or defined.
+    pub fn supports(&self, system: &dyn GameSystem, action_label: &str) -> bool {
+        system.supports(action_label)
+            || self
+                .find(action_label)
+                .is_some_and(|definition| definition.applies_to(system))
+    }
+
+    /// Returns the system's catalog followed by the definitions it offers.
+    pub fn action_type_catalog(&self, system: &dyn GameSystem) -> Vec<ActionTypeMetadata> {
+        let mut catalog = system.action_type_catalog();
+        catalog.extend(
+            self.definitions
+                .iter()
+                .filter(|definition| definition.applies_to(system))
+                .map(|definition| definition.metadata()),
+        );
+        catalog
+    }
+
+    /// Returns the system's helper JS followed by the helpers of the definitions it offers.
+    pub fn helpers_js(&self, system: &dyn GameSystem) -> String {
+        let mut helpers_js = system.helpers_js().to_string();
+        for definition in &self.definitions {
+            if definition.applies_to(system) && !definition.helpers.is_empty() {
+                helpers_js.push('\n');
+                helpers_js.push_str(&definition.helpers);
+            }
+        }
+        helpers_js
+    }
+
+    /// Resolves the definition of a custom action; built-in actions are left untouched.
+    pub fn bind(&self, action: &mut CalculationAction) -> Result<(), ActionError> {
+        let CalculationAction::Custom(custom) = action else {
+            return Ok(());
+        };
+        let definition = self
+            .definitions
+            .iter()
+            .find(|definition| definition.label == custom.action_type)
+            .ok_or_else(|| {
+                ActionError::InvalidAction(format!("unknown action type: {}", custom.action_type))
+            })?;
+        custom.bind(definition.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::{Dnd5e, Pathfinder2e};
+    use pretty_assertions::assert_eq;
+
+    const HERO_POINTS: &str = r#"
+id: hero-points
+label: HeroPoints
+systems: [pf2e]
+roles:
+  - key: levelFieldName
+    kind: numericText
+  - key: bonusFieldNames
+    kind: numericText
+    required: false
+    multiple: true
+  - key: noteFieldName
+    kind: text
+    required: false
+  - key: heroPointsFieldName
+    kind: numericText
+    target: true
+script: calculateHeroPointsFromFields({{levelFieldName}}, {{ bonusFieldNames }}, {{noteFieldName}});
+helpers: |
+  function calculateHeroPointsFromFields(levelField, bonusFields, noteField) {}
+"#;
+
+    fn hero_points() -> ActionDefinitions {
+        ActionDefinitions::new(vec![ActionDefinition::from_yaml(HERO_POINTS).unwrap()]).unwrap()
+    }
+
+    fn custom_action(json: serde_json::Value) -> CalculationAction {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_catalog_and_helpers_include_definitions_of_the_system() {
+        let definitions = hero_points();
+
+        let catalog = definitions.action_type_catalog(&Pathfinder2e);
+        let hero_points = catalog.last().unwrap();
+        assert_eq!(hero_points.id, "hero-points");
+        assert_eq!(hero_points.action_label, "HeroPoints");
+        assert_eq!(hero_points.roles.len(), 4);
+        assert!(hero_points.roles[3].is_target);
+        assert!(hero_points.roles[1].multiple);
+
+        assert!(definitions.supports(&Pathfinder2e, "HeroPoints"));
+        assert!(!definitions.supports(&Dnd5e, "HeroPoints"));
+        assert_eq!(
+            definitions.action_type_catalog(&Dnd5e).len(),
+            Dnd5e.action_type_catalog().len()
+        );
+        assert!(definitions.helpers_js(&Pathfinder2e).ends_with(
+            "function calculateHeroPointsFromFields(levelField, bonusFields, noteField) {}\n"
+        ));
+        assert_eq!(definitions.helpers_js(&Dnd5e), Dnd5e.helpers_js());
+    }
+
+    #[test]
+    fn test_bound_custom_action_generates_script_from_template() {
+        let mut action = custom_action(serde_json::json!({
+            "HeroPoints": {"levelFieldName": "Level", "bonusFieldNames": ["Bonus1", "Bonus2"], "heroPointsFieldName": "Hero"}
+        }));
+        hero_points().bind(&mut action).unwrap();
+
+        assert_eq!(action.action_label(), "HeroPoints");
+        assert_eq!(action.target_field(), "Hero");
+        assert_eq!(action.input_fields(), vec!["Bonus1", "Bonus2", "Level"]);
+        assert_eq!(
+            action.generate_js().unwrap(),
+            r#"calculateHeroPointsFromFields("Level", ["Bonus1","Bonus2"], undefined);"#
+        );
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "HeroPoints": {"bonusFieldNames": ["Bonus1", "Bonus2"], "heroPointsFieldName": "Hero", "levelFieldName": "Level"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_bind_rejects_mapping_that_does_not_fit_the_definition() {
+        let definitions = hero_points();
+        let bind = |json| definitions.bind(&mut custom_action(json)).unwrap_err();
+
+        assert_eq!(
+            bind(serde_json::json!({"HeroPoints": {"levelFieldName": "Level"}})),
+            ActionError::InvalidAction(
+                "HeroPoints is missing required role 'heroPointsFieldName'".to_string()
+            )
+        );
+        assert_eq!(
+            bind(
+                serde_json::json!({"HeroPoints": {"levelFieldName": "Level", "heroPointsFieldName": "Hero", "luckFieldName": "Luck"}})
+            ),
+            ActionError::InvalidAction("HeroPoints has no role 'luckFieldName'".to_string())
+        );
+        assert_eq!(
+            bind(
+                serde_json::json!({"HeroPoints": {"levelFieldName": ["A", "B"], "heroPointsFieldName": "Hero"}})
+            ),
+            ActionError::InvalidAction(
+                "role 'levelFieldName' of HeroPoints takes a single field".to_string()
+            )
+        );
+        assert_eq!(
+            bind(serde_json::json!({"VillainPoints": {"levelFieldName": "Level"}})),
+            ActionError::InvalidAction("unknown action type: VillainPoints".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_unusable_definitions() {
+        let invalid = |patch: &dyn Fn(&mut ActionDefinition)| {
+            let mut definition = ActionDefinition::from_yaml(HERO_POINTS).unwrap();
+            patch(&mut definition);
+            match definition.validate() {
+                Err(ActionError::InvalidActionDefinition(message)) => message,
+                result => panic!("expected an invalid definition, got {:?}", result),
+            }
+        };
+
+        assert_eq!(
+            invalid(&|d| d.label = "AbilityModifier".to_string()),
+            "hero-points: shadows a built-in action type"
+        );
+        assert_eq!(
+            invalid(&|d| d.id = "Hero Points".to_string()),
+            "Hero Points: id must be kebab-case"
+        );
+        assert_eq!(
+            invalid(&|d| d.systems = vec!["dnd4e".to_string()]),
+            "hero-points: unknown game system: dnd4e"
+        );
+        assert_eq!(
+            invalid(&|d| d.roles[0].target = true),
+            "hero-points: exactly one role must be the target"
+        );
+        assert_eq!(
+            invalid(&|d| d.script = "calculate({{levelFieldName}}, {{luck}});".to_string()),
+            "hero-points: script references unknown role 'luck'"
+        );
+        assert_eq!(
+            invalid(&|d| d.script = "calculate({{levelFieldName);".to_string()),
+            "hero-points: script has an unclosed '{{' placeholder"
+        );
+    }
+
+    #[test]
+    fn test_load_dir_reads_yaml_and_json_definitions() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("hero-points.yaml"), HERO_POINTS).unwrap();
+        fs::write(
+            dir.path().join("luck.json"),
+            r#"{"id": "luck", "label": "Luck", "roles": [{"key": "luckFieldName", "kind": "numericText", "target": true}], "script": "event.value = 1;"}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), "not a definition").unwrap();
+
+        let definitions = ActionDefinitions::load_dir(dir.path()).unwrap();
+
+        assert_eq!(definitions.len(), 2);
+        assert!(definitions.find("Luck").is_some());
+
+        fs::write(dir.path().join("broken.yml"), "id: [").unwrap();
+        let error = ActionDefinitions::load_dir(dir.path()).unwrap_err();
+        assert!(error.to_string().contains("broken.yml"), "{}", error);
+    }
+}
@@ -1,6 +1,57 @@
+use crate::action::FieldKind;
+use crate::ports::driven::FormFieldKind;
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Why a mapped field cannot fill its role.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldRoleProblem {
+    /// The sheet has no form field with the mapped name.
+    Missing,
+    /// The form field exists but is not of the kind the role expects.
+    WrongKind {
+        expected: FieldKind,
+        actual: FormFieldKind,
+    },
+}
+
+/// A role of a calculation action whose mapped field does not fit the sheet.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{role}: {}", describe_problem(field_name, problem))]
+pub struct FieldRoleViolation {
+    /// Catalog key of the role, e.g. `abilityScoreFieldName`.
+    pub role: String,
+    pub field_name: String,
+    pub problem: FieldRoleProblem,
+}
+
+fn describe_problem(field_name: &str, problem: &FieldRoleProblem) -> String {
+    match problem {
+        FieldRoleProblem::Missing => format!("field \"{}\" not found in PDF sheet", field_name),
+        FieldRoleProblem::WrongKind { expected, actual } => format!(
+            "field \"{}\" is a {}, expected a {}",
+            field_name, actual, expected
+        ),
+    }
+}
+
+/// A formula that cannot be parsed; `position` is the byte offset of the offending input.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("invalid formula at position {position}: {message}")]
+pub struct FormulaError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl FormulaError {
+    pub fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ActionError {
     #[error("sheet not found: {0}")]
@@ -15,8 +66,30 @@ pub enum ActionError {
     SavePdfError,
     #[error("field not found in PDF sheet: {0}")]
     FieldNotFound(String),
+    #[error("attached action not found: {0}")]
+    ActionNotFound(Uuid),
     #[error("invalid action: {0}")]
     InvalidAction(String),
+    #[error("invalid field mapping: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidFieldMapping(Vec<FieldRoleViolation>),
+    #[error("calculation cycle: {}", .0.join(" -> "))]
+    CalculationCycle(Vec<String>),
+    #[error("invalid action definition: {0}")]
+    InvalidActionDefinition(String),
+    #[error("unknown game system: {0}")]
+    UnknownGameSystem(String),
+    #[error("{system} does not support {action} actions")]
+    UnsupportedAction { system: String, action: String },
+    #[error("sheet already has {action} actions, which {system} does not support")]
+    GameSystemMismatch { system: String, action: String },
+    #[error("mapping template not found: {0}")]
+    TemplateNotFound(Uuid),
+    #[error("invalid mapping template: {0}")]
+    InvalidTemplate(String),
+    #[error("mapping template '{0}' was created for a sheet with different form fields")]
+    TemplateMismatch(String),
+    #[error("{0} actions cannot be previewed")]
+    PreviewUnsupported(String),
     #[error("database error: {0}")]
     DatabaseError(String),
 }
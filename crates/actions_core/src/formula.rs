@@ -0,0 +1,619 @@
+use crate::error::FormulaError;
+use serde::{Deserialize, Serialize};
+
+/// Deepest nesting of parentheses, calls and negations a formula may use.
+const MAX_DEPTH: usize = 32;
+
+/// Most tokens a formula may have. Operator chains such as `{A} + {A} + ...` nest as deeply as
+/// they are long, and the expression tree is walked recursively, so the length is capped too.
+const MAX_TOKENS: usize = 256;
+
+/// Arithmetic operator joining two sub-expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl BinaryOperator {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+        }
+    }
+}
+
+/// Function a formula may call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Floor,
+    Ceil,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "floor" => Some(Self::Floor),
+            "ceil" => Some(Self::Ceil),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Floor => "floor",
+            Self::Ceil => "ceil",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+
+    /// Returns whether the function can be called with `count` arguments.
+    fn accepts_arguments(self, count: usize) -> bool {
+        match self {
+            Self::Floor | Self::Ceil => count == 1,
+            Self::Min | Self::Max => count >= 2,
+        }
+    }
+}
+
+/// Parsed formula expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Integer(i64),
+    /// Value of a form field; checkboxes read as 0 or 1.
+    Field(String),
+    Negate(Box<Expression>),
+    Binary {
+        operator: BinaryOperator,
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+    Call {
+        function: Function,
+        arguments: Vec<Expression>,
+    },
+}
+
+impl Expression {
+    /// Compiles the expression to a JavaScript expression reading fields through the helpers.
+    pub fn to_js(&self) -> String {
+        match self {
+            Self::Integer(value) => value.to_string(),
+            Self::Field(name) => format!(
+                "getFormulaValueFromField({})",
+                serde_json::Value::from(name.as_str())
+            ),
+            Self::Negate(operand) => format!("(-{})", operand.to_js()),
+            Self::Binary { operator, lhs, rhs } => {
+                format!("({} {} {})", lhs.to_js(), operator.symbol(), rhs.to_js())
+            }
+            Self::Call {
+                function,
+                arguments,
+            } => {
+                let arguments: Vec<String> = arguments.iter().map(Self::to_js).collect();
+                format!("Math.{}({})", function.name(), arguments.join(", "))
+            }
+        }
+    }
+
+    /// Evaluates the expression in floating point like its compiled JavaScript, reading fields
+    /// through `field_value`.
+    pub fn evaluate(&self, field_value: &impl Fn(&str) -> f64) -> f64 {
+        match self {
+            Self::Integer(value) => *value as f64,
+            Self::Field(name) => field_value(name),
+            Self::Negate(operand) => -operand.evaluate(field_value),
+            Self::Binary { operator, lhs, rhs } => {
+                let (lhs, rhs) = (lhs.evaluate(field_value), rhs.evaluate(field_value));
+                match operator {
+                    BinaryOperator::Add => lhs + rhs,
+                    BinaryOperator::Subtract => lhs - rhs,
+                    BinaryOperator::Multiply => lhs * rhs,
+                    BinaryOperator::Divide => lhs / rhs,
+                }
+            }
+            Self::Call {
+                function,
+                arguments,
+            } => {
+                let mut values = arguments
+                    .iter()
+                    .map(|argument| argument.evaluate(field_value));
+                match function {
+                    Function::Floor => values.next().map_or(f64::NAN, f64::floor),
+                    Function::Ceil => values.next().map_or(f64::NAN, f64::ceil),
+                    // Math.min and Math.max propagate NaN, unlike f64::min and f64::max
+                    Function::Min => values
+                        .reduce(|a, b| {
+                            if a.is_nan() || b.is_nan() {
+                                f64::NAN
+                            } else {
+                                a.min(b)
+                            }
+                        })
+                        .unwrap_or(f64::INFINITY),
+                    Function::Max => values
+                        .reduce(|a, b| {
+                            if a.is_nan() || b.is_nan() {
+                                f64::NAN
+                            } else {
+                                a.max(b)
+                            }
+                        })
+                        .unwrap_or(f64::NEG_INFINITY),
+                }
+            }
+        }
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Self::Integer(_) => {}
+            Self::Field(name) => {
+                if !fields.contains(&name.as_str()) {
+                    fields.push(name);
+                }
+            }
+            Self::Negate(operand) => operand.collect_fields(fields),
+            Self::Binary { lhs, rhs, .. } => {
+                lhs.collect_fields(fields);
+                rhs.collect_fields(fields);
+            }
+            Self::Call { arguments, .. } => {
+                for argument in arguments {
+                    argument.collect_fields(fields);
+                }
+            }
+        }
+    }
+}
+
+/// A validated formula over form fields, e.g. `floor({STR} * 15 / 2) + {Bonus}`.
+///
+/// Fields are referenced by name in braces; integers, `+ - * /`, parentheses and the
+/// functions `floor`, `ceil`, `min` and `max` are supported. Serializes as its source text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Formula {
+    source: String,
+    expression: Expression,
+}
+
+impl Formula {
+    pub fn parse(source: &str) -> Result<Self, FormulaError> {
+        let tokens = tokenize(source)?;
+        if let Some(token) = tokens.get(MAX_TOKENS) {
+            return Err(FormulaError::new(token.position, "formula is too long"));
+        }
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            end: source.len(),
+            depth: 0,
+        };
+        let expression = parser.parse_sum()?;
+        if let Some(token) = parser.peek() {
+            return Err(FormulaError::new(
+                token.position,
+                "expected an operator or the end of the formula",
+            ));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            expression,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// Returns the referenced field names in order of first appearance.
+    pub fn field_references(&self) -> Vec<&str> {
+        let mut fields = Vec::new();
+        self.expression.collect_fields(&mut fields);
+        fields
+    }
+}
+
+impl TryFrom<String> for Formula {
+    type Error = FormulaError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl From<Formula> for String {
+    fn from(formula: Formula) -> Self {
+        formula.source
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Integer(i64),
+    Field(String),
+    Identifier(String),
+    Operator(BinaryOperator),
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    /// Byte offset of the token in the source.
+    position: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, FormulaError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '+' => TokenKind::Operator(BinaryOperator::Add),
+            '-' => TokenKind::Operator(BinaryOperator::Subtract),
+            '*' => TokenKind::Operator(BinaryOperator::Multiply),
+            '/' => TokenKind::Operator(BinaryOperator::Divide),
+            '(' => TokenKind::OpenParen,
+            ')' => TokenKind::CloseParen,
+            ',' => TokenKind::Comma,
+            '{' => {
+                let start = position + 1;
+                let end = source[start..]
+                    .find('}')
+                    .map(|offset| start + offset)
+                    .ok_or_else(|| FormulaError::new(position, "unclosed field reference"))?;
+                let name = &source[start..end];
+                if name.trim().is_empty() {
+                    return Err(FormulaError::new(position, "empty field reference"));
+                }
+                while chars.next_if(|(i, _)| *i <= end).is_some() {}
+                TokenKind::Field(name.to_string())
+            }
+            '0'..='9' => {
+                let mut end = position + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = i + 1;
+                }
+                let value = source[position..end]
+                    .parse()
+                    .map_err(|_| FormulaError::new(position, "integer is too large"))?;
+                TokenKind::Integer(value)
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut end = position + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric()) {
+                    end = i + 1;
+                }
+                TokenKind::Identifier(source[position..end].to_string())
+            }
+            c => {
+                return Err(FormulaError::new(
+                    position,
+                    format!("unexpected character '{}'", c),
+                ));
+            }
+        };
+        tokens.push(Token { kind, position });
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser; `depth` bounds the recursion so hostile input cannot overflow
+/// the stack.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    /// Byte length of the source, reported for errors at the end of the formula.
+    end: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next_operator(&mut self, operators: &[BinaryOperator]) -> Option<BinaryOperator> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Operator(operator),
+                ..
+            }) if operators.contains(operator) => {
+                let operator = *operator;
+                self.position += 1;
+                Some(operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> Result<(), FormulaError> {
+        match self.peek() {
+            Some(token) if token.kind == kind => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(token) => Err(FormulaError::new(
+                token.position,
+                format!("expected {}", expected),
+            )),
+            None => Err(FormulaError::new(
+                self.end,
+                format!("expected {}", expected),
+            )),
+        }
+    }
+
+    fn enter(&mut self, position: usize) -> Result<(), FormulaError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(FormulaError::new(position, "formula is nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression, FormulaError> {
+        let mut lhs = self.parse_product()?;
+        while let Some(operator) =
+            self.next_operator(&[BinaryOperator::Add, BinaryOperator::Subtract])
+        {
+            let rhs = self.parse_product()?;
+            lhs = Expression::Binary {
+                operator,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_product(&mut self) -> Result<Expression, FormulaError> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(operator) =
+            self.next_operator(&[BinaryOperator::Multiply, BinaryOperator::Divide])
+        {
+            let rhs = self.parse_unary()?;
+            lhs = Expression::Binary {
+                operator,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, FormulaError> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(FormulaError::new(self.end, "expected a value"));
+        };
+        self.position += 1;
+
+        match token.kind {
+            TokenKind::Operator(BinaryOperator::Subtract) => {
+                self.enter(token.position)?;
+                let operand = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(Expression::Negate(Box::new(operand)))
+            }
+            TokenKind::Integer(value) => Ok(Expression::Integer(value)),
+            TokenKind::Field(name) => Ok(Expression::Field(name)),
+            TokenKind::OpenParen => {
+                self.enter(token.position)?;
+                let expression = self.parse_sum()?;
+                self.expect(TokenKind::CloseParen, "')'")?;
+                self.depth -= 1;
+                Ok(expression)
+            }
+            TokenKind::Identifier(name) => {
+                let function = Function::from_name(&name).ok_or_else(|| {
+                    FormulaError::new(token.position, format!("unknown function '{}'", name))
+                })?;
+                self.enter(token.position)?;
+                self.expect(TokenKind::OpenParen, "'(' after function name")?;
+                let mut arguments = vec![self.parse_sum()?];
+                while self.peek().is_some_and(|t| t.kind == TokenKind::Comma) {
+                    self.position += 1;
+                    arguments.push(self.parse_sum()?);
+                }
+                self.expect(TokenKind::CloseParen, "')'")?;
+                self.depth -= 1;
+
+                if !function.accepts_arguments(arguments.len()) {
+                    return Err(FormulaError::new(
+                        token.position,
+                        format!(
+                            "{}() does not take {} argument(s)",
+                            function.name(),
+                            arguments.len()
+                        ),
+                    ));
+                }
+                Ok(Expression::Call {
+                    function,
+                    arguments,
+                })
+            }
+            _ => Err(FormulaError::new(token.position, "expected a value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn field(name: &str) -> Box<Expression> {
+        Box::new(Expression::Field(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_applies_operator_precedence() {
+        // Arrange
+        let source = "{STR} * 15 + -{Load}";
+
+        // Act
+        let formula = Formula::parse(source).unwrap();
+
+        // Assert
+        assert_eq!(
+            formula.expression(),
+            &Expression::Binary {
+                operator: BinaryOperator::Add,
+                lhs: Box::new(Expression::Binary {
+                    operator: BinaryOperator::Multiply,
+                    lhs: field("STR"),
+                    rhs: Box::new(Expression::Integer(15)),
+                }),
+                rhs: Box::new(Expression::Negate(field("Load"))),
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_js_reads_fields_through_helper() {
+        // Arrange
+        let formula = Formula::parse("floor(({HD Total} - {HD Used}) / 2) + max({A}, 1)").unwrap();
+
+        // Act
+        let js = formula.expression().to_js();
+
+        // Assert
+        assert_eq!(
+            js,
+            r#"(Math.floor(((getFormulaValueFromField("HD Total") - getFormulaValueFromField("HD Used")) / 2)) + Math.max(getFormulaValueFromField("A"), 1))"#
+        );
+    }
+
+    #[test]
+    fn test_evaluate_matches_js_arithmetic() {
+        // Arrange
+        let formula = Formula::parse("floor(({HD Total} - {HD Used}) / 2) + max({A}, 1)").unwrap();
+        let field_value = |name: &str| match name {
+            "HD Total" => 5.0,
+            "HD Used" => 2.0,
+            _ => -3.0,
+        };
+
+        // Act
+        let value = formula.expression().evaluate(&field_value);
+
+        // Assert
+        assert_eq!(value, 2.0);
+        assert!(
+            Formula::parse("1 / 0")
+                .unwrap()
+                .expression()
+                .evaluate(&field_value)
+                .is_infinite()
+        );
+    }
+
+    #[test]
+    fn test_field_references_are_listed_once_in_order() {
+        let formula = Formula::parse("{B} + {A} * {B} - min({C}, {A})").unwrap();
+
+        assert_eq!(formula.field_references(), vec!["B", "A", "C"]);
+    }
+
+    #[test]
+    fn test_field_reference_escapes_quotes_in_js() {
+        let formula = Formula::parse(r#"{Say "hi"}"#).unwrap();
+
+        assert_eq!(
+            formula.expression().to_js(),
+            r#"getFormulaValueFromField("Say \"hi\"")"#
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_formulas() {
+        let cases = [
+            ("{STR", FormulaError::new(0, "unclosed field reference")),
+            ("{ } + 1", FormulaError::new(0, "empty field reference")),
+            ("1 +", FormulaError::new(3, "expected a value")),
+            (
+                "1 2",
+                FormulaError::new(2, "expected an operator or the end of the formula"),
+            ),
+            ("round(1)", FormulaError::new(0, "unknown function 'round'")),
+            (
+                "min(1)",
+                FormulaError::new(0, "min() does not take 1 argument(s)"),
+            ),
+            ("(1 + 2", FormulaError::new(6, "expected ')'")),
+            ("1 % 2", FormulaError::new(2, "unexpected character '%'")),
+            (
+                "99999999999999999999",
+                FormulaError::new(0, "integer is too large"),
+            ),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(Formula::parse(source), Err(expected), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_deep_nesting() {
+        let source = format!(
+            "{}1{}",
+            "(".repeat(MAX_DEPTH + 1),
+            ")".repeat(MAX_DEPTH + 1)
+        );
+
+        let result = Formula::parse(&source);
+
+        assert_eq!(
+            result,
+            Err(FormulaError::new(MAX_DEPTH, "formula is nested too deeply"))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_long_operator_chain() {
+        let source = vec!["{A}"; 50_000].join("+");
+
+        let result = Formula::parse(&source);
+
+        // The 257th token is the 129th field reference, after 128 "{A}+" terms
+        assert_eq!(
+            result,
+            Err(FormulaError::new(128 * 4, "formula is too long"))
+        );
+    }
+
+    #[test]
+    fn test_formula_serializes_as_source_text() {
+        let formula: Formula = serde_json::from_value(serde_json::json!("{STR} * 15")).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&formula).unwrap(),
+            serde_json::json!("{STR} * 15")
+        );
+        assert!(serde_json::from_value::<Formula>(serde_json::json!("{STR} *")).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use crate::action::{AttachedAction, CalculationAction};
+use crate::definition::ActionDefinitions;
+use crate::error::ActionError;
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+/// A calculated field reading another field: `source_field` feeds `target_field`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDependency {
+    /// ID of the attached action whose calculation creates this dependency.
+    pub action_id: Uuid,
+    pub source_field: String,
+    pub target_field: String,
+}
+
+/// Directed graph of the field dependencies introduced by a sheet's attached calculations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    pub dependencies: Vec<FieldDependency>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph from stored action mappings, resolving custom action types against
+    /// `action_definitions`.
+    pub fn from_attached_actions(
+        attached_actions: &[AttachedAction],
+        action_definitions: &ActionDefinitions,
+    ) -> Result<Self, ActionError> {
+        let mut graph = Self::default();
+        for attached_action in attached_actions {
+            let mut action =
+                serde_json::from_value::<CalculationAction>(attached_action.mapping.clone())
+                    .map_err(|e| {
+                        ActionError::InvalidAction(format!(
+                            "failed to deserialize stored action: {}",
+                            e
+                        ))
+                    })?;
+            action_definitions.bind(&mut action)?;
+            graph.insert(attached_action.id, &action);
+        }
+        Ok(graph)
+    }
+
+    /// Adds the dependencies of `action`, replacing those of any calculation previously
+    /// attached to the same target field.
+    pub fn insert(&mut self, action_id: Uuid, action: &CalculationAction) {
+        let target_field = action.target_field();
+        self.dependencies
+            .retain(|dependency| dependency.target_field != target_field);
+        self.dependencies
+            .extend(
+                action
+                    .input_fields()
+                    .into_iter()
+                    .map(|source_field| FieldDependency {
+                        action_id,
+                        source_field: source_field.to_string(),
+                        target_field: target_field.to_string(),
+                    }),
+            );
+    }
+
+    /// Adds the dependencies of `action` unless they would close a cycle, in which case the
+    /// graph is left unchanged and the cycle is reported.
+    pub fn try_insert(
+        &mut self,
+        action_id: Uuid,
+        action: &CalculationAction,
+    ) -> Result<(), ActionError> {
+        let previous = self.dependencies.clone();
+        self.insert(action_id, action);
+
+        match self.find_cycle() {
+            Some(cycle) => {
+                self.dependencies = previous;
+                Err(ActionError::CalculationCycle(cycle))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns every field that takes part in a dependency, sorted by name.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.dependencies
+            .iter()
+            .flat_map(|dependency| {
+                [
+                    dependency.source_field.as_str(),
+                    dependency.target_field.as_str(),
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns the calculated fields ordered so that each comes after every calculated field it
+    /// reads from. Independent fields are ordered by name; fields caught in a cycle come last.
+    pub fn calculation_order(&self) -> Vec<String> {
+        let mut pending_inputs: HashMap<&str, usize> = HashMap::new();
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+        for dependency in &self.dependencies {
+            pending_inputs
+                .entry(dependency.source_field.as_str())
+                .or_default();
+            *pending_inputs
+                .entry(dependency.target_field.as_str())
+                .or_default() += 1;
+            edges
+                .entry(dependency.source_field.as_str())
+                .or_default()
+                .push(dependency.target_field.as_str());
+        }
+        let calculated: BTreeSet<&str> = self
+            .dependencies
+            .iter()
+            .map(|dependency| dependency.target_field.as_str())
+            .collect();
+
+        let mut ready: BTreeSet<&str> = pending_inputs
+            .iter()
+            .filter(|(_, pending)| **pending == 0)
+            .map(|(field, _)| *field)
+            .collect();
+        let mut order = Vec::with_capacity(calculated.len());
+        while let Some(field) = ready.pop_first() {
+            if calculated.contains(field) {
+                order.push(field.to_string());
+            }
+            for next in edges.get(field).into_iter().flatten() {
+                let pending = pending_inputs
+                    .get_mut(next)
+                    .expect("every target field has an entry");
+                *pending -= 1;
+                if *pending == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        for field in calculated {
+            if pending_inputs[field] > 0 {
+                order.push(field.to_string());
+            }
+        }
+
+        order
+    }
+
+    /// Returns the fields of a dependency cycle, starting and ending with the same field,
+    /// or `None` when the graph is acyclic.
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+        for dependency in &self.dependencies {
+            edges
+                .entry(dependency.source_field.as_str())
+                .or_default()
+                .push(dependency.target_field.as_str());
+        }
+
+        let mut visited = BTreeSet::new();
+        for field in self.fields() {
+            let mut path = Vec::new();
+            if let Some(cycle) = find_cycle_from(field, &edges, &mut visited, &mut path) {
+                return Some(cycle);
+            }
+        }
+
+        None
+    }
+}
+
+/// Depth-first search from `field`; `path` holds the fields currently being explored, so
+/// reaching one of them again closes a cycle.
+fn find_cycle_from<'a>(
+    field: &'a str,
+    edges: &HashMap<&'a str, Vec<&'a str>>,
+    visited: &mut BTreeSet<&'a str>,
+    path: &mut Vec<&'a str>,
+) -> Option<Vec<String>> {
+    if let Some(start) = path.iter().position(|on_path| *on_path == field) {
+        let mut cycle: Vec<String> = path[start..].iter().map(|f| f.to_string()).collect();
+        cycle.push(field.to_string());
+        return Some(cycle);
+    }
+    if !visited.insert(field) {
+        return None;
+    }
+
+    path.push(field);
+    for next in edges.get(field).into_iter().flatten() {
+        if let Some(cycle) = find_cycle_from(next, edges, visited, path) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_insert_links_inputs_to_target_field() {
+        let action_id = Uuid::new_v4();
+        let mut graph = DependencyGraph::default();
+
+        graph.insert(
+            action_id,
+            &CalculationAction::ability_modifier("STR", "STRmod"),
+        );
+
+        assert_eq!(
+            graph.dependencies,
+            vec![FieldDependency {
+                action_id,
+                source_field: "STR".to_string(),
+                target_field: "STRmod".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_insert_replaces_calculation_on_same_target_field() {
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STR", "STRmod"),
+        );
+
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("DEX", "STRmod"),
+        );
+
+        assert_eq!(graph.fields(), BTreeSet::from(["DEX", "STRmod"]));
+    }
+
+    #[test]
+    fn test_find_cycle_returns_none_for_chained_calculations() {
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STR", "STRmod"),
+        );
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::saving_throw_modifier("STRmod", "ST Prof", "ProfBonus", "ST STR"),
+        );
+
+        assert_eq!(graph.find_cycle(), None);
+    }
+
+    #[test]
+    fn test_find_cycle_reports_fields_of_cycle() {
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STR", "STRmod"),
+        );
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STRmod", "STR"),
+        );
+
+        assert_eq!(
+            graph.find_cycle(),
+            Some(vec![
+                "STR".to_string(),
+                "STRmod".to_string(),
+                "STR".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_try_insert_leaves_graph_unchanged_when_closing_cycle() {
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STR", "STRmod"),
+        );
+        let before = graph.clone();
+
+        let result = graph.try_insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STRmod", "STR"),
+        );
+
+        assert_eq!(
+            result,
+            Err(ActionError::CalculationCycle(vec![
+                "STR".to_string(),
+                "STRmod".to_string(),
+                "STR".to_string()
+            ]))
+        );
+        assert_eq!(graph, before);
+    }
+
+    #[test]
+    fn test_calculation_order_places_inputs_before_dependent_calculations() {
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::saving_throw_modifier("STRmod", "ST Prof", "ProfBonus", "ST STR"),
+        );
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STR", "STRmod"),
+        );
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("DEX", "DEXmod"),
+        );
+
+        assert_eq!(
+            graph.calculation_order(),
+            vec![
+                "DEXmod".to_string(),
+                "STRmod".to_string(),
+                "ST STR".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_cycle_reports_field_reading_itself() {
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            Uuid::new_v4(),
+            &CalculationAction::ability_modifier("STR", "STR"),
+        );
+
+        assert_eq!(
+            graph.find_cycle(),
+            Some(vec!["STR".to_string(), "STR".to_string()])
+        );
+    }
+}
@@ -1,3 +1,10 @@
 pub mod action;
+pub mod definition;
 pub mod error;
+pub mod formula;
+pub mod graph;
 pub mod ports;
+pub mod preview;
+pub mod suggestion;
+pub mod system;
+pub mod template;
@@ -1,9 +1,12 @@
 use crate::action::AttachedAction;
 use crate::error::ActionError;
+use crate::template::MappingTemplate;
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use tracing::warn;
 use uuid::Uuid;
 
 #[derive(Clone, Debug)]
@@ -18,16 +21,165 @@ impl SheetReference {
     }
 }
 
+/// A local copy of a stored sheet, handed out by [`SheetStoragePort`] for reading and editing.
+///
+/// The file is deleted when the copy is dropped, so copies that are only read, or whose edits
+/// are abandoned, do not pile up. Writing the copy back to storage consumes it.
+#[derive(Debug, PartialEq)]
+pub struct LocalSheet {
+    path: PathBuf,
+}
+
+impl LocalSheet {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for LocalSheet {
+    fn drop(&mut self) {
+        // Already gone when storage moved the file into place on write
+        if let Err(e) = std::fs::remove_file(&self.path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            warn!(error = %e, path = %self.path.display(), "failed to remove local copy of sheet");
+        }
+    }
+}
+
+/// A generated calculation script and the field it attaches to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalculationScript {
+    pub target_field: String,
+    pub js: String,
+}
+
+impl CalculationScript {
+    pub fn new(target_field: impl Into<String>, js: impl Into<String>) -> Self {
+        Self {
+            target_field: target_field.into(),
+            js: js.into(),
+        }
+    }
+}
+
+/// A value computed for a calculated field, to be written into the sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComputedValue {
+    pub target_field: String,
+    pub value: String,
+}
+
+impl ComputedValue {
+    pub fn new(target_field: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            target_field: target_field.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Kind of an AcroForm field, derived from its field type and flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormFieldKind {
+    Text,
+    Choice,
+    Checkbox,
+    Radio,
+    PushButton,
+    Signature,
+    Unknown,
+}
+
+impl std::fmt::Display for FormFieldKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text field",
+            Self::Choice => "choice field",
+            Self::Checkbox => "checkbox",
+            Self::Radio => "radio button",
+            Self::PushButton => "push button",
+            Self::Signature => "signature field",
+            Self::Unknown => "field of unknown type",
+        })
+    }
+}
+
+/// A terminal form field of the sheet, addressed by its fully-qualified name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormField {
+    pub name: String,
+    pub kind: FormFieldKind,
+}
+
+impl FormField {
+    pub fn new(name: impl Into<String>, kind: FormFieldKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait ActionPdfPort: Send + Sync {
+    /// Installs the document-level helper script under a stable name in the JavaScript name
+    /// tree, replacing a previous helper in place and preserving any other document scripts.
+    /// Leaves the sheet untouched when the embedded helper already matches `js`.
     fn add_doc_level_js(&self, js: &str, sheet_path: &Path) -> Result<(), ActionError>;
 
+    /// Attaches `js` as the calculation of `target_field` and reorders the AcroForm calculation
+    /// order (`/CO`) to follow `calculation_order`, a topological order of calculated fields.
     fn attach_calculation_js(
         &self,
         js: &str,
         sheet_path: &Path,
         target_field: &str,
+        calculation_order: &[String],
+    ) -> Result<(), ActionError>;
+
+    /// Installs the helper script and attaches every calculation script within a single
+    /// load/save of the sheet, then reorders `/CO` to follow `calculation_order`. Returns one
+    /// result per script, in order; the outer error is reserved for failures that affect the
+    /// whole sheet.
+    fn attach_calculation_scripts(
+        &self,
+        helpers_js: &str,
+        scripts: &[CalculationScript],
+        calculation_order: &[String],
+        sheet_path: &Path,
+    ) -> Result<Vec<Result<(), ActionError>>, ActionError>;
+
+    /// Lists every terminal form field of the sheet with its fully-qualified name and kind.
+    fn list_form_fields(&self, sheet_path: &Path) -> Result<Vec<FormField>, ActionError>;
+
+    /// Reads the stored value (`/V`) of every terminal form field that has one, keyed by its
+    /// fully-qualified name. Button fields yield their state name, e.g. `Off`.
+    fn read_field_values(&self, sheet_path: &Path) -> Result<HashMap<String, String>, ActionError>;
+
+    /// Writes each value as the stored value (`/V`) of its target field and regenerates the
+    /// field's appearance, so viewers that do not run scripts still show it. Unless
+    /// `keep_scripts` is set, the calculation scripts of those fields are removed as well.
+    /// Button fields are left unchanged, script included, and values of fields missing from the
+    /// sheet are skipped.
+    fn write_computed_values(
+        &self,
+        sheet_path: &Path,
+        values: &[ComputedValue],
+        keep_scripts: bool,
+    ) -> Result<(), ActionError>;
+
+    /// Removes the calculation script from the target field and drops the field
+    /// from the AcroForm calculation order.
+    fn detach_calculation_js(
+        &self,
+        sheet_path: &Path,
+        target_field: &str,
     ) -> Result<(), ActionError>;
 }
 
@@ -40,11 +192,19 @@ pub trait SheetReferencePort: Send + Sync {
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait SheetStoragePort: Send + Sync {
-    /// Downloads the sheet from storage and returns a local path to the file.
-    async fn read(&self, path: PathBuf) -> Result<PathBuf, ActionError>;
+    /// Downloads the sheet from storage to a local copy.
+    async fn read(&self, path: PathBuf) -> Result<LocalSheet, ActionError>;
 
-    /// Uploads the modified local file back to the storage path.
-    async fn write(&self, local_path: PathBuf, storage_path: PathBuf) -> Result<(), ActionError>;
+    /// Downloads the pristine upload kept alongside the sheet at `storage_path` to a local copy.
+    /// Fails with `FileNotFound` when no original was retained.
+    async fn read_original(&self, storage_path: PathBuf) -> Result<LocalSheet, ActionError>;
+
+    /// Uploads the modified local copy back to the storage path.
+    async fn write(
+        &self,
+        local_sheet: LocalSheet,
+        storage_path: PathBuf,
+    ) -> Result<(), ActionError>;
 }
 
 #[cfg_attr(test, automock)]
@@ -52,4 +212,34 @@ pub trait SheetStoragePort: Send + Sync {
 pub trait AttachedActionPort: Send + Sync {
     async fn save(&self, action: &AttachedAction) -> Result<(), ActionError>;
     async fn list_by_sheet_id(&self, sheet_id: &Uuid) -> Result<Vec<AttachedAction>, ActionError>;
+
+    /// Find an attached action by ID, scoped to the sheet it belongs to.
+    async fn find_by_id(
+        &self,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+    ) -> Result<AttachedAction, ActionError>;
+
+    /// Overwrite the type, target field and mapping of an attached action, keeping its ID.
+    async fn update(&self, action: &AttachedAction) -> Result<(), ActionError>;
+
+    /// Delete an attached action by ID, scoped to the sheet it belongs to.
+    async fn delete(&self, sheet_id: &Uuid, action_id: &Uuid) -> Result<(), ActionError>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait MappingTemplatePort: Send + Sync {
+    async fn save(&self, template: &MappingTemplate) -> Result<(), ActionError>;
+
+    /// Lists the game system's templates created from sheets with the given fingerprint, oldest
+    /// first.
+    async fn list_by_fingerprint(
+        &self,
+        system: &str,
+        fingerprint: &str,
+    ) -> Result<Vec<MappingTemplate>, ActionError>;
+
+    /// Fails with `TemplateNotFound` when no template has the ID.
+    async fn find_by_id(&self, template_id: &Uuid) -> Result<MappingTemplate, ActionError>;
 }
@@ -1,11 +1,19 @@
-use crate::action::AttachedAction;
 pub use crate::action::CalculationAction;
+use crate::action::{ActionTypeMetadata, AttachActionResult, AttachedAction};
+use crate::definition::ActionDefinitions;
 use crate::error::ActionError;
+use crate::graph::DependencyGraph;
 use crate::ports::driven::{
-    ActionPdfPort, AttachedActionPort, SheetReferencePort, SheetStoragePort,
+    ActionPdfPort, AttachedActionPort, CalculationScript, ComputedValue, FormField, LocalSheet,
+    MappingTemplatePort, SheetReferencePort, SheetStoragePort,
 };
+use crate::preview::{self, PreviewResult, SheetValues};
+use crate::suggestion::{self, MappingSuggestions};
+use crate::system::GameSystem;
+use crate::template::{self, MappingTemplate};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{Span, debug, info, instrument, warn};
+use tracing::{Span, debug, error, info, instrument, warn};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -14,6 +22,7 @@ pub struct ActionService {
     sheet_storage_port: Arc<dyn SheetStoragePort>,
     action_pdf_port: Arc<dyn ActionPdfPort>,
     attached_action_port: Arc<dyn AttachedActionPort>,
+    action_definitions: Arc<ActionDefinitions>,
 }
 
 impl ActionService {
@@ -28,36 +37,65 @@ impl ActionService {
             sheet_storage_port,
             action_pdf_port,
             attached_action_port,
+            action_definitions: Arc::default(),
         }
     }
 
-    #[instrument(name = "actions.attach.calculation", skip(self, sheet_id, action), level = "info", err, fields(sheet_id = %sheet_id, action = tracing::field::Empty, target_field = tracing::field::Empty))]
+    /// Makes the action types declared in definition files available on top of the built-in ones.
+    pub fn with_action_definitions(mut self, action_definitions: ActionDefinitions) -> Self {
+        self.action_definitions = Arc::new(action_definitions);
+        self
+    }
+
+    /// Returns the catalog of the action types the game system offers, built in or defined.
+    pub fn action_type_catalog(&self, system: &dyn GameSystem) -> Vec<ActionTypeMetadata> {
+        self.action_definitions.action_type_catalog(system)
+    }
+
+    #[instrument(name = "actions.attach.calculation", skip(self, system, sheet_id, action), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, action = tracing::field::Empty, target_field = tracing::field::Empty))]
     pub async fn attach_calculation_script(
         &self,
+        system: &dyn GameSystem,
         sheet_id: &Uuid,
-        action: CalculationAction,
+        mut action: CalculationAction,
     ) -> Result<(), ActionError> {
         debug!(%sheet_id, "attaching calculation script for sheet");
 
-        // Serialize action mapping for persistence before the match consumes it
-        let action_mapping = serde_json::to_value(&action).map_err(|e| {
-            ActionError::InvalidAction(format!("failed to serialize action: {}", e))
-        })?;
+        self.action_definitions.bind(&mut action)?;
+        ensure_supported(system, &self.action_definitions, &action)?;
 
         let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
         debug!("sheet reference located");
 
         let storage_path = sheet_reference.path.clone();
-        let local_path = self.sheet_storage_port.read(sheet_reference.path).await?;
+        let local_sheet = self.sheet_storage_port.read(sheet_reference.path).await?;
 
-        debug!(path = %local_path.display(), "sheet path resolved and readable");
+        debug!(path = %local_sheet.path().display(), "sheet path resolved and readable");
 
-        let dnd_helpers_js =
-            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/js/dnd-helpers.js"));
-        self.action_pdf_port
-            .add_doc_level_js(dnd_helpers_js, &local_path)?;
+        // Reject mappings that do not fit the sheet before the PDF is modified
+        let form_fields = self.action_pdf_port.list_form_fields(local_sheet.path())?;
+        action.expand_field_patterns(&form_fields)?;
+        action.validate_fields(&form_fields)?;
 
-        info!("document-level helper JS attached");
+        // Serialize the expanded mapping so stored actions list concrete fields
+        let action_mapping = serde_json::to_value(&action).map_err(|e| {
+            ActionError::InvalidAction(format!("failed to serialize action: {}", e))
+        })?;
+
+        // Reject calculations that would end up reading their own result
+        let existing_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+        ensure_sheet_fits_system(system, &self.action_definitions, &existing_actions)?;
+        let action_id = Uuid::new_v4();
+        let mut graph =
+            DependencyGraph::from_attached_actions(&existing_actions, &self.action_definitions)?;
+        graph.try_insert(action_id, &action)?;
+
+        self.action_pdf_port.add_doc_level_js(
+            &self.action_definitions.helpers_js(system),
+            local_sheet.path(),
+        )?;
+
+        info!("document-level helper JS up to date");
 
         let action_label = action.action_label();
         let target_field = action.target_field().to_string();
@@ -70,61 +108,890 @@ impl ActionService {
         span.record("action", tracing::field::display(action_label));
         span.record("target_field", tracing::field::display(&target_field));
 
-        self.action_pdf_port
-            .attach_calculation_js(&action_js, &local_path, &target_field)?;
+        self.action_pdf_port.attach_calculation_js(
+            &action_js,
+            local_sheet.path(),
+            &target_field,
+            &graph.calculation_order(),
+        )?;
 
         info!(target_field = %target_field, "calculation JS attached to target field");
 
-        // Upload the modified PDF back to storage
-        self.sheet_storage_port
-            .write(local_path, storage_path)
-            .await?;
-
-        info!("modified PDF uploaded back to storage");
+        let previous = existing_actions
+            .into_iter()
+            .find(|existing| existing.target_field == target_field);
 
-        // Persist the attached action
+        // Persist before uploading so a failed save leaves the stored sheet untouched
         let attached_action = AttachedAction {
-            id: Uuid::new_v4(),
+            id: action_id,
             sheet_id: *sheet_id,
             action_type: action_label.to_string(),
             target_field: target_field.clone(),
             mapping: action_mapping,
         };
 
-        if let Err(e) = self.attached_action_port.save(&attached_action).await {
-            warn!(error = %e, "failed to persist attached action — PDF was modified but action not saved");
+        self.attached_action_port.save(&attached_action).await?;
+
+        debug!(action_id = %attached_action.id, "attached action persisted");
+
+        // Upload the modified PDF back to storage
+        if let Err(e) = self
+            .sheet_storage_port
+            .write(local_sheet, storage_path)
+            .await
+        {
+            error!(error = ?e, "failed to upload modified PDF, rolling back attached action");
+            self.rollback_attached_actions(vec![(attached_action, previous)])
+                .await;
+            return Err(e);
         }
 
+        info!("modified PDF uploaded back to storage");
+
         info!("attach_calculation_script completed successfully");
 
         Ok(())
     }
 
+    /// Attaches many calculation actions with a single download, PDF load/save and upload.
+    ///
+    /// Actions that cannot be attached (e.g. unknown target field) are reported in their
+    /// result without failing the others.
+    #[instrument(name = "actions.attach.calculations", skip(self, system, sheet_id, actions), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, actions = actions.len()))]
+    pub async fn attach_calculation_scripts(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        actions: Vec<CalculationAction>,
+    ) -> Result<Vec<AttachActionResult>, ActionError> {
+        debug!(%sheet_id, "attaching calculation scripts for sheet");
+
+        let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
+        debug!("sheet reference located");
+
+        let storage_path = sheet_reference.path.clone();
+        let local_sheet = self.sheet_storage_port.read(sheet_reference.path).await?;
+        let form_fields = self.action_pdf_port.list_form_fields(local_sheet.path())?;
+
+        let mut previous_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+        ensure_sheet_fits_system(system, &self.action_definitions, &previous_actions)?;
+        let mut graph =
+            DependencyGraph::from_attached_actions(&previous_actions, &self.action_definitions)?;
+
+        // Validate, serialize and generate JS up front so invalid actions never touch the PDF
+        let prepared: Vec<_> = actions
+            .into_iter()
+            .map(|mut action| {
+                let action_id = Uuid::new_v4();
+                let prepared = self
+                    .action_definitions
+                    .bind(&mut action)
+                    .and_then(|()| ensure_supported(system, &self.action_definitions, &action))
+                    .and_then(|()| action.expand_field_patterns(&form_fields))
+                    .and_then(|()| action.validate_fields(&form_fields))
+                    .and_then(|()| graph.try_insert(action_id, &action))
+                    .and_then(|()| {
+                        serde_json::to_value(&action).map_err(|e| {
+                            ActionError::InvalidAction(format!("failed to serialize action: {}", e))
+                        })
+                    })
+                    .and_then(|mapping| Ok((mapping, action.generate_js()?)));
+                (
+                    action_id,
+                    action.action_label().to_string(),
+                    action.target_field().to_string(),
+                    prepared,
+                )
+            })
+            .collect();
+
+        let scripts: Vec<CalculationScript> = prepared
+            .iter()
+            .filter_map(|(_, _, target_field, prepared)| {
+                prepared
+                    .as_ref()
+                    .ok()
+                    .map(|(_, js)| CalculationScript::new(target_field, js))
+            })
+            .collect();
+
+        let mut upload = None;
+        let mut pdf_results = if scripts.is_empty() {
+            Vec::new()
+        } else {
+            debug!(path = %local_sheet.path().display(), scripts = scripts.len(), "applying calculation scripts");
+
+            let pdf_results = self.action_pdf_port.attach_calculation_scripts(
+                &self.action_definitions.helpers_js(system),
+                &scripts,
+                &graph.calculation_order(),
+                local_sheet.path(),
+            )?;
+
+            if pdf_results.iter().any(Result::is_ok) {
+                upload = Some((local_sheet, storage_path));
+            }
+
+            pdf_results
+        }
+        .into_iter();
+
+        // Persist every attached action before uploading so a failed save leaves the stored sheet untouched
+        let mut persisted = Vec::new();
+        let mut results = Vec::with_capacity(prepared.len());
+        for (action_id, action_label, target_field, prepared) in prepared {
+            let outcome = match prepared {
+                Ok((mapping, _)) => match pdf_results.next() {
+                    Some(Ok(())) => {
+                        let attached_action = AttachedAction {
+                            id: action_id,
+                            sheet_id: *sheet_id,
+                            action_type: action_label.to_string(),
+                            target_field: target_field.clone(),
+                            mapping,
+                        };
+
+                        if let Err(e) = self.attached_action_port.save(&attached_action).await {
+                            error!(error = ?e, target_field = %target_field, "failed to persist attached action, rolling back batch");
+                            self.rollback_attached_actions(persisted).await;
+                            return Err(e);
+                        }
+
+                        let id = attached_action.id;
+                        let previous = previous_actions
+                            .iter()
+                            .position(|existing| existing.target_field == target_field)
+                            .map(|index| previous_actions.swap_remove(index));
+                        persisted.push((attached_action, previous));
+
+                        Ok(id)
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Err(ActionError::InvalidAction(format!(
+                        "no result for calculation script on field {}",
+                        target_field
+                    ))),
+                },
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = &outcome {
+                warn!(error = %e, action = action_label, target_field = %target_field, "calculation action not attached");
+            }
+
+            results.push(AttachActionResult {
+                action_type: action_label.to_string(),
+                target_field,
+                outcome,
+            });
+        }
+
+        if let Some((local_sheet, storage_path)) = upload {
+            if let Err(e) = self
+                .sheet_storage_port
+                .write(local_sheet, storage_path)
+                .await
+            {
+                error!(error = ?e, "failed to upload modified PDF, rolling back attached actions");
+                self.rollback_attached_actions(persisted).await;
+                return Err(e);
+            }
+
+            info!("modified PDF uploaded back to storage");
+        }
+
+        info!(
+            attached = results.iter().filter(|r| r.outcome.is_ok()).count(),
+            failed = results.iter().filter(|r| r.outcome.is_err()).count(),
+            "attach_calculation_scripts completed"
+        );
+
+        Ok(results)
+    }
+
+    /// Rebuilds the sheet from its pristine upload by replaying every stored action mapping
+    /// through the current generator, so helper JS fixes reach existing sheets. Sheets
+    /// uploaded before originals were retained are rebuilt from the current sheet instead.
+    ///
+    /// The stored sheet is only replaced when every action replays; otherwise it is left
+    /// unchanged and the results report which actions failed.
+    #[instrument(name = "actions.regenerate", skip(self, system, sheet_id), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id))]
+    pub async fn regenerate_sheet(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+    ) -> Result<Vec<AttachActionResult>, ActionError> {
+        debug!(%sheet_id, "regenerating sheet from stored actions");
+
+        let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
+        let attached_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+        ensure_sheet_fits_system(system, &self.action_definitions, &attached_actions)?;
+
+        let storage_path = sheet_reference.path.clone();
+        let local_sheet = match self
+            .sheet_storage_port
+            .read_original(sheet_reference.path.clone())
+            .await
+        {
+            Ok(path) => path,
+            Err(ActionError::FileNotFound) => {
+                warn!("no original retained for sheet, regenerating from current sheet");
+                self.sheet_storage_port.read(sheet_reference.path).await?
+            }
+            Err(e) => return Err(e),
+        };
+
+        debug!(path = %local_sheet.path().display(), actions = attached_actions.len(), "replaying stored actions");
+
+        let mut graph = DependencyGraph::default();
+        let prepared: Vec<_> = attached_actions
+            .into_iter()
+            .map(|attached_action| {
+                let js =
+                    serde_json::from_value::<CalculationAction>(attached_action.mapping.clone())
+                        .map_err(|e| {
+                            ActionError::InvalidAction(format!(
+                                "failed to deserialize stored action: {}",
+                                e
+                            ))
+                        })
+                        .and_then(|mut action| {
+                            self.action_definitions.bind(&mut action)?;
+                            graph.insert(attached_action.id, &action);
+                            action.generate_js()
+                        });
+                (attached_action, js)
+            })
+            .collect();
+
+        let scripts: Vec<CalculationScript> = prepared
+            .iter()
+            .filter_map(|(attached_action, js)| {
+                js.as_ref()
+                    .ok()
+                    .map(|js| CalculationScript::new(&attached_action.target_field, js))
+            })
+            .collect();
+
+        let mut pdf_results = if scripts.is_empty() {
+            Vec::new()
+        } else {
+            self.action_pdf_port.attach_calculation_scripts(
+                &self.action_definitions.helpers_js(system),
+                &scripts,
+                &graph.calculation_order(),
+                local_sheet.path(),
+            )?
+        }
+        .into_iter();
+
+        let results: Vec<_> = prepared
+            .into_iter()
+            .map(|(attached_action, js)| {
+                let outcome = match js {
+                    Ok(_) => match pdf_results.next() {
+                        Some(Ok(())) => Ok(attached_action.id),
+                        Some(Err(e)) => Err(e),
+                        None => Err(ActionError::InvalidAction(format!(
+                            "no result for calculation script on field {}",
+                            attached_action.target_field
+                        ))),
+                    },
+                    Err(e) => Err(e),
+                };
+
+                if let Err(e) = &outcome {
+                    warn!(error = %e, action_id = %attached_action.id, target_field = %attached_action.target_field, "stored action not replayed");
+                }
+
+                AttachActionResult {
+                    action_type: attached_action.action_type,
+                    target_field: attached_action.target_field,
+                    outcome,
+                }
+            })
+            .collect();
+
+        // A partial replay would silently drop calculations from the stored sheet
+        if results.iter().all(|r| r.outcome.is_ok()) {
+            self.sheet_storage_port
+                .write(local_sheet, storage_path)
+                .await?;
+
+            info!("regenerated PDF uploaded back to storage");
+        } else {
+            warn!("stored sheet left unchanged because not every stored action was replayed");
+        }
+
+        info!(
+            replayed = results.iter().filter(|r| r.outcome.is_ok()).count(),
+            failed = results.iter().filter(|r| r.outcome.is_err()).count(),
+            "regenerate_sheet completed"
+        );
+
+        Ok(results)
+    }
+
+    /// Compensates attached actions persisted for an upload that did not complete, restoring
+    /// the action previously attached to the same target field or removing the new one.
+    async fn rollback_attached_actions(
+        &self,
+        persisted: Vec<(AttachedAction, Option<AttachedAction>)>,
+    ) {
+        for (attached_action, previous) in persisted {
+            let result = match previous {
+                Some(previous) => self.attached_action_port.save(&previous).await,
+                None => {
+                    self.attached_action_port
+                        .delete(&attached_action.sheet_id, &attached_action.id)
+                        .await
+                }
+            };
+
+            if let Err(e) = result {
+                error!(error = ?e, action_id = %attached_action.id, target_field = %attached_action.target_field, "failed to roll back attached action");
+            }
+        }
+    }
+
+    /// Proposes actions mapping the sheet's fields, leaving out action types the game system does
+    /// not offer and fields that already have a calculation attached.
+    #[instrument(name = "actions.suggest", skip(self, system, sheet_id), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id))]
+    pub async fn suggest_actions(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+    ) -> Result<MappingSuggestions, ActionError> {
+        let form_fields = self.form_fields(sheet_id).await?;
+        let attached_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+
+        let mut suggestions = suggestion::suggest_actions(&form_fields);
+        suggestions.suggestions.retain(|suggestion| {
+            let action = &suggestion.action;
+            self.action_definitions
+                .supports(system, action.action_label())
+                && !attached_actions
+                    .iter()
+                    .any(|attached| attached.target_field == action.target_field())
+        });
+        debug!(
+            sheet_name = suggestions.sheet_name,
+            count = suggestions.suggestions.len(),
+            "suggested actions"
+        );
+
+        Ok(suggestions)
+    }
+
+    /// Lists the terminal form fields of the sheet.
+    pub async fn form_fields(&self, sheet_id: &Uuid) -> Result<Vec<FormField>, ActionError> {
+        let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
+        let local_sheet = self.sheet_storage_port.read(sheet_reference.path).await?;
+        self.action_pdf_port.list_form_fields(local_sheet.path())
+    }
+
+    /// Returns the field dependency graph formed by every calculation attached to the sheet.
+    pub async fn dependency_graph(&self, sheet_id: &Uuid) -> Result<DependencyGraph, ActionError> {
+        let attached_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+        DependencyGraph::from_attached_actions(&attached_actions, &self.action_definitions)
+    }
+
+    /// Computes the value of every calculated field from the sheet's stored values, with
+    /// `overrides` standing in for the values of the named fields, without modifying the sheet.
+    ///
+    /// Results follow the calculation order, each action reading the results of the ones before
+    /// it; an action that cannot be evaluated is reported in its result.
+    #[instrument(name = "actions.preview", skip(self, system, sheet_id, overrides), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, overrides = overrides.len()))]
+    pub async fn preview(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        overrides: HashMap<String, String>,
+    ) -> Result<Vec<PreviewResult>, ActionError> {
+        let (_, results) = self
+            .evaluate_calculations(system, sheet_id, &overrides)
+            .await?;
+
+        debug!(
+            computed = results.iter().filter(|r| r.outcome.is_ok()).count(),
+            failed = results.iter().filter(|r| r.outcome.is_err()).count(),
+            "previewed calculations"
+        );
+
+        Ok(results)
+    }
+
+    /// Returns a local copy of the sheet with the value of every calculated field written into
+    /// it, for PDF viewers and printers that do not run calculation scripts. The stored sheet is
+    /// left unchanged.
+    ///
+    /// Unless `keep_scripts` is set, the scripts of the fields whose value could be computed are
+    /// removed from the copy; fields that cannot be evaluated keep their script.
+    #[instrument(name = "actions.export_computed", skip(self, system, sheet_id), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, keep_scripts))]
+    pub async fn export_computed_sheet(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        keep_scripts: bool,
+    ) -> Result<LocalSheet, ActionError> {
+        let (local_sheet, results) = self
+            .evaluate_calculations(system, sheet_id, &HashMap::new())
+            .await?;
+
+        let values: Vec<ComputedValue> = results
+            .iter()
+            .filter_map(|result| match &result.outcome {
+                Ok(value) => Some(ComputedValue::new(&result.target_field, value.to_string())),
+                Err(e) => {
+                    warn!(error = %e, target_field = %result.target_field, "calculated field not baked");
+                    None
+                }
+            })
+            .collect();
+
+        if !values.is_empty() {
+            self.action_pdf_port.write_computed_values(
+                local_sheet.path(),
+                &values,
+                keep_scripts,
+            )?;
+        }
+
+        info!(
+            baked = values.len(),
+            skipped = results.len() - values.len(),
+            "exported sheet with computed values"
+        );
+
+        Ok(local_sheet)
+    }
+
+    /// Evaluates the attached actions against a local copy of the sheet, returning the copy and
+    /// one result per action in calculation order.
+    async fn evaluate_calculations(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(LocalSheet, Vec<PreviewResult>), ActionError> {
+        let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
+        let attached_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+        ensure_sheet_fits_system(system, &self.action_definitions, &attached_actions)?;
+
+        let local_sheet = self.sheet_storage_port.read(sheet_reference.path).await?;
+        let form_fields = self.action_pdf_port.list_form_fields(local_sheet.path())?;
+        let stored_values = self.action_pdf_port.read_field_values(local_sheet.path())?;
+
+        let mut values = SheetValues::new(&form_fields, &stored_values);
+        for (field_name, value) in overrides {
+            values.set(field_name, value)?;
+        }
+
+        let mut graph = DependencyGraph::default();
+        let mut actions = Vec::new();
+        let mut results = Vec::new();
+        for attached_action in attached_actions {
+            match serde_json::from_value::<CalculationAction>(attached_action.mapping)
+                .map_err(|e| {
+                    ActionError::InvalidAction(format!(
+                        "failed to deserialize stored action: {}",
+                        e
+                    ))
+                })
+                .and_then(|mut action| {
+                    self.action_definitions.bind(&mut action)?;
+                    Ok(action)
+                }) {
+                Ok(action) => {
+                    graph.insert(attached_action.id, &action);
+                    actions.push(action);
+                }
+                Err(e) => {
+                    warn!(error = %e, action_id = %attached_action.id, target_field = %attached_action.target_field, "stored action not evaluated");
+                    results.push(PreviewResult {
+                        action_type: attached_action.action_type,
+                        target_field: attached_action.target_field,
+                        outcome: Err(e),
+                    });
+                }
+            }
+        }
+
+        // Fields without calculated inputs are missing from the order and are evaluated last
+        let calculation_order = graph.calculation_order();
+        actions.sort_by_key(|action| {
+            calculation_order
+                .iter()
+                .position(|field| field == action.target_field())
+                .unwrap_or(calculation_order.len())
+        });
+        results.extend(preview::preview_actions(&mut values, &actions));
+
+        Ok((local_sheet, results))
+    }
+
     pub async fn list_attached_actions(
         &self,
         sheet_id: &Uuid,
     ) -> Result<Vec<AttachedAction>, ActionError> {
         self.attached_action_port.list_by_sheet_id(sheet_id).await
     }
+
+    /// Replaces an attached action with `action`, moving its script when the target field changes.
+    ///
+    /// An action already attached to the new target field is replaced, as when attaching.
+    #[instrument(name = "actions.update", skip(self, system, sheet_id, action_id, action), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, action_id = %action_id, target_field = tracing::field::Empty))]
+    pub async fn update_action(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+        mut action: CalculationAction,
+    ) -> Result<(), ActionError> {
+        debug!(%sheet_id, %action_id, "updating attached action");
+
+        self.action_definitions.bind(&mut action)?;
+        ensure_supported(system, &self.action_definitions, &action)?;
+
+        let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
+        let original = self
+            .attached_action_port
+            .find_by_id(sheet_id, action_id)
+            .await?;
+
+        let storage_path = sheet_reference.path.clone();
+        let local_sheet = self.sheet_storage_port.read(sheet_reference.path).await?;
+
+        let form_fields = self.action_pdf_port.list_form_fields(local_sheet.path())?;
+        action.expand_field_patterns(&form_fields)?;
+        action.validate_fields(&form_fields)?;
+
+        let action_mapping = serde_json::to_value(&action).map_err(|e| {
+            ActionError::InvalidAction(format!("failed to serialize action: {}", e))
+        })?;
+
+        // The graph is checked without the action being replaced
+        let other_actions: Vec<AttachedAction> = self
+            .attached_action_port
+            .list_by_sheet_id(sheet_id)
+            .await?
+            .into_iter()
+            .filter(|attached| attached.id != *action_id)
+            .collect();
+        ensure_sheet_fits_system(system, &self.action_definitions, &other_actions)?;
+        let mut graph =
+            DependencyGraph::from_attached_actions(&other_actions, &self.action_definitions)?;
+        graph.try_insert(*action_id, &action)?;
+
+        let target_field = action.target_field().to_string();
+        Span::current().record("target_field", tracing::field::display(&target_field));
+
+        self.action_pdf_port.add_doc_level_js(
+            &self.action_definitions.helpers_js(system),
+            local_sheet.path(),
+        )?;
+
+        if original.target_field != target_field {
+            self.action_pdf_port
+                .detach_calculation_js(local_sheet.path(), &original.target_field)?;
+            info!(target_field = %original.target_field, "calculation JS removed from previous target field");
+        }
+
+        self.action_pdf_port.attach_calculation_js(
+            &action.generate_js()?,
+            local_sheet.path(),
+            &target_field,
+            &graph.calculation_order(),
+        )?;
+
+        info!(target_field = %target_field, "calculation JS attached to target field");
+
+        let displaced = other_actions
+            .into_iter()
+            .find(|attached| attached.target_field == target_field);
+        let updated = AttachedAction {
+            id: *action_id,
+            sheet_id: *sheet_id,
+            action_type: action.action_label().to_string(),
+            target_field,
+            mapping: action_mapping,
+        };
+
+        // Free the target field before the row moves onto it
+        if let Some(displaced) = &displaced {
+            self.attached_action_port
+                .delete(sheet_id, &displaced.id)
+                .await?;
+        }
+        if let Err(e) = self.attached_action_port.update(&updated).await {
+            self.restore_updated_action(&original, displaced).await;
+            return Err(e);
+        }
+
+        if let Err(e) = self
+            .sheet_storage_port
+            .write(local_sheet, storage_path)
+            .await
+        {
+            error!(error = ?e, "failed to upload modified PDF, restoring attached action");
+            self.restore_updated_action(&original, displaced).await;
+            return Err(e);
+        }
+
+        info!("update_action completed successfully");
+
+        Ok(())
+    }
+
+    /// Undoes the persisted part of [`Self::update_action`] after a later step failed.
+    async fn restore_updated_action(
+        &self,
+        original: &AttachedAction,
+        displaced: Option<AttachedAction>,
+    ) {
+        if let Err(e) = self.attached_action_port.update(original).await {
+            error!(error = ?e, action_id = %original.id, "failed to restore updated attached action");
+        }
+        if let Some(displaced) = displaced
+            && let Err(e) = self.attached_action_port.save(&displaced).await
+        {
+            error!(error = ?e, action_id = %displaced.id, target_field = %displaced.target_field, "failed to restore replaced attached action");
+        }
+    }
+
+    #[instrument(name = "actions.detach", skip(self, system, sheet_id, action_id), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, action_id = %action_id, target_field = tracing::field::Empty))]
+    pub async fn detach_action(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        action_id: &Uuid,
+    ) -> Result<(), ActionError> {
+        debug!(%sheet_id, %action_id, "detaching action from sheet");
+
+        let sheet_reference = self.sheet_reference_port.find_by_id(sheet_id).await?;
+        let attached_actions = self.attached_action_port.list_by_sheet_id(sheet_id).await?;
+        ensure_sheet_fits_system(system, &self.action_definitions, &attached_actions)?;
+        let attached_action = self
+            .attached_action_port
+            .find_by_id(sheet_id, action_id)
+            .await?;
+
+        Span::current().record(
+            "target_field",
+            tracing::field::display(&attached_action.target_field),
+        );
+
+        let storage_path = sheet_reference.path.clone();
+        let local_sheet = self.sheet_storage_port.read(sheet_reference.path).await?;
+
+        self.action_pdf_port
+            .detach_calculation_js(local_sheet.path(), &attached_action.target_field)?;
+
+        info!(target_field = %attached_action.target_field, "calculation JS removed from target field");
+
+        // Delete before uploading so a failed delete leaves the stored sheet untouched
+        self.attached_action_port
+            .delete(sheet_id, action_id)
+            .await?;
+
+        debug!("attached action deleted");
+
+        if let Err(e) = self
+            .sheet_storage_port
+            .write(local_sheet, storage_path)
+            .await
+        {
+            error!(error = ?e, "failed to upload modified PDF, restoring attached action");
+            if let Err(e) = self.attached_action_port.save(&attached_action).await {
+                error!(error = ?e, action_id = %attached_action.id, target_field = %attached_action.target_field, "failed to restore detached action");
+            }
+            return Err(e);
+        }
+
+        info!("modified PDF uploaded back to storage");
+
+        info!("detach_action completed successfully");
+
+        Ok(())
+    }
+}
+
+fn ensure_supported(
+    system: &dyn GameSystem,
+    action_definitions: &ActionDefinitions,
+    action: &CalculationAction,
+) -> Result<(), ActionError> {
+    if action_definitions.supports(system, action.action_label()) {
+        Ok(())
+    } else {
+        Err(ActionError::UnsupportedAction {
+            system: system.name().to_string(),
+            action: action.action_label().to_string(),
+        })
+    }
+}
+
+/// A sheet holds a single helper JS slot, so every action on it must come from the same system.
+fn ensure_sheet_fits_system(
+    system: &dyn GameSystem,
+    action_definitions: &ActionDefinitions,
+    attached_actions: &[AttachedAction],
+) -> Result<(), ActionError> {
+    match attached_actions
+        .iter()
+        .find(|attached| !action_definitions.supports(system, &attached.action_type))
+    {
+        Some(attached) => Err(ActionError::GameSystemMismatch {
+            system: system.name().to_string(),
+            action: attached.action_type.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Saves the actions of a mapped sheet as a named template and replays them onto fresh copies
+/// of the same sheet.
+#[derive(Clone)]
+pub struct MappingTemplateService {
+    action_service: ActionService,
+    mapping_template_port: Arc<dyn MappingTemplatePort>,
+}
+
+impl MappingTemplateService {
+    pub fn new(
+        action_service: ActionService,
+        mapping_template_port: Arc<dyn MappingTemplatePort>,
+    ) -> Self {
+        Self {
+            action_service,
+            mapping_template_port,
+        }
+    }
+
+    /// Saves every action attached to the sheet as a template named `name`.
+    #[instrument(name = "templates.create", skip(self, system, sheet_id, name), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id))]
+    pub async fn create_template(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        name: &str,
+    ) -> Result<MappingTemplate, ActionError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ActionError::InvalidTemplate(
+                "name must not be empty".to_string(),
+            ));
+        }
+
+        let form_fields = self.action_service.form_fields(sheet_id).await?;
+        let attached_actions = self.action_service.list_attached_actions(sheet_id).await?;
+        if attached_actions.is_empty() {
+            return Err(ActionError::InvalidTemplate(
+                "sheet has no attached actions to save".to_string(),
+            ));
+        }
+        ensure_sheet_fits_system(
+            system,
+            &self.action_service.action_definitions,
+            &attached_actions,
+        )?;
+
+        let template = MappingTemplate {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            system: system.id().to_string(),
+            fingerprint: template::fingerprint(&form_fields),
+            actions: attached_actions
+                .into_iter()
+                .map(|attached| attached.mapping)
+                .collect(),
+        };
+        self.mapping_template_port.save(&template).await?;
+
+        info!(template_id = %template.id, actions = template.actions.len(), "saved mapping template");
+        Ok(template)
+    }
+
+    /// Lists the game system's templates that fit the sheet's form fields.
+    pub async fn list_templates(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+    ) -> Result<Vec<MappingTemplate>, ActionError> {
+        let form_fields = self.action_service.form_fields(sheet_id).await?;
+        self.mapping_template_port
+            .list_by_fingerprint(system.id(), &template::fingerprint(&form_fields))
+            .await
+    }
+
+    /// Attaches every action of the template to the sheet in a single pass, rejecting templates
+    /// created from a sheet with other form fields.
+    #[instrument(name = "templates.apply", skip(self, system, sheet_id, template_id), level = "info", err, fields(system = system.id(), sheet_id = %sheet_id, template_id = %template_id))]
+    pub async fn apply_template(
+        &self,
+        system: &dyn GameSystem,
+        sheet_id: &Uuid,
+        template_id: &Uuid,
+    ) -> Result<Vec<AttachActionResult>, ActionError> {
+        let template = self.mapping_template_port.find_by_id(template_id).await?;
+        if template.system != system.id() {
+            return Err(ActionError::TemplateNotFound(*template_id));
+        }
+
+        let form_fields = self.action_service.form_fields(sheet_id).await?;
+        if template.fingerprint != template::fingerprint(&form_fields) {
+            return Err(ActionError::TemplateMismatch(template.name));
+        }
+
+        let actions = template
+            .actions
+            .into_iter()
+            .map(|mapping| {
+                serde_json::from_value::<CalculationAction>(mapping).map_err(|e| {
+                    ActionError::InvalidTemplate(format!(
+                        "failed to deserialize stored action: {}",
+                        e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.action_service
+            .attach_calculation_scripts(system, sheet_id, actions)
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::action::FieldKind;
+    use crate::error::{FieldRoleProblem, FieldRoleViolation};
+    use crate::graph::FieldDependency;
     use crate::ports::driven::{
-        MockActionPdfPort, MockAttachedActionPort, MockSheetReferencePort, MockSheetStoragePort,
-        SheetReference,
+        FormField, FormFieldKind, MockActionPdfPort, MockAttachedActionPort,
+        MockMappingTemplatePort, MockSheetReferencePort, MockSheetStoragePort, SheetReference,
     };
+    use crate::preview::FieldValue;
+    use crate::system::{Dnd5e, Pathfinder2e};
     use pretty_assertions::assert_eq;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::sync::Arc;
 
     fn mock_attached_action_port() -> MockAttachedActionPort {
         let mut port = MockAttachedActionPort::new();
+        port.expect_list_by_sheet_id().returning(|_| Ok(vec![]));
         port.expect_save().returning(|_| Ok(()));
         port
     }
 
+    fn form_fields(fields: &[(&str, FormFieldKind)]) -> Vec<FormField> {
+        fields
+            .iter()
+            .map(|(name, kind)| FormField::new(*name, *kind))
+            .collect()
+    }
+
     #[tokio::test]
     async fn test_should_attach_ability_modifier_calculation_script() {
         // Arrange
@@ -145,25 +1012,34 @@ mod tests {
             .expect_read()
             .withf(move |path| *path == sheet_path)
             .times(1)
-            .returning(Ok);
+            .returning(|path| Ok(LocalSheet::new(path)));
         sheet_storage_port
             .expect_write()
-            .withf(move |local, storage| *local == storage_path && *storage == storage_path)
+            .withf(move |local, storage| local.path() == storage_path && *storage == storage_path)
             .times(1)
             .returning(|_, _| Ok(()));
 
         let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("score", FormFieldKind::Text),
+                    ("modifier", FormFieldKind::Text),
+                ]))
+            });
         action_pdf_port
             .expect_add_doc_level_js()
             .times(1)
             .returning(|_, _| Ok(()));
         action_pdf_port
             .expect_attach_calculation_js()
-            .withf(|js, _, target_field| {
+            .withf(|js, _, target_field, _| {
                 js.contains("calculateModifierFromScore") && target_field == "modifier"
             })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_, _, _, _| Ok(()));
 
         let service = ActionService::new(
             Arc::new(sheet_reference_port),
@@ -175,7 +1051,9 @@ mod tests {
         let action = CalculationAction::ability_modifier("score", "modifier");
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(result, Ok(()));
@@ -195,24 +1073,38 @@ mod tests {
             .returning(move |_| Ok(sheet_reference.clone()));
 
         let mut sheet_storage_port = MockSheetStoragePort::new();
-        sheet_storage_port.expect_read().times(1).returning(Ok);
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
         sheet_storage_port
             .expect_write()
             .times(1)
             .returning(|_, _| Ok(()));
 
         let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("ability_mod", FormFieldKind::Text),
+                    ("proficient", FormFieldKind::Checkbox),
+                    ("prof_bonus", FormFieldKind::Text),
+                    ("save_modifier", FormFieldKind::Text),
+                ]))
+            });
         action_pdf_port
             .expect_add_doc_level_js()
             .times(1)
             .returning(|_, _| Ok(()));
         action_pdf_port
             .expect_attach_calculation_js()
-            .withf(|js, _, target_field| {
+            .withf(|js, _, target_field, _| {
                 js.contains("calculateSaveFromFields") && target_field == "save_modifier"
             })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_, _, _, _| Ok(()));
 
         let service = ActionService::new(
             Arc::new(sheet_reference_port),
@@ -229,7 +1121,9 @@ mod tests {
         };
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(result, Ok(()));
@@ -249,26 +1143,40 @@ mod tests {
             .returning(move |_| Ok(sheet_reference.clone()));
 
         let mut sheet_storage_port = MockSheetStoragePort::new();
-        sheet_storage_port.expect_read().times(1).returning(Ok);
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
         sheet_storage_port
             .expect_write()
             .times(1)
             .returning(|_, _| Ok(()));
 
         let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("ability_mod", FormFieldKind::Text),
+                    ("proficient", FormFieldKind::Checkbox),
+                    ("prof_bonus", FormFieldKind::Text),
+                    ("skill_mod", FormFieldKind::Text),
+                ]))
+            });
         action_pdf_port
             .expect_add_doc_level_js()
             .times(1)
             .returning(|_, _| Ok(()));
         action_pdf_port
             .expect_attach_calculation_js()
-            .withf(|js, _, target_field| {
+            .withf(|js, _, target_field, _| {
                 js.contains("calculateSkillFromFields")
                     && js.contains("undefined")
                     && target_field == "skill_mod"
             })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_, _, _, _| Ok(()));
 
         let service = ActionService::new(
             Arc::new(sheet_reference_port),
@@ -287,7 +1195,9 @@ mod tests {
         };
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(result, Ok(()));
@@ -307,27 +1217,43 @@ mod tests {
             .returning(move |_| Ok(sheet_reference.clone()));
 
         let mut sheet_storage_port = MockSheetStoragePort::new();
-        sheet_storage_port.expect_read().times(1).returning(Ok);
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
         sheet_storage_port
             .expect_write()
             .times(1)
             .returning(|_, _| Ok(()));
 
         let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("ability_mod", FormFieldKind::Text),
+                    ("proficient", FormFieldKind::Checkbox),
+                    ("expertise", FormFieldKind::Checkbox),
+                    ("half_prof", FormFieldKind::Checkbox),
+                    ("prof_bonus", FormFieldKind::Text),
+                    ("skill_mod", FormFieldKind::Text),
+                ]))
+            });
         action_pdf_port
             .expect_add_doc_level_js()
             .times(1)
             .returning(|_, _| Ok(()));
         action_pdf_port
             .expect_attach_calculation_js()
-            .withf(|js, _, target_field| {
+            .withf(|js, _, target_field, _| {
                 js.contains("calculateSkillFromFields")
                     && js.contains(r#""expertise""#)
                     && js.contains(r#""half_prof""#)
                     && target_field == "skill_mod"
             })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_, _, _, _| Ok(()));
 
         let service = ActionService::new(
             Arc::new(sheet_reference_port),
@@ -346,28 +1272,103 @@ mod tests {
         };
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(result, Ok(()));
     }
 
     #[tokio::test]
-    async fn test_should_return_error_when_sheet_not_found() {
+    async fn test_should_expand_field_pattern_before_attaching_sum() {
         // Arrange
         let sheet_id = Uuid::new_v4();
+        let sheet_path = PathBuf::from("/tmp/test-sheet.pdf");
+        let sheet_reference = SheetReference::new(sheet_id, sheet_path.clone());
 
         let mut sheet_reference_port = MockSheetReferencePort::new();
         sheet_reference_port
             .expect_find_by_id()
-            .times(1)
-            .returning(move |id| Err(ActionError::NotFound(*id)));
+            .returning(move |_| Ok(sheet_reference.clone()));
 
-        let sheet_storage_port = MockSheetStoragePort::new();
-        let action_pdf_port = MockActionPdfPort::new();
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
 
-        let service = ActionService::new(
-            Arc::new(sheet_reference_port),
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port.expect_list_form_fields().returning(|_| {
+            Ok(form_fields(&[
+                ("Wgt10", FormFieldKind::Text),
+                ("Wgt2", FormFieldKind::Text),
+                ("Wgt1", FormFieldKind::Text),
+                ("WgtTotal", FormFieldKind::Text),
+            ]))
+        });
+        action_pdf_port
+            .expect_add_doc_level_js()
+            .returning(|_, _| Ok(()));
+        action_pdf_port
+            .expect_attach_calculation_js()
+            .withf(|js, _, target_field, _| {
+                js == r#"calculateSumFromFields(["Wgt1","Wgt2","Wgt10"]);"#
+                    && target_field == "WgtTotal"
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .returning(|_| Ok(vec![]));
+        attached_action_port
+            .expect_save()
+            .withf(|attached_action| {
+                attached_action.mapping["SumFields"]["sourceFieldNames"]
+                    == serde_json::json!(["Wgt1", "Wgt2", "Wgt10"])
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::sum_fields(Vec::new(), Some("Wgt*"), "WgtTotal");
+
+        // Act
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_should_return_error_when_sheet_not_found() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |id| Err(ActionError::NotFound(*id)));
+
+        let sheet_storage_port = MockSheetStoragePort::new();
+        let action_pdf_port = MockActionPdfPort::new();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
             Arc::new(sheet_storage_port),
             Arc::new(action_pdf_port),
             Arc::new(mock_attached_action_port()),
@@ -376,7 +1377,9 @@ mod tests {
         let action = CalculationAction::ability_modifier("score", "modifier");
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(result, Err(ActionError::NotFound(sheet_id)));
@@ -413,7 +1416,9 @@ mod tests {
         let action = CalculationAction::ability_modifier("score", "modifier");
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(
@@ -436,9 +1441,21 @@ mod tests {
             .returning(move |_| Ok(sheet_reference.clone()));
 
         let mut sheet_storage_port = MockSheetStoragePort::new();
-        sheet_storage_port.expect_read().times(1).returning(Ok);
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
 
         let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("score", FormFieldKind::Text),
+                    ("modifier", FormFieldKind::Text),
+                ]))
+            });
         action_pdf_port
             .expect_add_doc_level_js()
             .times(1)
@@ -454,7 +1471,9 @@ mod tests {
         let action = CalculationAction::ability_modifier("score", "modifier");
 
         // Act
-        let result = service.attach_calculation_script(&sheet_id, action).await;
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
 
         // Assert
         assert_eq!(
@@ -462,4 +1481,1932 @@ mod tests {
             Err(ActionError::InvalidAction("PDF error".to_string()))
         );
     }
+
+    #[tokio::test]
+    async fn test_should_reject_mapping_that_does_not_fit_sheet_before_modifying_pdf() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("ability_mod", FormFieldKind::Text),
+                    ("proficient", FormFieldKind::Text),
+                    ("save_modifier", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port.expect_add_doc_level_js().never();
+        action_pdf_port.expect_attach_calculation_js().never();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port.expect_save().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::saving_throw_modifier(
+            "ability_mod",
+            "proficient",
+            "prof_bonus",
+            "save_modifier",
+        );
+
+        // Act
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidFieldMapping(vec![
+                FieldRoleViolation {
+                    role: "proficiencyBonusFieldName".to_string(),
+                    field_name: "prof_bonus".to_string(),
+                    problem: FieldRoleProblem::Missing,
+                },
+                FieldRoleViolation {
+                    role: "proficiencyFieldName".to_string(),
+                    field_name: "proficient".to_string(),
+                    problem: FieldRoleProblem::WrongKind {
+                        expected: FieldKind::Checkbox,
+                        actual: FormFieldKind::Text,
+                    },
+                },
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_action_that_would_create_calculation_cycle() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("STR", FormFieldKind::Text),
+                    ("STRmod", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port.expect_add_doc_level_js().never();
+        action_pdf_port.expect_attach_calculation_js().never();
+
+        let existing = AttachedAction {
+            mapping: serde_json::to_value(CalculationAction::ability_modifier("STR", "STRmod"))
+                .unwrap(),
+            ..attached_action(sheet_id, Uuid::new_v4(), "STRmod")
+        };
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |_| Ok(vec![existing.clone()]));
+        attached_action_port.expect_save().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service
+            .attach_calculation_script(
+                &Dnd5e,
+                &sheet_id,
+                CalculationAction::ability_modifier("STRmod", "STR"),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::CalculationCycle(vec![
+                "STR".to_string(),
+                "STRmod".to_string(),
+                "STR".to_string(),
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_action_the_game_system_does_not_support() {
+        // Arrange
+        let service = ActionService::new(
+            Arc::new(MockSheetReferencePort::new()),
+            Arc::new(MockSheetStoragePort::new()),
+            Arc::new(MockActionPdfPort::new()),
+            Arc::new(MockAttachedActionPort::new()),
+        );
+
+        // Act
+        let result = service
+            .attach_calculation_script(
+                &Pathfinder2e,
+                &Uuid::new_v4(),
+                CalculationAction::passive_score("Perception", None::<&str>, None, None, "Passive"),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::UnsupportedAction {
+                system: "Pathfinder 2nd Edition".to_string(),
+                action: "PassiveScore".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_attach_custom_action_with_helpers_of_its_definition() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("Level", FormFieldKind::Text),
+                    ("Hero", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port
+            .expect_add_doc_level_js()
+            .withf(|js, _| {
+                js.starts_with(Pathfinder2e.helpers_js())
+                    && js.contains("function calculateHeroPoints(")
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        action_pdf_port
+            .expect_attach_calculation_js()
+            .withf(|js, _, target_field, _| {
+                js == r#"calculateHeroPoints("Level");"# && target_field == "Hero"
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let definitions = ActionDefinitions::new(vec![
+            crate::definition::ActionDefinition::from_yaml(
+                r#"
+id: hero-points
+label: HeroPoints
+systems: [pf2e]
+roles:
+  - key: levelFieldName
+    kind: numericText
+  - key: heroPointsFieldName
+    kind: numericText
+    target: true
+script: calculateHeroPoints({{levelFieldName}});
+helpers: "function calculateHeroPoints(levelField) {}"
+"#,
+            )
+            .unwrap(),
+        ])
+        .unwrap();
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(mock_attached_action_port()),
+        )
+        .with_action_definitions(definitions);
+
+        let action: CalculationAction = serde_json::from_value(serde_json::json!({
+            "HeroPoints": {"levelFieldName": "Level", "heroPointsFieldName": "Hero"}
+        }))
+        .unwrap();
+
+        // Act
+        let result = service
+            .attach_calculation_script(&Pathfinder2e, &sheet_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(result, Ok(()));
+        assert_eq!(service.action_type_catalog(&Pathfinder2e).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_sheet_with_actions_of_another_game_system() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("STR", FormFieldKind::Text),
+                    ("STRmod", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port.expect_add_doc_level_js().never();
+
+        let existing = AttachedAction {
+            action_type: "SkillModifier".to_string(),
+            ..attached_action(sheet_id, Uuid::new_v4(), "Athletics")
+        };
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |_| Ok(vec![existing.clone()]));
+        attached_action_port.expect_save().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service
+            .attach_calculation_script(
+                &Pathfinder2e,
+                &sheet_id,
+                CalculationAction::ability_modifier("STR", "STRmod"),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::GameSystemMismatch {
+                system: "Pathfinder 2nd Edition".to_string(),
+                action: "SkillModifier".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_suggest_actions_for_fields_without_calculation() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("STR", FormFieldKind::Text),
+                    ("STRmod", FormFieldKind::Text),
+                    ("DEX", FormFieldKind::Text),
+                    ("DEXmod", FormFieldKind::Text),
+                    ("Initiative", FormFieldKind::Text),
+                    ("Carrying Capacity", FormFieldKind::Text),
+                ]))
+            });
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |_| Ok(vec![attached_action(sheet_id, Uuid::new_v4(), "STRmod")]));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service.suggest_actions(&Pathfinder2e, &sheet_id).await;
+
+        // Assert
+        let targets: Vec<_> = result
+            .unwrap()
+            .suggestions
+            .into_iter()
+            .map(|suggestion| suggestion.action.target_field().to_string())
+            .collect();
+        // STRmod is already calculated; Pathfinder has no initiative or carrying capacity action
+        assert_eq!(targets, vec!["DEXmod"]);
+    }
+
+    #[tokio::test]
+    async fn test_should_return_dependency_graph_of_attached_actions() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let existing = AttachedAction {
+            mapping: serde_json::to_value(CalculationAction::ability_modifier("STR", "STRmod"))
+                .unwrap(),
+            ..attached_action(sheet_id, action_id, "STRmod")
+        };
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |_| Ok(vec![existing.clone()]));
+
+        let service = ActionService::new(
+            Arc::new(MockSheetReferencePort::new()),
+            Arc::new(MockSheetStoragePort::new()),
+            Arc::new(MockActionPdfPort::new()),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let graph = service.dependency_graph(&sheet_id).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            graph.dependencies,
+            vec![FieldDependency {
+                action_id,
+                source_field: "STR".to_string(),
+                target_field: "STRmod".to_string(),
+            }]
+        );
+    }
+
+    fn previewed_sheet(
+        sheet_id: Uuid,
+        attached_actions: Vec<AttachedAction>,
+        mut action_pdf_port: MockActionPdfPort,
+    ) -> ActionService {
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("STR", FormFieldKind::Text),
+                    ("STRmod", FormFieldKind::Text),
+                    ("Athletics Prof", FormFieldKind::Checkbox),
+                    ("ProfBonus", FormFieldKind::Text),
+                    ("Athletics", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port
+            .expect_read_field_values()
+            .times(1)
+            .returning(|_| {
+                Ok(HashMap::from([
+                    ("STR".to_string(), "10".to_string()),
+                    ("Athletics Prof".to_string(), "Yes".to_string()),
+                    ("ProfBonus".to_string(), "2".to_string()),
+                ]))
+            });
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |_| Ok(attached_actions.clone()));
+
+        ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_should_preview_chained_calculations_with_overridden_values() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let athletics = AttachedAction {
+            action_type: "SkillModifier".to_string(),
+            mapping: serde_json::to_value(CalculationAction::skill_modifier(
+                "STRmod",
+                "Athletics Prof",
+                None::<String>,
+                None::<String>,
+                "ProfBonus",
+                "Athletics",
+            ))
+            .unwrap(),
+            ..attached_action(sheet_id, Uuid::new_v4(), "Athletics")
+        };
+        let strength = AttachedAction {
+            mapping: serde_json::to_value(CalculationAction::ability_modifier("STR", "STRmod"))
+                .unwrap(),
+            ..attached_action(sheet_id, Uuid::new_v4(), "STRmod")
+        };
+        let service = previewed_sheet(
+            sheet_id,
+            vec![athletics, strength],
+            MockActionPdfPort::new(),
+        );
+
+        // Act
+        let result = service
+            .preview(
+                &Dnd5e,
+                &sheet_id,
+                HashMap::from([("STR".to_string(), "17".to_string())]),
+            )
+            .await;
+
+        // Assert
+        let outcomes: Vec<_> = result
+            .unwrap()
+            .into_iter()
+            .map(|result| (result.target_field, result.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("STRmod".to_string(), Ok(FieldValue::Number(3.0))),
+                ("Athletics".to_string(), Ok(FieldValue::Number(5.0))),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_preview_override_of_unknown_field() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let service = previewed_sheet(sheet_id, Vec::new(), MockActionPdfPort::new());
+
+        // Act
+        let result = service
+            .preview(
+                &Dnd5e,
+                &sheet_id,
+                HashMap::from([("Strength".to_string(), "17".to_string())]),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::FieldNotFound("Strength".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_export_copy_with_computed_values_and_keep_unsupported_scripts() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let strength = AttachedAction {
+            mapping: serde_json::to_value(CalculationAction::ability_modifier("STR", "STRmod"))
+                .unwrap(),
+            ..attached_action(sheet_id, Uuid::new_v4(), "STRmod")
+        };
+        let broken = AttachedAction {
+            mapping: serde_json::json!({ "Unknown": {} }),
+            ..attached_action(sheet_id, Uuid::new_v4(), "Athletics")
+        };
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_write_computed_values()
+            .withf(|path, values, keep_scripts| {
+                path == Path::new("/tmp/test-sheet.pdf")
+                    && values == [ComputedValue::new("STRmod", "0")]
+                    && !keep_scripts
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let service = previewed_sheet(sheet_id, vec![strength, broken], action_pdf_port);
+
+        // Act
+        let result = service
+            .export_computed_sheet(&Dnd5e, &sheet_id, false)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.map(|local_sheet| local_sheet.path().to_path_buf()),
+            Ok(PathBuf::from("/tmp/test-sheet.pdf"))
+        );
+    }
+
+    fn attached_action(sheet_id: Uuid, action_id: Uuid, target_field: &str) -> AttachedAction {
+        AttachedAction {
+            id: action_id,
+            sheet_id,
+            action_type: "AbilityModifier".to_string(),
+            target_field: target_field.to_string(),
+            mapping: serde_json::to_value(CalculationAction::ability_modifier(
+                "score",
+                target_field,
+            ))
+            .unwrap(),
+        }
+    }
+
+    fn attachable_sheet(
+        sheet_id: Uuid,
+    ) -> (
+        MockSheetReferencePort,
+        MockSheetStoragePort,
+        MockActionPdfPort,
+    ) {
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("score", FormFieldKind::Text),
+                    ("modifier", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port
+            .expect_add_doc_level_js()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        action_pdf_port
+            .expect_attach_calculation_js()
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        (sheet_reference_port, sheet_storage_port, action_pdf_port)
+    }
+
+    #[tokio::test]
+    async fn test_should_not_upload_sheet_when_persisting_attached_action_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let (sheet_reference_port, mut sheet_storage_port, action_pdf_port) =
+            attachable_sheet(sheet_id);
+        sheet_storage_port.expect_write().never();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .returning(|_| Ok(vec![]));
+        attached_action_port
+            .expect_save()
+            .times(1)
+            .returning(|_| Err(ActionError::InvalidAction("DB error".to_string())));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::ability_modifier("score", "modifier");
+
+        // Act
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("DB error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_delete_attached_action_when_upload_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let (sheet_reference_port, mut sheet_storage_port, action_pdf_port) =
+            attachable_sheet(sheet_id);
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Err(ActionError::InvalidAction("Upload error".to_string())));
+
+        let saved_id = Arc::new(std::sync::Mutex::new(None));
+        let saved = saved_id.clone();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .returning(|_| Ok(vec![]));
+        attached_action_port
+            .expect_save()
+            .times(1)
+            .returning(move |action| {
+                *saved.lock().unwrap() = Some(action.id);
+                Ok(())
+            });
+        attached_action_port
+            .expect_delete()
+            .withf(move |sid, aid| *sid == sheet_id && Some(*aid) == *saved_id.lock().unwrap())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::ability_modifier("score", "modifier");
+
+        // Act
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("Upload error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_restore_previous_attached_action_when_upload_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let previous_id = Uuid::new_v4();
+        let (sheet_reference_port, mut sheet_storage_port, action_pdf_port) =
+            attachable_sheet(sheet_id);
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Err(ActionError::InvalidAction("Upload error".to_string())));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .returning(move |sid| Ok(vec![attached_action(*sid, previous_id, "modifier")]));
+        attached_action_port
+            .expect_save()
+            .withf(move |action| action.id != previous_id)
+            .times(1)
+            .returning(|_| Ok(()));
+        attached_action_port
+            .expect_save()
+            .withf(move |action| action.id == previous_id)
+            .times(1)
+            .returning(|_| Ok(()));
+        attached_action_port.expect_delete().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::ability_modifier("score", "modifier");
+
+        // Act
+        let result = service
+            .attach_calculation_script(&Dnd5e, &sheet_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("Upload error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_detach_action_from_sheet() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_path = PathBuf::from("/tmp/test-sheet.pdf");
+        let sheet_reference = SheetReference::new(sheet_id, sheet_path.clone());
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_detach_calculation_js()
+            .withf(|_, target_field| target_field == "modifier")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |sid| Ok(vec![attached_action(*sid, action_id, "modifier")]));
+        attached_action_port
+            .expect_find_by_id()
+            .withf(move |sid, aid| *sid == sheet_id && *aid == action_id)
+            .times(1)
+            .returning(move |sid, aid| Ok(attached_action(*sid, *aid, "modifier")));
+        attached_action_port
+            .expect_delete()
+            .withf(move |sid, aid| *sid == sheet_id && *aid == action_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service.detach_action(&Dnd5e, &sheet_id, &action_id).await;
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_should_restore_detached_action_when_upload_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Err(ActionError::InvalidAction("Upload error".to_string())));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_detach_calculation_js()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |sid| Ok(vec![attached_action(*sid, action_id, "modifier")]));
+        attached_action_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |sid, aid| Ok(attached_action(*sid, *aid, "modifier")));
+        attached_action_port
+            .expect_delete()
+            .withf(move |sid, aid| *sid == sheet_id && *aid == action_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        attached_action_port
+            .expect_save()
+            .withf(move |action| action.id == action_id && action.target_field == "modifier")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service.detach_action(&Dnd5e, &sheet_id, &action_id).await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("Upload error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_keep_stored_sheet_when_deleting_detached_action_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_detach_calculation_js()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |sid| Ok(vec![attached_action(*sid, action_id, "modifier")]));
+        attached_action_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |sid, aid| Ok(attached_action(*sid, *aid, "modifier")));
+        attached_action_port
+            .expect_delete()
+            .times(1)
+            .returning(|_, _| Err(ActionError::InvalidAction("DB error".to_string())));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service.detach_action(&Dnd5e, &sheet_id, &action_id).await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("DB error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_not_detach_action_from_sheet_of_other_game_system() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port.expect_read().never();
+        sheet_storage_port.expect_write().never();
+        let action_pdf_port = MockActionPdfPort::new();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |sid| {
+                Ok(vec![AttachedAction {
+                    action_type: "SkillModifier".to_string(),
+                    ..attached_action(*sid, action_id, "Athletics")
+                }])
+            });
+        attached_action_port.expect_delete().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service
+            .detach_action(&Pathfinder2e, &sheet_id, &action_id)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::GameSystemMismatch {
+                system: "Pathfinder 2nd Edition".to_string(),
+                action: "SkillModifier".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_return_error_when_detaching_unknown_action() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let sheet_storage_port = MockSheetStoragePort::new();
+        let action_pdf_port = MockActionPdfPort::new();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        attached_action_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(|_, aid| Err(ActionError::ActionNotFound(*aid)));
+        attached_action_port.expect_delete().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service.detach_action(&Dnd5e, &sheet_id, &action_id).await;
+
+        // Assert
+        assert_eq!(result, Err(ActionError::ActionNotFound(action_id)));
+    }
+
+    #[tokio::test]
+    async fn test_should_move_updated_action_to_new_target_field() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("score", FormFieldKind::Text),
+                    ("modifier", FormFieldKind::Text),
+                    ("other modifier", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port
+            .expect_add_doc_level_js()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        action_pdf_port
+            .expect_detach_calculation_js()
+            .withf(|_, target_field| target_field == "modifier")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        action_pdf_port
+            .expect_attach_calculation_js()
+            .withf(|_, _, target_field, calculation_order| {
+                target_field == "other modifier"
+                    && calculation_order == ["other modifier".to_string()]
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(|sid, aid| Ok(attached_action(*sid, *aid, "modifier")));
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |sid| Ok(vec![attached_action(*sid, action_id, "modifier")]));
+        attached_action_port
+            .expect_update()
+            .withf(move |action| action.id == action_id && action.target_field == "other modifier")
+            .times(1)
+            .returning(|_| Ok(()));
+        attached_action_port.expect_delete().never();
+        attached_action_port.expect_save().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::ability_modifier("score", "other modifier");
+
+        // Act
+        let result = service
+            .update_action(&Dnd5e, &sheet_id, &action_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_should_restore_updated_action_when_upload_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let (sheet_reference_port, mut sheet_storage_port, mut action_pdf_port) =
+            attachable_sheet(sheet_id);
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Err(ActionError::InvalidAction("Upload error".to_string())));
+        action_pdf_port
+            .expect_detach_calculation_js()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let original = attached_action(sheet_id, action_id, "modifier");
+        let mut attached_action_port = MockAttachedActionPort::new();
+        let found = original.clone();
+        attached_action_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_, _| Ok(found.clone()));
+        let listed = original.clone();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |_| Ok(vec![listed.clone()]));
+        attached_action_port
+            .expect_update()
+            .withf(|action| action.target_field == "score")
+            .times(1)
+            .returning(|_| Ok(()));
+        attached_action_port
+            .expect_update()
+            .withf(|action| action.target_field == "modifier")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let action = CalculationAction::ability_modifier("modifier", "score");
+
+        // Act
+        let result = service
+            .update_action(&Dnd5e, &sheet_id, &action_id, action)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("Upload error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_attach_calculation_scripts_in_single_pdf_pass() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("STR", FormFieldKind::Text),
+                    ("STRmod", FormFieldKind::Text),
+                    ("DEX", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port
+            .expect_attach_calculation_scripts()
+            .withf(|helpers_js, scripts, calculation_order, _| {
+                helpers_js.contains("function calculateModifierFromScore")
+                    && scripts.len() == 1
+                    && scripts[0].target_field == "STRmod"
+                    && calculation_order == ["STRmod".to_string()]
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![Ok(())]));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        attached_action_port
+            .expect_save()
+            .withf(|action| action.target_field == "STRmod")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let actions = vec![
+            CalculationAction::ability_modifier("STR", "STRmod"),
+            CalculationAction::ability_modifier("DEX", "missing"),
+        ];
+
+        // Act
+        let results = service
+            .attach_calculation_scripts(&Dnd5e, &sheet_id, actions)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].target_field, "STRmod");
+        assert!(results[0].outcome.is_ok());
+        assert_eq!(results[1].target_field, "missing");
+        assert_eq!(
+            results[1].outcome,
+            Err(ActionError::InvalidFieldMapping(vec![FieldRoleViolation {
+                role: "abilityModifierFieldName".to_string(),
+                field_name: "missing".to_string(),
+                problem: FieldRoleProblem::Missing,
+            }]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_not_upload_sheet_when_no_calculation_script_attached() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| Ok(form_fields(&[("STR", FormFieldKind::Text)])));
+        action_pdf_port.expect_attach_calculation_scripts().never();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        attached_action_port.expect_save().never();
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let results = service
+            .attach_calculation_scripts(
+                &Dnd5e,
+                &sheet_id,
+                vec![CalculationAction::ability_modifier("STR", "missing")],
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(results.len(), 1);
+        assert!(results[0].outcome.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_roll_back_batch_when_persisting_attached_action_fails() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(1)
+            .returning(|_| {
+                Ok(form_fields(&[
+                    ("STR", FormFieldKind::Text),
+                    ("STRmod", FormFieldKind::Text),
+                    ("DEX", FormFieldKind::Text),
+                    ("DEXmod", FormFieldKind::Text),
+                ]))
+            });
+        action_pdf_port
+            .expect_attach_calculation_scripts()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![Ok(()), Ok(())]));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        attached_action_port
+            .expect_save()
+            .withf(|action| action.target_field == "STRmod")
+            .times(1)
+            .returning(|_| Ok(()));
+        attached_action_port
+            .expect_save()
+            .withf(|action| action.target_field == "DEXmod")
+            .times(1)
+            .returning(|_| Err(ActionError::InvalidAction("DB error".to_string())));
+        attached_action_port
+            .expect_delete()
+            .withf(move |sid, _| *sid == sheet_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        let actions = vec![
+            CalculationAction::ability_modifier("STR", "STRmod"),
+            CalculationAction::ability_modifier("DEX", "DEXmod"),
+        ];
+
+        // Act
+        let result = service
+            .attach_calculation_scripts(&Dnd5e, &sheet_id, actions)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("DB error".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_regenerate_sheet_from_original_upload() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let action_id = Uuid::new_v4();
+        let sheet_path = PathBuf::from("/tmp/test-sheet.pdf");
+        let original_path = PathBuf::from("/tmp/original.pdf");
+        let sheet_reference = SheetReference::new(sheet_id, sheet_path.clone());
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        let original = original_path.clone();
+        let storage_path = sheet_path.clone();
+        sheet_storage_port
+            .expect_read_original()
+            .withf(move |path| *path == sheet_path)
+            .times(1)
+            .returning(move |_| Ok(LocalSheet::new(original.clone())));
+        sheet_storage_port.expect_read().never();
+        let written = original_path.clone();
+        sheet_storage_port
+            .expect_write()
+            .withf(move |local, storage| local.path() == written && *storage == storage_path)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_attach_calculation_scripts()
+            .withf(move |_, scripts, _, path| {
+                scripts.len() == 1 && scripts[0].target_field == "STRmod" && path == original_path
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![Ok(())]));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(move |sid| Ok(vec![attached_action(*sid, action_id, "STRmod")]));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let results = service.regenerate_sheet(&Dnd5e, &sheet_id).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            results,
+            vec![AttachActionResult {
+                action_type: "AbilityModifier".to_string(),
+                target_field: "STRmod".to_string(),
+                outcome: Ok(action_id),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_regenerating_sheet_when_original_cannot_be_read() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read_original()
+            .times(1)
+            .returning(|_| Err(ActionError::InvalidAction("connection reset".to_string())));
+        sheet_storage_port.expect_read().never();
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port.expect_attach_calculation_scripts().never();
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|sid| Ok(vec![attached_action(*sid, Uuid::new_v4(), "STRmod")]));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let result = service.regenerate_sheet(&Dnd5e, &sheet_id).await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::InvalidAction("connection reset".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_regenerate_sheet_from_current_sheet_when_original_missing() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read_original()
+            .times(1)
+            .returning(|_| Err(ActionError::FileNotFound));
+        sheet_storage_port
+            .expect_read()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_attach_calculation_scripts()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![Ok(())]));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|sid| Ok(vec![attached_action(*sid, Uuid::new_v4(), "STRmod")]));
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let results = service.regenerate_sheet(&Dnd5e, &sheet_id).await.unwrap();
+
+        // Assert
+        assert_eq!(results.len(), 1);
+        assert!(results[0].outcome.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_should_leave_stored_sheet_unchanged_when_stored_action_cannot_be_replayed() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read_original()
+            .times(1)
+            .returning(|path| Ok(LocalSheet::new(path)));
+        sheet_storage_port.expect_write().never();
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_attach_calculation_scripts()
+            .withf(|_, scripts, _, _| scripts.len() == 1 && scripts[0].target_field == "DEXmod")
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![Ok(())]));
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|sid| {
+                let mut corrupted = attached_action(*sid, Uuid::new_v4(), "STRmod");
+                corrupted.mapping = serde_json::json!({ "Unknown": {} });
+                let replayable = attached_action(*sid, Uuid::new_v4(), "DEXmod");
+                Ok(vec![corrupted, replayable])
+            });
+
+        let service = ActionService::new(
+            Arc::new(sheet_reference_port),
+            Arc::new(sheet_storage_port),
+            Arc::new(action_pdf_port),
+            Arc::new(attached_action_port),
+        );
+
+        // Act
+        let results = service.regenerate_sheet(&Dnd5e, &sheet_id).await.unwrap();
+
+        // Assert
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            results[0].outcome,
+            Err(ActionError::InvalidAction(_))
+        ));
+        assert!(results[1].outcome.is_ok());
+    }
+
+    fn mapped_sheet(
+        sheet_id: Uuid,
+        fields: &'static [(&'static str, FormFieldKind)],
+        lookups: usize,
+    ) -> (
+        MockSheetReferencePort,
+        MockSheetStoragePort,
+        MockActionPdfPort,
+    ) {
+        let sheet_reference = SheetReference::new(sheet_id, PathBuf::from("/tmp/test-sheet.pdf"));
+
+        let mut sheet_reference_port = MockSheetReferencePort::new();
+        sheet_reference_port
+            .expect_find_by_id()
+            .times(lookups)
+            .returning(move |_| Ok(sheet_reference.clone()));
+
+        let mut sheet_storage_port = MockSheetStoragePort::new();
+        sheet_storage_port
+            .expect_read()
+            .times(lookups)
+            .returning(|path| Ok(LocalSheet::new(path)));
+
+        let mut action_pdf_port = MockActionPdfPort::new();
+        action_pdf_port
+            .expect_list_form_fields()
+            .times(lookups)
+            .returning(move |_| Ok(form_fields(fields)));
+
+        (sheet_reference_port, sheet_storage_port, action_pdf_port)
+    }
+
+    const MAPPED_FIELDS: &[(&str, FormFieldKind)] = &[
+        ("score", FormFieldKind::Text),
+        ("modifier", FormFieldKind::Text),
+    ];
+
+    #[tokio::test]
+    async fn test_should_save_attached_actions_as_mapping_template() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let (sheet_reference_port, sheet_storage_port, action_pdf_port) =
+            mapped_sheet(sheet_id, MAPPED_FIELDS, 1);
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|sid| Ok(vec![attached_action(*sid, Uuid::new_v4(), "modifier")]));
+
+        let expected_fingerprint = template::fingerprint(&form_fields(MAPPED_FIELDS));
+        let mut mapping_template_port = MockMappingTemplatePort::new();
+        mapping_template_port
+            .expect_save()
+            .withf(move |template| {
+                template.name == "Official 5e sheet"
+                    && template.system == "dnd5e"
+                    && template.fingerprint == expected_fingerprint
+                    && template.actions.len() == 1
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = MappingTemplateService::new(
+            ActionService::new(
+                Arc::new(sheet_reference_port),
+                Arc::new(sheet_storage_port),
+                Arc::new(action_pdf_port),
+                Arc::new(attached_action_port),
+            ),
+            Arc::new(mapping_template_port),
+        );
+
+        // Act
+        let result = service
+            .create_template(&Dnd5e, &sheet_id, "  Official 5e sheet ")
+            .await;
+
+        // Assert
+        let template = result.unwrap();
+        assert_eq!(template.name, "Official 5e sheet");
+        assert_eq!(
+            template.actions,
+            vec![attached_action(sheet_id, Uuid::new_v4(), "modifier").mapping]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_not_save_mapping_template_of_sheet_without_actions() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let (sheet_reference_port, sheet_storage_port, action_pdf_port) =
+            mapped_sheet(sheet_id, MAPPED_FIELDS, 1);
+
+        let mut mapping_template_port = MockMappingTemplatePort::new();
+        mapping_template_port.expect_save().never();
+
+        let service = MappingTemplateService::new(
+            ActionService::new(
+                Arc::new(sheet_reference_port),
+                Arc::new(sheet_storage_port),
+                Arc::new(action_pdf_port),
+                Arc::new(mock_attached_action_port()),
+            ),
+            Arc::new(mapping_template_port),
+        );
+
+        // Act
+        let result = service.create_template(&Dnd5e, &sheet_id, "Empty").await;
+
+        // Assert
+        assert!(matches!(result, Err(ActionError::InvalidTemplate(_))));
+    }
+
+    #[tokio::test]
+    async fn test_should_not_save_mapping_template_of_sheet_with_actions_of_other_system() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let (sheet_reference_port, sheet_storage_port, action_pdf_port) =
+            mapped_sheet(sheet_id, MAPPED_FIELDS, 1);
+
+        let mut attached_action_port = MockAttachedActionPort::new();
+        attached_action_port
+            .expect_list_by_sheet_id()
+            .times(1)
+            .returning(|sid| {
+                Ok(vec![AttachedAction {
+                    action_type: "SkillModifier".to_string(),
+                    ..attached_action(*sid, Uuid::new_v4(), "modifier")
+                }])
+            });
+
+        let mut mapping_template_port = MockMappingTemplatePort::new();
+        mapping_template_port.expect_save().never();
+
+        let service = MappingTemplateService::new(
+            ActionService::new(
+                Arc::new(sheet_reference_port),
+                Arc::new(sheet_storage_port),
+                Arc::new(action_pdf_port),
+                Arc::new(attached_action_port),
+            ),
+            Arc::new(mapping_template_port),
+        );
+
+        // Act
+        let result = service
+            .create_template(&Pathfinder2e, &sheet_id, "Skills")
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::GameSystemMismatch {
+                system: "Pathfinder 2nd Edition".to_string(),
+                action: "SkillModifier".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_apply_mapping_template_to_sheet_with_same_fingerprint() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let template_id = Uuid::new_v4();
+        let (sheet_reference_port, mut sheet_storage_port, mut action_pdf_port) =
+            mapped_sheet(sheet_id, MAPPED_FIELDS, 2);
+        sheet_storage_port
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        action_pdf_port
+            .expect_attach_calculation_scripts()
+            .withf(|_, scripts, _, _| scripts.len() == 1 && scripts[0].target_field == "modifier")
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![Ok(())]));
+
+        let mut mapping_template_port = MockMappingTemplatePort::new();
+        mapping_template_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |id| {
+                Ok(MappingTemplate {
+                    id: *id,
+                    name: "Official 5e sheet".to_string(),
+                    system: "dnd5e".to_string(),
+                    fingerprint: template::fingerprint(&form_fields(MAPPED_FIELDS)),
+                    actions: vec![attached_action(sheet_id, Uuid::new_v4(), "modifier").mapping],
+                })
+            });
+
+        let service = MappingTemplateService::new(
+            ActionService::new(
+                Arc::new(sheet_reference_port),
+                Arc::new(sheet_storage_port),
+                Arc::new(action_pdf_port),
+                Arc::new(mock_attached_action_port()),
+            ),
+            Arc::new(mapping_template_port),
+        );
+
+        // Act
+        let results = service
+            .apply_template(&Dnd5e, &sheet_id, &template_id)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].target_field, "modifier");
+        assert!(results[0].outcome.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_mapping_template_of_sheet_with_other_fields() {
+        // Arrange
+        let sheet_id = Uuid::new_v4();
+        let (sheet_reference_port, mut sheet_storage_port, mut action_pdf_port) =
+            mapped_sheet(sheet_id, &[("STR", FormFieldKind::Text)], 1);
+        sheet_storage_port.expect_write().never();
+        action_pdf_port.expect_attach_calculation_scripts().never();
+
+        let mut mapping_template_port = MockMappingTemplatePort::new();
+        mapping_template_port
+            .expect_find_by_id()
+            .times(1)
+            .returning(|id| {
+                Ok(MappingTemplate {
+                    id: *id,
+                    name: "Official 5e sheet".to_string(),
+                    system: "dnd5e".to_string(),
+                    fingerprint: template::fingerprint(&form_fields(MAPPED_FIELDS)),
+                    actions: vec![],
+                })
+            });
+
+        let service = MappingTemplateService::new(
+            ActionService::new(
+                Arc::new(sheet_reference_port),
+                Arc::new(sheet_storage_port),
+                Arc::new(action_pdf_port),
+                Arc::new(MockAttachedActionPort::new()),
+            ),
+            Arc::new(mapping_template_port),
+        );
+
+        // Act
+        let result = service
+            .apply_template(&Dnd5e, &sheet_id, &Uuid::new_v4())
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ActionError::TemplateMismatch(
+                "Official 5e sheet".to_string()
+            ))
+        );
+    }
 }
@@ -0,0 +1,736 @@
+//! Evaluates calculation actions in Rust, mirroring the helper JS they run in a PDF viewer, so a
+//! sheet's computed fields can be previewed without opening it.
+
+use crate::action::CalculationAction;
+use crate::error::ActionError;
+use crate::ports::driven::{FormField, FormFieldKind};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Value of a form field as the helper JS sees it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FieldValue {
+    Number(f64),
+    Text(String),
+}
+
+impl FieldValue {
+    /// Reads a stored `/V` the way Acrobat exposes `field.value`: text that reads as a number
+    /// becomes one, and checkboxes and radio buttons hold their export value or `Off`.
+    fn from_stored(kind: FormFieldKind, value: &str) -> Self {
+        match kind {
+            FormFieldKind::Text | FormFieldKind::Choice if !value.trim().is_empty() => {
+                match js_number(value) {
+                    Some(number) => Self::Number(number),
+                    None => Self::Text(value.to_string()),
+                }
+            }
+            _ => Self::Text(value.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => f.write_str(&js_string(*number)),
+            Self::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+/// Outcome of previewing one attached action.
+#[derive(Debug, PartialEq)]
+pub struct PreviewResult {
+    pub action_type: String,
+    pub target_field: String,
+    pub outcome: Result<FieldValue, ActionError>,
+}
+
+/// The sheet's form fields with their current values, updated as actions are evaluated so later
+/// calculations read the results of earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct SheetValues {
+    fields: HashMap<String, (FormFieldKind, FieldValue)>,
+}
+
+impl SheetValues {
+    /// Takes the stored value of every form field, treating a field without one as blank.
+    pub fn new(form_fields: &[FormField], stored_values: &HashMap<String, String>) -> Self {
+        let fields = form_fields
+            .iter()
+            .map(|field| {
+                let value = stored_values.get(&field.name).map_or_else(
+                    || match field.kind {
+                        FormFieldKind::Checkbox | FormFieldKind::Radio => "Off",
+                        _ => "",
+                    },
+                    String::as_str,
+                );
+                (
+                    field.name.clone(),
+                    (field.kind, FieldValue::from_stored(field.kind, value)),
+                )
+            })
+            .collect();
+        Self { fields }
+    }
+
+    /// Replaces the value of a form field as if it had been typed in.
+    pub fn set(&mut self, field_name: &str, value: &str) -> Result<(), ActionError> {
+        let (kind, current) = self
+            .fields
+            .get_mut(field_name)
+            .ok_or_else(|| ActionError::FieldNotFound(field_name.to_string()))?;
+        *current = FieldValue::from_stored(*kind, value);
+        Ok(())
+    }
+
+    pub fn get(&self, field_name: &str) -> Option<&FieldValue> {
+        self.fields.get(field_name).map(|(_, value)| value)
+    }
+
+    /// Computes the target field of `action` and stores the result in it.
+    pub fn evaluate(&mut self, action: &CalculationAction) -> Result<FieldValue, ActionError> {
+        let value = self.compute(action)?;
+        if let Some((_, current)) = self.fields.get_mut(action.target_field()) {
+            *current = value.clone();
+        }
+        Ok(value)
+    }
+
+    fn compute(&self, action: &CalculationAction) -> Result<FieldValue, ActionError> {
+        let value = match action {
+            CalculationAction::AbilityModifier {
+                score_field_name, ..
+            } => calculate_modifier(self.number(score_field_name)),
+            CalculationAction::SavingThrowModifier {
+                ability_modifier_field_name,
+                proficiency_field_name,
+                proficiency_bonus_field_name,
+                ..
+            } => {
+                self.number(ability_modifier_field_name)
+                    + proficiency_bonus_if(
+                        self.boolean(proficiency_field_name),
+                        self.number(proficiency_bonus_field_name),
+                    )
+            }
+            CalculationAction::SkillModifier {
+                ability_modifier_field_name,
+                proficiency_field_name,
+                expertise_field_name,
+                half_prof_field_name,
+                proficiency_bonus_field_name,
+                ..
+            } => {
+                let multiplier = proficiency_multiplier(
+                    self.boolean(proficiency_field_name),
+                    self.optional_boolean(expertise_field_name.as_deref()),
+                    self.optional_boolean(half_prof_field_name.as_deref()),
+                );
+                (self.number(ability_modifier_field_name)
+                    + multiplier * self.number(proficiency_bonus_field_name))
+                .floor()
+            }
+            CalculationAction::PassiveScore {
+                skill_modifier_field_name,
+                bonus_field_name,
+                advantage_field_name,
+                disadvantage_field_name,
+                ..
+            } => {
+                let advantage = if self.optional_boolean(advantage_field_name.as_deref()) {
+                    5.0
+                } else {
+                    0.0
+                };
+                let disadvantage = if self.optional_boolean(disadvantage_field_name.as_deref()) {
+                    5.0
+                } else {
+                    0.0
+                };
+                10.0 + self.number(skill_modifier_field_name)
+                    + self.optional_number(bonus_field_name.as_deref())
+                    + advantage
+                    - disadvantage
+            }
+            CalculationAction::ProficiencyBonus {
+                level_field_name,
+                multiclass_level_field_names,
+                ..
+            } => {
+                let level = self.number(level_field_name) + self.sum(multiclass_level_field_names);
+                2.0 + ((level.max(1.0) - 1.0) / 4.0).floor()
+            }
+            CalculationAction::Initiative {
+                dex_modifier_field_name,
+                misc_bonus_field_names,
+                jack_of_all_trades_field_name,
+                proficiency_bonus_field_name,
+                ..
+            } => {
+                let multiplier = proficiency_multiplier(
+                    false,
+                    false,
+                    self.optional_boolean(jack_of_all_trades_field_name.as_deref()),
+                );
+                (self.number(dex_modifier_field_name)
+                    + self.sum(misc_bonus_field_names)
+                    + multiplier * self.optional_number(proficiency_bonus_field_name.as_deref()))
+                .floor()
+            }
+            CalculationAction::ArmorClass {
+                base_armor_field_name,
+                dex_modifier_field_name,
+                max_dex_bonus_field_name,
+                shield_field_name,
+                misc_bonus_field_name,
+                ..
+            } => {
+                let base_armor = self
+                    .blank_or_number(Some(base_armor_field_name))
+                    .unwrap_or(10.0);
+                let dex_modifier = self.number(dex_modifier_field_name);
+                let dex_bonus = match self.blank_or_number(max_dex_bonus_field_name.as_deref()) {
+                    Some(max_dex_bonus) => js_min(dex_modifier, max_dex_bonus),
+                    None => dex_modifier,
+                };
+                let shield = if self.optional_boolean(shield_field_name.as_deref()) {
+                    2.0
+                } else {
+                    0.0
+                };
+                base_armor
+                    + dex_bonus
+                    + shield
+                    + self.optional_number(misc_bonus_field_name.as_deref())
+            }
+            CalculationAction::SpellSaveDc {
+                spellcasting_ability_field_name,
+                intelligence_modifier_field_name,
+                wisdom_modifier_field_name,
+                charisma_modifier_field_name,
+                proficiency_bonus_field_name,
+                misc_bonus_field_name,
+                ..
+            }
+            | CalculationAction::SpellAttackBonus {
+                spellcasting_ability_field_name,
+                intelligence_modifier_field_name,
+                wisdom_modifier_field_name,
+                charisma_modifier_field_name,
+                proficiency_bonus_field_name,
+                misc_bonus_field_name,
+                ..
+            } => {
+                let modifier_field =
+                    match spellcasting_ability_key(&self.text(spellcasting_ability_field_name))
+                        .as_str()
+                    {
+                        "INT" => Some(intelligence_modifier_field_name.as_str()),
+                        "WIS" => Some(wisdom_modifier_field_name.as_str()),
+                        "CHA" => Some(charisma_modifier_field_name.as_str()),
+                        _ => None,
+                    };
+                let base = if matches!(action, CalculationAction::SpellSaveDc { .. }) {
+                    8.0
+                } else {
+                    0.0
+                };
+                base + self.number(proficiency_bonus_field_name)
+                    + self.optional_number(modifier_field)
+                    + self.optional_number(misc_bonus_field_name.as_deref())
+            }
+            CalculationAction::WeaponAttackBonus {
+                strength_modifier_field_name,
+                dexterity_modifier_field_name,
+                finesse_field_name,
+                ranged_field_name,
+                proficiency_field_name,
+                proficiency_bonus_field_name,
+                magic_bonus_field_name,
+                ..
+            } => {
+                self.weapon_ability_modifier(
+                    strength_modifier_field_name,
+                    dexterity_modifier_field_name,
+                    finesse_field_name.as_deref(),
+                    ranged_field_name.as_deref(),
+                ) + proficiency_bonus_if(
+                    self.boolean(proficiency_field_name),
+                    self.number(proficiency_bonus_field_name),
+                ) + self.optional_number(magic_bonus_field_name.as_deref())
+            }
+            CalculationAction::WeaponDamageBonus {
+                strength_modifier_field_name,
+                dexterity_modifier_field_name,
+                finesse_field_name,
+                ranged_field_name,
+                magic_bonus_field_name,
+                ..
+            } => {
+                self.weapon_ability_modifier(
+                    strength_modifier_field_name,
+                    dexterity_modifier_field_name,
+                    finesse_field_name.as_deref(),
+                    ranged_field_name.as_deref(),
+                ) + self.optional_number(magic_bonus_field_name.as_deref())
+            }
+            CalculationAction::CarryingCapacity {
+                strength_score_field_name,
+                size_field_name,
+                ..
+            } => {
+                self.number(strength_score_field_name)
+                    * 15.0
+                    * size_carrying_multiplier(&self.optional_text(size_field_name.as_deref()))
+            }
+            CalculationAction::EncumbranceStatus {
+                strength_score_field_name,
+                weight_field_names,
+                size_field_name,
+                ..
+            } => {
+                let total_weight = self.sum(weight_field_names);
+                let capacity_per_point = self.number(strength_score_field_name)
+                    * size_carrying_multiplier(&self.optional_text(size_field_name.as_deref()));
+                let status = if total_weight > capacity_per_point * 10.0 {
+                    "Heavily Encumbered"
+                } else if total_weight > capacity_per_point * 5.0 {
+                    "Encumbered"
+                } else {
+                    "Unencumbered"
+                };
+                return Ok(FieldValue::Text(status.to_string()));
+            }
+            CalculationAction::ProficiencyRankModifier {
+                ability_modifier_field_name,
+                proficiency_rank_field_name,
+                level_field_name,
+                item_bonus_field_name,
+                ..
+            } => {
+                let rank_bonus = proficiency_rank_bonus(&self.text(proficiency_rank_field_name));
+                let proficiency = if rank_bonus > 0.0 {
+                    rank_bonus + self.number(level_field_name)
+                } else {
+                    0.0
+                };
+                self.number(ability_modifier_field_name)
+                    + proficiency
+                    + self.optional_number(item_bonus_field_name.as_deref())
+            }
+            CalculationAction::SumFields {
+                source_field_names, ..
+            } => self.sum(source_field_names),
+            CalculationAction::Formula { expression, .. } => {
+                let value = expression
+                    .expression()
+                    .evaluate(&|field_name| self.formula_number(field_name));
+                if value.is_finite() { value } else { 0.0 }
+            }
+            CalculationAction::Custom(custom) => {
+                return Err(ActionError::PreviewUnsupported(custom.action_type.clone()));
+            }
+        };
+        Ok(FieldValue::Number(value))
+    }
+
+    /// `getNumberValueFromField`: a missing or non-numeric field reads as 0.
+    fn number(&self, field_name: &str) -> f64 {
+        match self.get(field_name) {
+            Some(FieldValue::Number(number)) if !number.is_nan() => *number,
+            Some(FieldValue::Text(text)) => js_number(text).filter(|n| !n.is_nan()).unwrap_or(0.0),
+            _ => 0.0,
+        }
+    }
+
+    /// An unmapped optional role reads like a missing field.
+    fn optional_number(&self, field_name: Option<&str>) -> f64 {
+        field_name.map_or(0.0, |field_name| self.number(field_name))
+    }
+
+    /// `getOptionalNumberValueFromField`: a missing, blank or non-numeric field reads as `None`.
+    fn blank_or_number(&self, field_name: Option<&str>) -> Option<f64> {
+        match self.get(field_name?)? {
+            FieldValue::Number(number) => Some(*number),
+            FieldValue::Text(text) if text.is_empty() => None,
+            FieldValue::Text(text) => js_number(text),
+        }
+        .filter(|number| !number.is_nan())
+    }
+
+    /// `getBoolValueFromField`: anything but a missing field, `Off`, 0 or blank is true.
+    fn boolean(&self, field_name: &str) -> bool {
+        match self.get(field_name) {
+            Some(FieldValue::Number(number)) => *number != 0.0,
+            Some(FieldValue::Text(text)) => text != "Off" && !text.is_empty(),
+            None => false,
+        }
+    }
+
+    fn optional_boolean(&self, field_name: Option<&str>) -> bool {
+        field_name.is_some_and(|field_name| self.boolean(field_name))
+    }
+
+    /// `getTextValueFromField`: a missing field reads as blank.
+    fn text(&self, field_name: &str) -> String {
+        self.get(field_name)
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+
+    fn optional_text(&self, field_name: Option<&str>) -> String {
+        field_name
+            .map(|field_name| self.text(field_name))
+            .unwrap_or_default()
+    }
+
+    /// `getFormulaValueFromField`: checkboxes and radio buttons read as 0 or 1.
+    fn formula_number(&self, field_name: &str) -> f64 {
+        match self.fields.get(field_name) {
+            Some((FormFieldKind::Checkbox | FormFieldKind::Radio, _)) => {
+                if self.boolean(field_name) { 1.0 } else { 0.0 }
+            }
+            _ => self.number(field_name),
+        }
+    }
+
+    fn sum(&self, field_names: &[String]) -> f64 {
+        field_names
+            .iter()
+            .map(|field_name| self.number(field_name))
+            .sum()
+    }
+
+    /// `getWeaponAbilityModifier`: finesse takes the higher of STR and DEX, ranged takes DEX.
+    fn weapon_ability_modifier(
+        &self,
+        strength_modifier_field_name: &str,
+        dexterity_modifier_field_name: &str,
+        finesse_field_name: Option<&str>,
+        ranged_field_name: Option<&str>,
+    ) -> f64 {
+        let strength = self.number(strength_modifier_field_name);
+        let dexterity = self.number(dexterity_modifier_field_name);
+        if self.optional_boolean(finesse_field_name) {
+            js_max(strength, dexterity)
+        } else if self.optional_boolean(ranged_field_name) {
+            dexterity
+        } else {
+            strength
+        }
+    }
+}
+
+/// Evaluates `actions` in calculation order, each reading the results of the ones before it.
+pub fn preview_actions(
+    values: &mut SheetValues,
+    actions: &[CalculationAction],
+) -> Vec<PreviewResult> {
+    actions
+        .iter()
+        .map(|action| PreviewResult {
+            action_type: action.action_label().to_string(),
+            target_field: action.target_field().to_string(),
+            outcome: values.evaluate(action),
+        })
+        .collect()
+}
+
+fn calculate_modifier(score: f64) -> f64 {
+    ((score - 10.0) / 2.0).floor()
+}
+
+fn proficiency_bonus_if(proficient: bool, proficiency_bonus: f64) -> f64 {
+    if proficient { proficiency_bonus } else { 0.0 }
+}
+
+/// `getProficiencyMultiplier`: expertise doubles, half proficiency halves before rounding down.
+fn proficiency_multiplier(proficient: bool, expertise: bool, half: bool) -> f64 {
+    if expertise {
+        2.0
+    } else if proficient {
+        1.0
+    } else if half {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+/// First three letters of the selected ability, e.g. `WIS` for `Wisdom`.
+fn spellcasting_ability_key(ability: &str) -> String {
+    ability
+        .trim()
+        .chars()
+        .take(3)
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn size_carrying_multiplier(size: &str) -> f64 {
+    match size.trim().to_lowercase().as_str() {
+        "tiny" => 0.5,
+        "large" => 2.0,
+        "huge" => 4.0,
+        "gargantuan" => 8.0,
+        _ => 1.0,
+    }
+}
+
+/// `getProficiencyRankBonus`: ranks are matched by their initial.
+fn proficiency_rank_bonus(rank: &str) -> f64 {
+    match rank.trim().chars().next().map(|c| c.to_ascii_uppercase()) {
+        Some('T') => 2.0,
+        Some('E') => 4.0,
+        Some('M') => 6.0,
+        Some('L') => 8.0,
+        _ => 0.0,
+    }
+}
+
+/// `Math.min` of two numbers, which is NaN when either is.
+fn js_min(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else {
+        a.min(b)
+    }
+}
+
+fn js_max(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else {
+        a.max(b)
+    }
+}
+
+/// `Number(text)` for the decimal, hexadecimal and `Infinity` forms; `None` where JS gives NaN.
+fn js_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(0.0);
+    }
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        return u64::from_str_radix(hex, 16).ok().map(|n| n as f64);
+    }
+    match text {
+        "Infinity" | "+Infinity" => return Some(f64::INFINITY),
+        "-Infinity" => return Some(f64::NEG_INFINITY),
+        _ => {}
+    }
+    // Rust also accepts "inf" and "NaN", which JS does not
+    if text
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// `String(number)` for the values calculations produce.
+fn js_string(number: f64) -> String {
+    if number.is_nan() {
+        "NaN".to_string()
+    } else if number.is_infinite() {
+        if number > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        }
+        .to_string()
+    } else if number == 0.0 {
+        "0".to_string()
+    } else {
+        number.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition::CustomAction;
+    use crate::formula::Formula;
+    use pretty_assertions::assert_eq;
+
+    fn sheet(fields: &[(&str, FormFieldKind, &str)]) -> SheetValues {
+        let form_fields: Vec<FormField> = fields
+            .iter()
+            .map(|(name, kind, _)| FormField::new(*name, *kind))
+            .collect();
+        let stored_values = fields
+            .iter()
+            .map(|(name, _, value)| (name.to_string(), value.to_string()))
+            .collect();
+        SheetValues::new(&form_fields, &stored_values)
+    }
+
+    #[test]
+    fn test_should_floor_modifiers_of_odd_and_low_scores() {
+        let mut values = sheet(&[
+            ("STR", FormFieldKind::Text, "15"),
+            ("STRmod", FormFieldKind::Text, ""),
+            ("DEX", FormFieldKind::Text, "7"),
+            ("DEXmod", FormFieldKind::Text, ""),
+        ]);
+
+        let strength = values.evaluate(&CalculationAction::ability_modifier("STR", "STRmod"));
+        let dexterity = values.evaluate(&CalculationAction::ability_modifier("DEX", "DEXmod"));
+
+        assert_eq!(strength, Ok(FieldValue::Number(2.0)));
+        assert_eq!(dexterity, Ok(FieldValue::Number(-2.0)));
+        assert_eq!(values.get("DEXmod"), Some(&FieldValue::Number(-2.0)));
+    }
+
+    #[test]
+    fn test_should_round_half_proficiency_down_and_read_checkboxes_as_truthy() {
+        // Arrange
+        let mut values = sheet(&[
+            ("CHAmod", FormFieldKind::Text, "1"),
+            ("Persuasion Prof", FormFieldKind::Checkbox, "Off"),
+            ("Jack", FormFieldKind::Checkbox, "Yes"),
+            ("ProfBonus", FormFieldKind::Text, "3"),
+            ("Persuasion", FormFieldKind::Text, ""),
+        ]);
+        let action = CalculationAction::skill_modifier(
+            "CHAmod",
+            "Persuasion Prof",
+            None::<String>,
+            Some("Jack"),
+            "ProfBonus",
+            "Persuasion",
+        );
+
+        // Act
+        let result = values.evaluate(&action);
+
+        // Assert
+        // 1 + 0.5 * 3 = 2.5, rounded down like Math.floor
+        assert_eq!(result, Ok(FieldValue::Number(2.0)));
+    }
+
+    #[test]
+    fn test_should_chain_calculations_in_order() {
+        // Arrange
+        let mut values = sheet(&[
+            ("STR", FormFieldKind::Text, "10"),
+            ("STRmod", FormFieldKind::Text, "99"),
+            ("Bonus", FormFieldKind::Checkbox, "Off"),
+            ("Total", FormFieldKind::Text, ""),
+        ]);
+        values.set("STR", "18").unwrap();
+        values.set("Bonus", "Yes").unwrap();
+        let actions = vec![
+            CalculationAction::ability_modifier("STR", "STRmod"),
+            CalculationAction::formula(
+                Formula::parse("{STRmod} * 2 + {Bonus} + {Missing}").unwrap(),
+                "Total",
+            ),
+        ];
+
+        // Act
+        let results = preview_actions(&mut values, &actions);
+
+        // Assert
+        let outcomes: Vec<_> = results.into_iter().map(|result| result.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![Ok(FieldValue::Number(4.0)), Ok(FieldValue::Number(9.0))]
+        );
+    }
+
+    #[test]
+    fn test_should_write_encumbrance_status_as_text() {
+        let mut values = sheet(&[
+            ("STR", FormFieldKind::Text, "10"),
+            ("Wgt1", FormFieldKind::Text, "40"),
+            ("Wgt2", FormFieldKind::Text, "20.5"),
+            ("Size", FormFieldKind::Choice, " tiny "),
+            ("Status", FormFieldKind::Text, ""),
+        ]);
+        let action = CalculationAction::encumbrance_status(
+            "STR",
+            vec!["Wgt1".to_string(), "Wgt2".to_string()],
+            Some("Size".to_string()),
+            "Status",
+        );
+
+        let result = values.evaluate(&action);
+
+        // 60.5 lb is above the 50 lb threshold of a tiny creature with STR 10
+        assert_eq!(
+            result,
+            Ok(FieldValue::Text("Heavily Encumbered".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_should_read_non_numeric_text_as_zero_and_blank_armor_as_unarmored() {
+        let mut values = sheet(&[
+            ("Armor", FormFieldKind::Text, ""),
+            ("DEXmod", FormFieldKind::Text, "+3"),
+            ("MaxDex", FormFieldKind::Text, "2"),
+            ("Misc", FormFieldKind::Text, "n/a"),
+            ("AC", FormFieldKind::Text, ""),
+        ]);
+        let action = CalculationAction::ArmorClass {
+            base_armor_field_name: "Armor".to_string(),
+            dex_modifier_field_name: "DEXmod".to_string(),
+            max_dex_bonus_field_name: Some("MaxDex".to_string()),
+            shield_field_name: None,
+            misc_bonus_field_name: Some("Misc".to_string()),
+            armor_class_field_name: "AC".to_string(),
+        };
+
+        let result = values.evaluate(&action);
+
+        assert_eq!(result, Ok(FieldValue::Number(12.0)));
+    }
+
+    #[test]
+    fn test_should_not_preview_custom_actions() {
+        let mut values = sheet(&[]);
+
+        let result = values.evaluate(&CalculationAction::Custom(CustomAction::new(
+            "Hit Dice",
+            Default::default(),
+        )));
+
+        assert_eq!(
+            result,
+            Err(ActionError::PreviewUnsupported("Hit Dice".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_should_reject_overriding_unknown_field() {
+        let mut values = sheet(&[("STR", FormFieldKind::Text, "10")]);
+
+        let result = values.set("STRENGTH", "12");
+
+        assert_eq!(
+            result,
+            Err(ActionError::FieldNotFound("STRENGTH".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_js_number_matches_js_conversion() {
+        assert_eq!(js_number(" 12 "), Some(12.0));
+        assert_eq!(js_number(""), Some(0.0));
+        assert_eq!(js_number("0x1F"), Some(31.0));
+        assert_eq!(js_number("1e2"), Some(100.0));
+        assert_eq!(js_number("-Infinity"), Some(f64::NEG_INFINITY));
+        assert_eq!(js_number("inf"), None);
+        assert_eq!(js_number("12 lb"), None);
+        assert_eq!(js_string(2.5), "2.5");
+        assert_eq!(js_string(-0.0), "0");
+        assert_eq!(js_string(f64::INFINITY), "Infinity");
+    }
+}
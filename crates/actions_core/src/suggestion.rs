@@ -0,0 +1,684 @@
+use crate::action::{CalculationAction, FieldKind};
+use crate::ports::driven::FormField;
+use std::collections::{HashMap, HashSet};
+
+/// Confidence of a field found where the layout of a recognised sheet puts it.
+const KNOWN_SHEET_CONFIDENCE: f32 = 0.95;
+/// Confidence of a field whose name spells out the role, e.g. `Strength Modifier`.
+const STRONG_NAME_CONFIDENCE: f32 = 0.8;
+/// Confidence of a field whose name is a common but ambiguous abbreviation, e.g. `Init`.
+const WEAK_NAME_CONFIDENCE: f32 = 0.5;
+
+/// A calculation action proposed for a sheet, with how sure the matcher is that the mapped
+/// fields play the roles it assigned them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionSuggestion {
+    pub action: CalculationAction,
+    /// Between 0 and 1; the confidence of the least certain required field.
+    pub confidence: f32,
+}
+
+/// The actions proposed for a sheet.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MappingSuggestions {
+    /// Name of the well-known sheet the fields were recognised as, if any.
+    pub sheet_name: Option<&'static str>,
+    pub suggestions: Vec<ActionSuggestion>,
+}
+
+struct Ability {
+    abbreviation: &'static str,
+    name: &'static str,
+}
+
+const ABILITIES: [Ability; 6] = [
+    Ability {
+        abbreviation: "STR",
+        name: "Strength",
+    },
+    Ability {
+        abbreviation: "DEX",
+        name: "Dexterity",
+    },
+    Ability {
+        abbreviation: "CON",
+        name: "Constitution",
+    },
+    Ability {
+        abbreviation: "INT",
+        name: "Intelligence",
+    },
+    Ability {
+        abbreviation: "WIS",
+        name: "Wisdom",
+    },
+    Ability {
+        abbreviation: "CHA",
+        name: "Charisma",
+    },
+];
+const STR: usize = 0;
+const DEX: usize = 1;
+const INT: usize = 3;
+const WIS: usize = 4;
+const CHA: usize = 5;
+
+struct Skill {
+    name: &'static str,
+    /// Index into [`ABILITIES`].
+    ability: usize,
+    /// Short name some sheets use instead, e.g. `Animal` for Animal Handling.
+    short_name: Option<&'static str>,
+}
+
+const SKILLS: [Skill; 18] = [
+    Skill {
+        name: "Acrobatics",
+        ability: DEX,
+        short_name: None,
+    },
+    Skill {
+        name: "Animal Handling",
+        ability: WIS,
+        short_name: Some("Animal"),
+    },
+    Skill {
+        name: "Arcana",
+        ability: INT,
+        short_name: None,
+    },
+    Skill {
+        name: "Athletics",
+        ability: STR,
+        short_name: None,
+    },
+    Skill {
+        name: "Deception",
+        ability: CHA,
+        short_name: None,
+    },
+    Skill {
+        name: "History",
+        ability: INT,
+        short_name: None,
+    },
+    Skill {
+        name: "Insight",
+        ability: WIS,
+        short_name: None,
+    },
+    Skill {
+        name: "Intimidation",
+        ability: CHA,
+        short_name: None,
+    },
+    Skill {
+        name: "Investigation",
+        ability: INT,
+        short_name: None,
+    },
+    Skill {
+        name: "Medicine",
+        ability: WIS,
+        short_name: None,
+    },
+    Skill {
+        name: "Nature",
+        ability: INT,
+        short_name: None,
+    },
+    Skill {
+        name: "Perception",
+        ability: WIS,
+        short_name: None,
+    },
+    Skill {
+        name: "Performance",
+        ability: CHA,
+        short_name: None,
+    },
+    Skill {
+        name: "Persuasion",
+        ability: CHA,
+        short_name: None,
+    },
+    Skill {
+        name: "Religion",
+        ability: INT,
+        short_name: None,
+    },
+    Skill {
+        name: "Sleight of Hand",
+        ability: DEX,
+        short_name: Some("Sleight"),
+    },
+    Skill {
+        name: "Stealth",
+        ability: DEX,
+        short_name: None,
+    },
+    Skill {
+        name: "Survival",
+        ability: WIS,
+        short_name: None,
+    },
+];
+const PERCEPTION: usize = 11;
+
+/// A spot on a character sheet that a role can be mapped to. Abilities and skills are indices
+/// into [`ABILITIES`] and [`SKILLS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    AbilityScore(usize),
+    AbilityModifier(usize),
+    SavingThrow(usize),
+    SavingThrowProficiency(usize),
+    Skill(usize),
+    SkillProficiency(usize),
+    SkillExpertise(usize),
+    ProficiencyBonus,
+    Initiative,
+    PassivePerception,
+    Level,
+    SpellcastingAbility,
+    SpellSaveDc,
+    SpellAttackBonus,
+    CarryingCapacity,
+}
+
+impl Slot {
+    fn kind(self) -> FieldKind {
+        match self {
+            Self::SavingThrowProficiency(_)
+            | Self::SkillProficiency(_)
+            | Self::SkillExpertise(_) => FieldKind::Checkbox,
+            Self::SpellcastingAbility => FieldKind::Choice,
+            _ => FieldKind::NumericText,
+        }
+    }
+
+    /// Normalized names sheets commonly give the slot: those spelling out the role, then
+    /// ambiguous abbreviations.
+    fn candidate_names(self) -> (Vec<String>, Vec<String>) {
+        let ability_names = |ability: usize| {
+            let ability = &ABILITIES[ability];
+            [normalize(ability.abbreviation), normalize(ability.name)]
+        };
+        let combine = |prefixes: &[String], suffixes: &[&str]| -> Vec<String> {
+            prefixes
+                .iter()
+                .flat_map(|prefix| {
+                    suffixes
+                        .iter()
+                        .map(move |suffix| format!("{prefix}{suffix}"))
+                })
+                .collect()
+        };
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+        match self {
+            Self::AbilityScore(ability) => {
+                (combine(&ability_names(ability), &["", "score"]), vec![])
+            }
+            Self::AbilityModifier(ability) => (
+                combine(&ability_names(ability), &["mod", "modifier"]),
+                combine(&ability_names(ability), &["bonus"]),
+            ),
+            Self::SavingThrow(ability) => {
+                let [abbreviation, name] = ability_names(ability);
+                let mut strong = combine(&ability_names(ability), &["save", "savingthrow"]);
+                strong.extend([format!("st{name}"), format!("st{abbreviation}")]);
+                (
+                    strong,
+                    combine(&["save".to_string()], &[&abbreviation, &name]),
+                )
+            }
+            Self::SavingThrowProficiency(ability) => {
+                let saves = combine(&ability_names(ability), &["save", "st", "savingthrow"]);
+                let mut strong = combine(&saves, &["prof", "proficiency"]);
+                strong.push(format!("st{}prof", ability_names(ability)[1]));
+                (strong, vec![])
+            }
+            Self::Skill(skill) => {
+                let skill = &SKILLS[skill];
+                let strong = combine(&[normalize(skill.name)], &["", "mod", "modifier", "bonus"]);
+                (
+                    strong,
+                    skill.short_name.map(normalize).into_iter().collect(),
+                )
+            }
+            Self::SkillProficiency(skill) => (
+                combine(
+                    &[normalize(SKILLS[skill].name)],
+                    &["prof", "proficiency", "proficient"],
+                ),
+                vec![],
+            ),
+            Self::SkillExpertise(skill) => (
+                combine(&[normalize(SKILLS[skill].name)], &["expertise", "exp"]),
+                vec![],
+            ),
+            Self::ProficiencyBonus => (
+                names(&["proficiencybonus", "profbonus"]),
+                names(&["prof", "pb"]),
+            ),
+            Self::Initiative => (names(&["initiative"]), names(&["init"])),
+            Self::PassivePerception => (
+                names(&["passiveperception", "passivewisdomperception"]),
+                names(&["passive"]),
+            ),
+            Self::Level => (names(&["level", "characterlevel", "totallevel"]), vec![]),
+            Self::SpellcastingAbility => (names(&["spellcastingability", "spellability"]), vec![]),
+            Self::SpellSaveDc => (names(&["spellsavedc", "spelldc"]), names(&["savedc"])),
+            Self::SpellAttackBonus => (
+                names(&["spellattackbonus", "spellatkbonus", "spellattack"]),
+                names(&["spellatk"]),
+            ),
+            Self::CarryingCapacity => (
+                names(&["carryingcapacity", "carrycapacity"]),
+                names(&["capacity"]),
+            ),
+        }
+    }
+}
+
+/// A widely used sheet whose field names are known exactly.
+struct SheetLayout {
+    name: &'static str,
+    /// Fields that together identify the sheet.
+    signature: &'static [&'static str],
+    field_name: fn(Slot) -> Option<String>,
+}
+
+const KNOWN_LAYOUTS: [SheetLayout; 1] = [SheetLayout {
+    name: "D&D 5e Character Sheet (Wizards of the Coast)",
+    signature: &[
+        "STRmod",
+        "ST Strength",
+        "Check Box 11",
+        "Check Box 23",
+        "SleightofHand",
+        "ProfBonus",
+        "SpellAtkBonus 2",
+    ],
+    field_name: wotc_field_name,
+}];
+
+/// Field names of the official form-fillable 5e character sheet, whose proficiency checkboxes
+/// are numbered rather than named.
+fn wotc_field_name(slot: Slot) -> Option<String> {
+    const MODIFIERS: [&str; 6] = ["STRmod", "DEXmod", "CONmod", "INTmod", "WISmod", "CHamod"];
+    const SAVE_CHECKBOXES: [u8; 6] = [11, 18, 19, 20, 21, 22];
+    const SKILL_FIELDS: [&str; 18] = [
+        "Acrobatics",
+        "Animal",
+        "Arcana",
+        "Athletics",
+        "Deception",
+        "History",
+        "Insight",
+        "Intimidation",
+        "Investigation",
+        "Medicine",
+        "Nature",
+        "Perception",
+        "Performance",
+        "Persuasion",
+        "Religion",
+        "SleightofHand",
+        "Stealth",
+        "Survival",
+    ];
+
+    let name = match slot {
+        Slot::AbilityScore(ability) => ABILITIES[ability].abbreviation.to_string(),
+        Slot::AbilityModifier(ability) => MODIFIERS[ability].to_string(),
+        Slot::SavingThrow(ability) => format!("ST {}", ABILITIES[ability].name),
+        Slot::SavingThrowProficiency(ability) => format!("Check Box {}", SAVE_CHECKBOXES[ability]),
+        Slot::Skill(skill) => SKILL_FIELDS[skill].to_string(),
+        Slot::SkillProficiency(skill) => format!("Check Box {}", 23 + skill),
+        Slot::ProficiencyBonus => "ProfBonus".to_string(),
+        Slot::Initiative => "Initiative".to_string(),
+        Slot::PassivePerception => "Passive".to_string(),
+        Slot::SpellcastingAbility => "SpellcastingAbility 2".to_string(),
+        Slot::SpellSaveDc => "SpellSaveDC 2".to_string(),
+        Slot::SpellAttackBonus => "SpellAtkBonus 2".to_string(),
+        Slot::SkillExpertise(_) | Slot::Level | Slot::CarryingCapacity => return None,
+    };
+    Some(name)
+}
+
+/// Lowercases the name and drops everything but letters and digits, so `DEXmod `,
+/// `Dex Mod` and `dex_mod` compare equal.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+struct FieldMatch<'a> {
+    name: &'a str,
+    confidence: f32,
+}
+
+struct Matcher<'a> {
+    fields: HashMap<String, &'a FormField>,
+    layout: Option<&'static SheetLayout>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(form_fields: &'a [FormField]) -> Self {
+        let mut fields = HashMap::new();
+        for field in form_fields {
+            fields.entry(normalize(&field.name)).or_insert(field);
+        }
+        let layout = KNOWN_LAYOUTS.iter().find(|layout| {
+            layout
+                .signature
+                .iter()
+                .all(|name| fields.contains_key(&normalize(name)))
+        });
+        Self { fields, layout }
+    }
+
+    fn lookup(&self, normalized_name: &str, slot: Slot) -> Option<&'a str> {
+        self.fields
+            .get(normalized_name)
+            .filter(|field| slot.kind().accepts(field.kind))
+            .map(|field| field.name.as_str())
+    }
+
+    /// Finds the field playing `slot`: where the recognised layout puts it, otherwise the
+    /// first field named like it.
+    fn find(&self, slot: Slot) -> Option<FieldMatch<'a>> {
+        let known = self
+            .layout
+            .and_then(|layout| (layout.field_name)(slot))
+            .and_then(|name| self.lookup(&normalize(&name), slot))
+            .map(|name| FieldMatch {
+                name,
+                confidence: KNOWN_SHEET_CONFIDENCE,
+            });
+        if known.is_some() {
+            return known;
+        }
+
+        let (strong, weak) = slot.candidate_names();
+        let find_any = |names: Vec<String>, confidence| {
+            names.iter().find_map(|name| {
+                self.lookup(name, slot)
+                    .map(|name| FieldMatch { name, confidence })
+            })
+        };
+        find_any(strong, STRONG_NAME_CONFIDENCE).or_else(|| find_any(weak, WEAK_NAME_CONFIDENCE))
+    }
+
+    /// Builds an action from the fields `build` asks for; `None` when a required one is missing.
+    fn suggest(
+        &self,
+        build: impl FnOnce(&mut Roles<'_, 'a>) -> Option<CalculationAction>,
+    ) -> Option<ActionSuggestion> {
+        let mut roles = Roles {
+            matcher: self,
+            confidence: 1.0,
+        };
+        let action = build(&mut roles)?;
+        Some(ActionSuggestion {
+            action,
+            confidence: roles.confidence,
+        })
+    }
+}
+
+struct Roles<'m, 'a> {
+    matcher: &'m Matcher<'a>,
+    confidence: f32,
+}
+
+impl Roles<'_, '_> {
+    fn required(&mut self, slot: Slot) -> Option<String> {
+        let found = self.matcher.find(slot)?;
+        self.confidence = self.confidence.min(found.confidence);
+        Some(found.name.to_string())
+    }
+
+    fn optional(&mut self, slot: Slot) -> Option<String> {
+        self.matcher.find(slot).map(|found| found.name.to_string())
+    }
+}
+
+/// Proposes calculation actions for a 5e sheet by recognising the official sheet and matching
+/// field names such as `STR`, `STRmod` or `Acrobatics` to the roles of every action type.
+///
+/// An action is only proposed when all its required fields are found, and no two proposals
+/// write to the same field.
+pub fn suggest_actions(form_fields: &[FormField]) -> MappingSuggestions {
+    let matcher = Matcher::new(form_fields);
+    let mut suggestions = Vec::new();
+
+    for ability in 0..ABILITIES.len() {
+        suggestions.extend(matcher.suggest(|r| {
+            Some(CalculationAction::AbilityModifier {
+                score_field_name: r.required(Slot::AbilityScore(ability))?,
+                modifier_field_name: r.required(Slot::AbilityModifier(ability))?,
+            })
+        }));
+    }
+    for ability in 0..ABILITIES.len() {
+        suggestions.extend(matcher.suggest(|r| {
+            Some(CalculationAction::SavingThrowModifier {
+                ability_modifier_field_name: r.required(Slot::AbilityModifier(ability))?,
+                proficiency_field_name: r.required(Slot::SavingThrowProficiency(ability))?,
+                proficiency_bonus_field_name: r.required(Slot::ProficiencyBonus)?,
+                saving_throw_modifier_field_name: r.required(Slot::SavingThrow(ability))?,
+            })
+        }));
+    }
+    for (index, skill) in SKILLS.iter().enumerate() {
+        suggestions.extend(matcher.suggest(|r| {
+            Some(CalculationAction::SkillModifier {
+                ability_modifier_field_name: r.required(Slot::AbilityModifier(skill.ability))?,
+                proficiency_field_name: r.required(Slot::SkillProficiency(index))?,
+                expertise_field_name: r.optional(Slot::SkillExpertise(index)),
+                half_prof_field_name: None,
+                proficiency_bonus_field_name: r.required(Slot::ProficiencyBonus)?,
+                skill_modifier_field_name: r.required(Slot::Skill(index))?,
+            })
+        }));
+    }
+    suggestions.extend(matcher.suggest(|r| {
+        Some(CalculationAction::PassiveScore {
+            skill_modifier_field_name: r.required(Slot::Skill(PERCEPTION))?,
+            bonus_field_name: None,
+            advantage_field_name: None,
+            disadvantage_field_name: None,
+            passive_score_field_name: r.required(Slot::PassivePerception)?,
+        })
+    }));
+    suggestions.extend(matcher.suggest(|r| {
+        Some(CalculationAction::Initiative {
+            dex_modifier_field_name: r.required(Slot::AbilityModifier(DEX))?,
+            misc_bonus_field_names: vec![],
+            jack_of_all_trades_field_name: None,
+            proficiency_bonus_field_name: None,
+            initiative_field_name: r.required(Slot::Initiative)?,
+        })
+    }));
+    suggestions.extend(matcher.suggest(|r| {
+        Some(CalculationAction::ProficiencyBonus {
+            level_field_name: r.required(Slot::Level)?,
+            multiclass_level_field_names: vec![],
+            proficiency_bonus_field_name: r.required(Slot::ProficiencyBonus)?,
+        })
+    }));
+    suggestions.extend(matcher.suggest(|r| {
+        Some(CalculationAction::SpellSaveDc {
+            spellcasting_ability_field_name: r.required(Slot::SpellcastingAbility)?,
+            intelligence_modifier_field_name: r.required(Slot::AbilityModifier(INT))?,
+            wisdom_modifier_field_name: r.required(Slot::AbilityModifier(WIS))?,
+            charisma_modifier_field_name: r.required(Slot::AbilityModifier(CHA))?,
+            proficiency_bonus_field_name: r.required(Slot::ProficiencyBonus)?,
+            misc_bonus_field_name: None,
+            spell_save_dc_field_name: r.required(Slot::SpellSaveDc)?,
+        })
+    }));
+    suggestions.extend(matcher.suggest(|r| {
+        Some(CalculationAction::SpellAttackBonus {
+            spellcasting_ability_field_name: r.required(Slot::SpellcastingAbility)?,
+            intelligence_modifier_field_name: r.required(Slot::AbilityModifier(INT))?,
+            wisdom_modifier_field_name: r.required(Slot::AbilityModifier(WIS))?,
+            charisma_modifier_field_name: r.required(Slot::AbilityModifier(CHA))?,
+            proficiency_bonus_field_name: r.required(Slot::ProficiencyBonus)?,
+            misc_bonus_field_name: None,
+            spell_attack_bonus_field_name: r.required(Slot::SpellAttackBonus)?,
+        })
+    }));
+    suggestions.extend(matcher.suggest(|r| {
+        Some(CalculationAction::CarryingCapacity {
+            strength_score_field_name: r.required(Slot::AbilityScore(STR))?,
+            size_field_name: None,
+            carrying_capacity_field_name: r.required(Slot::CarryingCapacity)?,
+        })
+    }));
+
+    // Loose name matches can land two proposals on the same field; the earlier one wins
+    let mut targets = HashSet::new();
+    suggestions.retain(|suggestion| targets.insert(suggestion.action.target_field().to_string()));
+
+    MappingSuggestions {
+        sheet_name: matcher.layout.map(|layout| layout.name),
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::driven::FormFieldKind;
+    use pretty_assertions::assert_eq;
+
+    fn fields(names: &[(&str, FormFieldKind)]) -> Vec<FormField> {
+        names
+            .iter()
+            .map(|(name, kind)| FormField::new(*name, *kind))
+            .collect()
+    }
+
+    fn find<'a>(suggestions: &'a MappingSuggestions, target: &str) -> Option<&'a ActionSuggestion> {
+        suggestions
+            .suggestions
+            .iter()
+            .find(|suggestion| suggestion.action.target_field() == target)
+    }
+
+    #[test]
+    fn test_should_map_numbered_checkboxes_of_the_official_sheet() {
+        let mut sheet = fields(&[
+            ("STR", FormFieldKind::Text),
+            ("STRmod", FormFieldKind::Text),
+            ("ST Strength", FormFieldKind::Text),
+            ("WISmod", FormFieldKind::Text),
+            ("Perception ", FormFieldKind::Text),
+            ("Passive", FormFieldKind::Text),
+            ("ProfBonus", FormFieldKind::Text),
+            ("SleightofHand", FormFieldKind::Text),
+            ("SpellAtkBonus 2", FormFieldKind::Text),
+        ]);
+        for number in [11, 23, 34] {
+            sheet.push(FormField::new(
+                format!("Check Box {number}"),
+                FormFieldKind::Checkbox,
+            ));
+        }
+
+        let actual = suggest_actions(&sheet);
+
+        assert_eq!(
+            actual.sheet_name,
+            Some("D&D 5e Character Sheet (Wizards of the Coast)")
+        );
+        assert_eq!(
+            find(&actual, "ST Strength"),
+            Some(&ActionSuggestion {
+                action: CalculationAction::saving_throw_modifier(
+                    "STRmod",
+                    "Check Box 11",
+                    "ProfBonus",
+                    "ST Strength"
+                ),
+                confidence: KNOWN_SHEET_CONFIDENCE,
+            })
+        );
+        assert_eq!(
+            find(&actual, "Perception ").map(|s| &s.action),
+            Some(&CalculationAction::skill_modifier(
+                "WISmod",
+                "Check Box 34",
+                None::<&str>,
+                None::<&str>,
+                "ProfBonus",
+                "Perception "
+            ))
+        );
+        assert!(find(&actual, "Passive").is_some());
+        // Check Box 23 is Acrobatics proficiency, but the sheet lacks the DEX modifier
+        assert!(find(&actual, "Acrobatics").is_none());
+    }
+
+    #[test]
+    fn test_should_match_descriptive_field_names_of_other_sheets() {
+        let sheet = fields(&[
+            ("Strength", FormFieldKind::Text),
+            ("Strength Modifier", FormFieldKind::Text),
+            ("Dex Mod", FormFieldKind::Text),
+            ("Stealth", FormFieldKind::Text),
+            ("Stealth Prof", FormFieldKind::Checkbox),
+            ("Stealth Expertise", FormFieldKind::Checkbox),
+            ("Proficiency Bonus", FormFieldKind::Text),
+            ("Init", FormFieldKind::Text),
+            ("Level", FormFieldKind::Text),
+            ("Carrying Capacity", FormFieldKind::Text),
+        ]);
+
+        let actual = suggest_actions(&sheet);
+
+        assert_eq!(actual.sheet_name, None);
+        let targets: Vec<_> = actual
+            .suggestions
+            .iter()
+            .map(|s| (s.action.target_field(), s.confidence))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("Strength Modifier", STRONG_NAME_CONFIDENCE),
+                ("Stealth", STRONG_NAME_CONFIDENCE),
+                ("Init", WEAK_NAME_CONFIDENCE),
+                ("Proficiency Bonus", STRONG_NAME_CONFIDENCE),
+                ("Carrying Capacity", STRONG_NAME_CONFIDENCE),
+            ]
+        );
+        assert_eq!(
+            find(&actual, "Stealth").map(|s| &s.action),
+            Some(&CalculationAction::skill_modifier(
+                "Dex Mod",
+                "Stealth Prof",
+                Some("Stealth Expertise"),
+                None::<&str>,
+                "Proficiency Bonus",
+                "Stealth"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_should_ignore_fields_of_the_wrong_kind() {
+        let sheet = fields(&[
+            ("DEXmod", FormFieldKind::Text),
+            ("Initiative", FormFieldKind::Checkbox),
+        ]);
+
+        assert_eq!(suggest_actions(&sheet), MappingSuggestions::default());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::action::{ActionTypeMetadata, CalculationAction};
+use crate::error::ActionError;
+
+macro_rules! helpers_js {
+    ($file:literal) => {
+        concat!(
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/js/common-helpers.js")),
+            "\n",
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/js/", $file)),
+        )
+    };
+}
+
+/// A tabletop rule set whose sheets get their own set of calculation actions and the
+/// document-level helper JS those actions call into.
+pub trait GameSystem: Send + Sync {
+    /// Identifier used in routes, e.g. `dnd5e`.
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name shown to users.
+    fn name(&self) -> &'static str;
+
+    /// Labels of the [`CalculationAction`] variants available on this system's sheets.
+    fn action_labels(&self) -> &'static [&'static str];
+
+    /// Document-level JS defining every helper the system's actions call.
+    fn helpers_js(&self) -> &'static str;
+
+    fn supports(&self, action_label: &str) -> bool {
+        self.action_labels().contains(&action_label)
+    }
+
+    /// Returns the catalog entries of the actions this system supports.
+    fn action_type_catalog(&self) -> Vec<ActionTypeMetadata> {
+        CalculationAction::action_type_catalog()
+            .into_iter()
+            .filter(|meta| self.supports(&meta.action_label))
+            .collect()
+    }
+}
+
+/// Dungeons & Dragons 5th edition (2014 rules).
+pub struct Dnd5e;
+
+impl GameSystem for Dnd5e {
+    fn id(&self) -> &'static str {
+        "dnd5e"
+    }
+
+    fn name(&self) -> &'static str {
+        "D&D 5th Edition"
+    }
+
+    fn action_labels(&self) -> &'static [&'static str] {
+        &[
+            "AbilityModifier",
+            "SkillModifier",
+            "SavingThrowModifier",
+            "PassiveScore",
+            "ProficiencyBonus",
+            "Initiative",
+            "ArmorClass",
+            "SpellSaveDc",
+            "SpellAttackBonus",
+            "WeaponAttackBonus",
+            "WeaponDamageBonus",
+            "CarryingCapacity",
+            "EncumbranceStatus",
+            "SumFields",
+            "Formula",
+        ]
+    }
+
+    fn helpers_js(&self) -> &'static str {
+        helpers_js!("dnd-helpers.js")
+    }
+}
+
+/// Dungeons & Dragons 5th edition with the 2024 rules, which drop variant encumbrance.
+pub struct Dnd5e2024;
+
+impl GameSystem for Dnd5e2024 {
+    fn id(&self) -> &'static str {
+        "dnd5e-2024"
+    }
+
+    fn name(&self) -> &'static str {
+        "D&D 5th Edition (2024)"
+    }
+
+    fn action_labels(&self) -> &'static [&'static str] {
+        &[
+            "AbilityModifier",
+            "SkillModifier",
+            "SavingThrowModifier",
+            "PassiveScore",
+            "ProficiencyBonus",
+            "Initiative",
+            "ArmorClass",
+            "SpellSaveDc",
+            "SpellAttackBonus",
+            "WeaponAttackBonus",
+            "WeaponDamageBonus",
+            "CarryingCapacity",
+            "SumFields",
+            "Formula",
+        ]
+    }
+
+    fn helpers_js(&self) -> &'static str {
+        helpers_js!("dnd-helpers.js")
+    }
+}
+
+/// Pathfinder 2nd edition.
+pub struct Pathfinder2e;
+
+impl GameSystem for Pathfinder2e {
+    fn id(&self) -> &'static str {
+        "pf2e"
+    }
+
+    fn name(&self) -> &'static str {
+        "Pathfinder 2nd Edition"
+    }
+
+    fn action_labels(&self) -> &'static [&'static str] {
+        &[
+            "AbilityModifier",
+            "ProficiencyRankModifier",
+            "SumFields",
+            "Formula",
+        ]
+    }
+
+    fn helpers_js(&self) -> &'static str {
+        helpers_js!("pf2e-helpers.js")
+    }
+}
+
+/// Every registered game system, in the order they are listed to users.
+pub fn game_systems() -> [&'static dyn GameSystem; 3] {
+    [&Dnd5e, &Dnd5e2024, &Pathfinder2e]
+}
+
+/// Looks up a registered game system by its route identifier.
+pub fn find_game_system(id: &str) -> Result<&'static dyn GameSystem, ActionError> {
+    game_systems()
+        .into_iter()
+        .find(|system| system.id() == id)
+        .ok_or_else(|| ActionError::UnknownGameSystem(id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_game_system() {
+        assert_eq!(
+            find_game_system("pf2e").unwrap().name(),
+            "Pathfinder 2nd Edition"
+        );
+        assert_eq!(
+            find_game_system("dnd4e").err(),
+            Some(ActionError::UnknownGameSystem("dnd4e".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_every_supported_action_is_in_the_catalog() {
+        for system in game_systems() {
+            assert_eq!(
+                system.action_type_catalog().len(),
+                system.action_labels().len(),
+                "{}",
+                system.id()
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalog_is_limited_to_the_system() {
+        let ids: Vec<_> = Pathfinder2e
+            .action_type_catalog()
+            .into_iter()
+            .map(|meta| meta.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "ability-modifier",
+                "proficiency-rank-modifier",
+                "sum-fields",
+                "formula"
+            ]
+        );
+        assert!(!Dnd5e2024.supports("EncumbranceStatus"));
+        assert!(Dnd5e.supports("EncumbranceStatus"));
+    }
+
+    #[test]
+    fn test_helpers_js_includes_common_helpers() {
+        for system in game_systems() {
+            assert!(
+                system
+                    .helpers_js()
+                    .contains("function getNumberValueFromField(")
+            );
+        }
+        assert!(
+            Dnd5e
+                .helpers_js()
+                .contains("function calculateSkillFromFields(")
+        );
+        assert!(
+            Pathfinder2e
+                .helpers_js()
+                .contains("function calculateRankedModifierFromFields(")
+        );
+        assert!(
+            !Pathfinder2e
+                .helpers_js()
+                .contains("function calculateSkillFromFields(")
+        );
+    }
+}
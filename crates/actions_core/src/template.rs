@@ -0,0 +1,65 @@
+use crate::ports::driven::FormField;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+/// A named set of actions saved from a mapped sheet, to be applied to fresh copies of the same
+/// sheet. Templates outlive the sheet they were created from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingTemplate {
+    pub id: Uuid,
+    pub name: String,
+    /// Identifier of the game system whose actions the template holds, e.g. `dnd5e`.
+    pub system: String,
+    /// [`fingerprint`] of the form fields of the sheet the template was created from.
+    pub fingerprint: String,
+    /// Action mappings in the stored format of [`crate::action::AttachedAction::mapping`].
+    pub actions: Vec<serde_json::Value>,
+}
+
+/// Identifies the structure of a sheet's AcroForm: the hex SHA-256 of its sorted field names and
+/// kinds. Copies of the same sheet share a fingerprint whatever their filled-in values are.
+pub fn fingerprint(form_fields: &[FormField]) -> String {
+    let fields: BTreeSet<String> = form_fields
+        .iter()
+        // Variant names rather than the display form, which is worded for users
+        .map(|field| format!("{}\u{1f}{:?}", field.name, field.kind))
+        .collect();
+
+    let mut hasher = Sha256::new();
+    for field in fields {
+        hasher.update(field.as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::driven::FormFieldKind;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn test_fingerprint_ignores_field_order_but_not_kinds() {
+        let str_field = FormField::new("STR", FormFieldKind::Text);
+        let proficiency = FormField::new("Check Box 11", FormFieldKind::Checkbox);
+
+        let actual = fingerprint(&[str_field.clone(), proficiency.clone()]);
+
+        assert_eq!(actual.len(), 64);
+        assert_eq!(actual, fingerprint(&[proficiency, str_field.clone()]));
+        assert_ne!(
+            actual,
+            fingerprint(&[
+                str_field.clone(),
+                FormField::new("Check Box 11", FormFieldKind::Text)
+            ])
+        );
+        assert_ne!(actual, fingerprint(&[str_field]));
+    }
+}
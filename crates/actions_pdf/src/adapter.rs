@@ -1,11 +1,17 @@
 use actions_core::error::ActionError;
-use actions_core::ports::driven::ActionPdfPort;
+use actions_core::ports::driven::{
+    ActionPdfPort, CalculationScript, ComputedValue, FormField, FormFieldKind,
+};
 use async_trait::async_trait;
-use common_pdf::find_form_field_by_name;
-use lopdf::{Dictionary, Document, Object, ObjectId, StringFormat, dictionary};
-use std::collections::BTreeSet;
+use common_pdf::{decode_field_value, find_form_field_by_name, terminal_fields};
+use lopdf::content::{Content, Operation};
+use lopdf::{Document, Object, ObjectId, Stream, StringFormat, dictionary, text_string};
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
-use tracing::{error, instrument};
+use tracing::{debug, error, instrument, warn};
+
+/// Name-tree key under which the document-level helper script is stored.
+const HELPERS_JS_NAME: &[u8] = b"HelpersJS";
 
 pub struct PdfActionAdapter;
 
@@ -13,248 +19,815 @@ pub struct PdfActionAdapter;
 impl ActionPdfPort for PdfActionAdapter {
     #[instrument(name = "pdf.add_doc_level_js", skip(self, js), fields(path = %sheet_path.display()))]
     fn add_doc_level_js(&self, js: &str, sheet_path: &Path) -> Result<(), ActionError> {
-        let mut doc = Document::load(sheet_path).map_err(|e| {
-            error!(error = ?e, "failed to load PDF sheet");
-            ActionError::LoadPdfError
-        })?;
+        let mut doc = load_document(sheet_path)?;
 
-        let catalog_id = doc
-            .trailer
-            .get(b"Root")
-            .and_then(|root| root.as_reference())
-            .map_err(|e| {
-                let msg = "failed to get Root reference from PDF trailer";
-                error!(error = ?e, msg);
-                ActionError::InvalidPdfSheet(msg.to_string())
-            })?;
+        if !install_helper_js(&mut doc, js)? {
+            debug!("document-level helper JS already up to date");
+            return Ok(());
+        }
 
-        let mut js_dict = Dictionary::new();
-        js_dict.set("S", Object::Name(b"JavaScript".to_vec()));
-        js_dict.set(
-            "JS",
-            Object::String(js.as_bytes().to_vec(), lopdf::StringFormat::Literal),
-        );
+        save_document(&mut doc, sheet_path)
+    }
+
+    #[instrument(name = "pdf.attach_calculation_js", skip(self, js, sheet_path, calculation_order), fields(path = %sheet_path.display(), target_field))]
+    fn attach_calculation_js(
+        &self,
+        js: &str,
+        sheet_path: &Path,
+        target_field: &str,
+        calculation_order: &[String],
+    ) -> Result<(), ActionError> {
+        let mut doc = load_document(sheet_path)?;
+
+        attach_field_calculation_js(&mut doc, js, target_field)?;
+        apply_calculation_order(&mut doc, calculation_order)?;
+
+        save_document(&mut doc, sheet_path)
+    }
+
+    #[instrument(name = "pdf.attach_calculation_scripts", skip(self, helpers_js, scripts, calculation_order), fields(path = %sheet_path.display(), scripts = scripts.len()))]
+    fn attach_calculation_scripts(
+        &self,
+        helpers_js: &str,
+        scripts: &[CalculationScript],
+        calculation_order: &[String],
+        sheet_path: &Path,
+    ) -> Result<Vec<Result<(), ActionError>>, ActionError> {
+        let mut doc = load_document(sheet_path)?;
+
+        install_helper_js(&mut doc, helpers_js)?;
 
-        let mut js_name_tree = Dictionary::new();
-        let js_action_id = doc.add_object(Object::Dictionary(js_dict));
-        js_name_tree.set(
-            "Names",
-            Object::Array(vec![
-                Object::String(b"HelpersJS".to_vec(), lopdf::StringFormat::Literal),
-                Object::Reference(js_action_id),
-            ]),
+        let results: Vec<Result<(), ActionError>> = scripts
+            .iter()
+            .map(|script| attach_field_calculation_js(&mut doc, &script.js, &script.target_field))
+            .collect();
+
+        apply_calculation_order(&mut doc, calculation_order)?;
+
+        debug!(
+            attached = results.iter().filter(|r| r.is_ok()).count(),
+            "applied calculation scripts to in-memory document"
         );
 
-        let js_name_tree_id = doc.add_object(Object::Dictionary(js_name_tree));
+        save_document(&mut doc, sheet_path)?;
+
+        Ok(results)
+    }
+
+    #[instrument(name = "pdf.list_form_fields", skip(self, sheet_path), fields(path = %sheet_path.display()))]
+    fn list_form_fields(&self, sheet_path: &Path) -> Result<Vec<FormField>, ActionError> {
+        let doc = load_document(sheet_path)?;
+
+        let fields: Vec<FormField> = terminal_form_fields(&doc)?
+            .into_iter()
+            .map(|(_, field)| field)
+            .collect();
+
+        debug!(fields = fields.len(), "listed form fields");
+
+        Ok(fields)
+    }
+
+    #[instrument(name = "pdf.read_field_values", skip(self, sheet_path), fields(path = %sheet_path.display()))]
+    fn read_field_values(&self, sheet_path: &Path) -> Result<HashMap<String, String>, ActionError> {
+        let doc = load_document(sheet_path)?;
+
+        let values: HashMap<String, String> = terminal_form_fields(&doc)?
+            .into_iter()
+            .filter_map(|(field_id, field)| {
+                let value = doc
+                    .get_dictionary(field_id)
+                    .and_then(|dict| dict.get_deref(b"V", &doc))
+                    .ok()
+                    .and_then(decode_field_value)?;
+                Some((field.name, value))
+            })
+            .collect();
+
+        debug!(values = values.len(), "read form field values");
+
+        Ok(values)
+    }
+
+    #[instrument(name = "pdf.write_computed_values", skip(self, sheet_path, values), fields(path = %sheet_path.display(), values = values.len(), keep_scripts))]
+    fn write_computed_values(
+        &self,
+        sheet_path: &Path,
+        values: &[ComputedValue],
+        keep_scripts: bool,
+    ) -> Result<(), ActionError> {
+        let mut doc = load_document(sheet_path)?;
+
+        let (acroform_id, _) = find_acroform_ids(&doc)?;
+        let fields = terminal_form_fields(&doc)?;
+
+        for computed in values {
+            let Some(&(field_id, ref field)) = fields
+                .iter()
+                .find(|(_, field)| field.name == computed.target_field)
+            else {
+                warn!(target_field = %computed.target_field, "computed value not written to missing field");
+                continue;
+            };
+
+            // A button's value selects one of its appearance states and cannot hold a number
+            if !matches!(field.kind, FormFieldKind::Text | FormFieldKind::Choice) {
+                warn!(target_field = %computed.target_field, kind = ?field.kind, "computed value not written to non-text field");
+                continue;
+            }
+
+            write_field_value(&mut doc, acroform_id, field_id, &computed.value)?;
+
+            if !keep_scripts {
+                remove_calculation_js(&mut doc, acroform_id, field_id, &computed.target_field)?;
+            }
+        }
+
+        debug!(values = values.len(), keep_scripts, "wrote computed values");
+
+        save_document(&mut doc, sheet_path)
+    }
+
+    #[instrument(name = "pdf.detach_calculation_js", skip(self, sheet_path), fields(path = %sheet_path.display(), target_field))]
+    fn detach_calculation_js(
+        &self,
+        sheet_path: &Path,
+        target_field: &str,
+    ) -> Result<(), ActionError> {
+        let mut doc = load_document(sheet_path)?;
+
+        let (acroform_id, fields_array_id) = find_acroform_ids(&doc)?;
 
-        let catalog = doc
-            .get_object_mut(catalog_id)
+        let target_field_id = find_form_field_by_name(&doc, fields_array_id, target_field)
+            .ok_or(ActionError::FieldNotFound(target_field.to_string()))?;
+
+        remove_calculation_js(&mut doc, acroform_id, target_field_id, target_field)?;
+
+        save_document(&mut doc, sheet_path)
+    }
+}
+
+/// Removes the calculation script (`/AA /C`) of a field and drops the field from the AcroForm
+/// calculation order (`/CO`).
+fn remove_calculation_js(
+    doc: &mut Document,
+    acroform_id: ObjectId,
+    target_field_id: ObjectId,
+    target_field: &str,
+) -> Result<(), ActionError> {
+    // Remove /AA /C from the target field, dropping /AA entirely once it is empty
+    let removed_js_id = {
+        let field_dict = doc
+            .get_object_mut(target_field_id)
             .and_then(|obj| obj.as_dict_mut())
             .map_err(|e| {
-                let msg = "failed to get Catalog object from PDF trailer";
+                let msg = format!("field \"{}\" is not a dictionary", target_field);
                 error!(error = ?e, msg);
-                ActionError::InvalidPdfSheet(msg.to_string())
+                ActionError::InvalidPdfSheet(msg)
             })?;
 
-        // Check if Names dictionary already exists
-        let names_dict_id = if let Ok(names_ref) = catalog.get(b"Names") {
-            if let Ok(existing_names_id) = names_ref.as_reference() {
-                // Update the existing Names dictionary
-                let names_dict = doc
-                    .get_object_mut(existing_names_id)
-                    .and_then(|obj| obj.as_dict_mut())
-                    .map_err(|e| {
-                        let msg = "failed to get Names dictionary from PDF trailer";
-                        error!(error = ?e, msg);
-                        ActionError::InvalidPdfSheet(msg.to_string())
-                    })?;
-                names_dict.set("JavaScript", Object::Reference(js_name_tree_id));
-                existing_names_id
-            } else {
-                // Create a new Names dictionary
-                let mut names_dict = Dictionary::new();
-                names_dict.set("JavaScript", Object::Reference(js_name_tree_id));
-                doc.add_object(Object::Dictionary(names_dict))
-            }
-        } else {
-            // Create a new Names dictionary
-            let mut names_dict = Dictionary::new();
-            names_dict.set("JavaScript", Object::Reference(js_name_tree_id));
-            doc.add_object(Object::Dictionary(names_dict))
+        let (removed_js, aa_is_empty) = match field_dict.get_mut(b"AA") {
+            Ok(Object::Dictionary(aa_dict)) => (aa_dict.remove(b"C"), aa_dict.is_empty()),
+            _ => (None, false),
         };
+        if aa_is_empty {
+            field_dict.remove(b"AA");
+        }
+        removed_js.and_then(|js| js.as_reference().ok())
+    };
+    remove_replaced_objects(doc, removed_js_id);
 
-        // Update catalog with Names dictionary reference
-        let catalog = doc
-            .get_object_mut(catalog_id)
+    // Drop the target field from the /AcroForm /CO calculation order
+    {
+        let acroform_dict = doc
+            .get_object_mut(acroform_id)
             .and_then(|obj| obj.as_dict_mut())
             .map_err(|e| {
-                let msg = "failed to get Catalog object from PDF trailer";
+                let msg = "failed to get AcroForm dictionary";
                 error!(error = ?e, msg);
                 ActionError::InvalidPdfSheet(msg.to_string())
             })?;
-        catalog.set("Names", Object::Reference(names_dict_id));
 
-        doc.save(sheet_path).map_err(|e| {
-            error!(error = ?e, "failed to save PDF");
-            ActionError::SavePdfError
-        })?;
+        let remaining: Option<Vec<Object>> = acroform_dict
+            .get(b"CO")
+            .and_then(Object::as_array)
+            .ok()
+            .map(|co_arr| {
+                co_arr
+                    .iter()
+                    .filter(|field| field.as_reference().ok() != Some(target_field_id))
+                    .cloned()
+                    .collect()
+            });
 
-        Ok(())
+        match remaining {
+            Some(co_arr) if co_arr.is_empty() => {
+                acroform_dict.remove(b"CO");
+            }
+            Some(co_arr) => acroform_dict.set(b"CO", Object::Array(co_arr)),
+            None => {}
+        }
     }
 
-    #[instrument(name = "pdf.attach_calculation_js", skip(self, js, sheet_path), fields(path = %sheet_path.display(), target_field))]
-    fn attach_calculation_js(
-        &self,
-        js: &str,
-        sheet_path: &Path,
-        target_field: &str,
-    ) -> Result<(), ActionError> {
-        let mut doc = Document::load(sheet_path).map_err(|e| {
-            error!(error = ?e, "failed to load PDF sheet");
-            ActionError::LoadPdfError
-        })?;
+    Ok(())
+}
 
-        let catalog_id = doc
-            .trailer
-            .get(b"Root")
-            .and_then(|root| root.as_reference())
-            .map_err(|e| {
-                let msg = "failed to get Root reference from PDF trailer";
-                error!(error = ?e, msg);
-                ActionError::InvalidPdfSheet(msg.to_string())
-            })?;
+/// Sets the value of a text or choice field and replaces the normal appearance of each of its
+/// widgets with one showing the value. The text is laid out from the field's default appearance
+/// (`/DA`) and quadding (`/Q`), falling back to the AcroForm's.
+fn write_field_value(
+    doc: &mut Document,
+    acroform_id: ObjectId,
+    field_id: ObjectId,
+    value: &str,
+) -> Result<(), ActionError> {
+    let acroform = doc.get_dictionary(acroform_id).map_err(|e| {
+        let msg = "failed to get AcroForm dictionary";
+        error!(error = ?e, msg);
+        ActionError::InvalidPdfSheet(msg.to_string())
+    })?;
+    let field = doc.get_dictionary(field_id).map_err(|e| {
+        let msg = format!("field {:?} is not a dictionary", field_id);
+        error!(error = ?e, msg);
+        ActionError::InvalidPdfSheet(msg)
+    })?;
 
-        let acroform_id = {
-            let catalog_dict = doc
-                .get_object(catalog_id)
-                .and_then(|obj| obj.as_dict())
-                .map_err(|e| {
-                    let msg = "failed to get Catalog dictionary";
-                    error!(error = ?e, msg);
-                    ActionError::InvalidPdfSheet(msg.to_string())
-                })?;
+    let default_appearance = field
+        .get(b"DA")
+        .or_else(|_| acroform.get(b"DA"))
+        .and_then(Object::as_str)
+        .map(<[u8]>::to_vec)
+        .unwrap_or_else(|_| DEFAULT_APPEARANCE.to_vec());
+    let quadding = field
+        .get(b"Q")
+        .or_else(|_| acroform.get(b"Q"))
+        .and_then(Object::as_i64)
+        .unwrap_or(0);
+    let resources = acroform.get(b"DR").ok().cloned();
 
-            catalog_dict
-                .get(b"AcroForm")
-                .and_then(|obj| obj.as_reference())
-                .map_err(|e| {
-                    let msg = "failed to get AcroForm reference";
-                    error!(error = ?e, msg);
-                    ActionError::InvalidPdfSheet(msg.to_string())
-                })?
-        };
+    // A field with a single widget may be merged with it; otherwise its kids are the widgets
+    let widget_ids: Vec<ObjectId> = if field.has(b"Rect") {
+        vec![field_id]
+    } else {
+        field
+            .get(b"Kids")
+            .and_then(Object::as_array)
+            .map(|kids| {
+                kids.iter()
+                    .filter_map(|kid| kid.as_reference().ok())
+                    .filter(|kid_id| doc.get_dictionary(*kid_id).is_ok_and(|kid| !kid.has(b"T")))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
 
-        let fields_array_id = {
-            let acroform_dict = doc
-                .get_object(acroform_id)
-                .and_then(|obj| obj.as_dict())
-                .map_err(|e| {
-                    let msg = "failed to get AcroForm dictionary";
-                    error!(error = ?e, msg);
-                    ActionError::InvalidPdfSheet(msg.to_string())
-                })?;
+    for widget_id in widget_ids {
+        let Some((width, height)) = doc
+            .get_dictionary(widget_id)
+            .ok()
+            .and_then(|widget| widget_size(widget.get(b"Rect").ok()?))
+        else {
+            continue;
+        };
 
-            acroform_dict
-                .get(b"Fields")
-                .and_then(|obj| obj.as_reference())
-                .map_err(|e| {
-                    let msg = "failed to get Fields array reference from AcroForm";
-                    error!(error = ?e, msg);
-                    ActionError::InvalidPdfSheet(msg.to_string())
-                })?
+        let content = text_appearance(&default_appearance, quadding, width, height, value)?;
+        let mut stream_dict = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), width.into(), height.into()],
         };
+        if let Some(resources) = &resources {
+            stream_dict.set("Resources", resources.clone());
+        }
+        let appearance_id = doc.add_object(Stream::new(stream_dict, content));
 
-        // Find the target field object by its /T (partial name), searching the hierarchy
-        let target_field_id = find_form_field_by_name(&doc, fields_array_id, target_field)
-            .ok_or(ActionError::FieldNotFound(target_field.to_string()))?;
+        if let Ok(widget) = doc.get_dictionary_mut(widget_id) {
+            widget.set("AP", dictionary! { "N" => appearance_id });
+        }
+    }
 
-        let js_action_id = {
-            let js_action = dictionary! {
-                b"S" => Object::Name(b"JavaScript".to_vec()),
-                b"JS" => Object::String(js.as_bytes().to_vec(), StringFormat::Literal),
-            };
-            doc.add_object(Object::Dictionary(js_action))
-        };
+    let field = doc.get_dictionary_mut(field_id).map_err(|e| {
+        let msg = format!("field {:?} is not a dictionary", field_id);
+        error!(error = ?e, msg);
+        ActionError::InvalidPdfSheet(msg)
+    })?;
+    field.set("V", text_string(value));
 
-        {
-            let field_obj = doc.get_object_mut(target_field_id).map_err(|e| {
-                let msg = format!(
-                    "failed to get field dictionary for field \"{}\"",
-                    target_field
-                );
-                error!(error = ?e, msg);
-                ActionError::FieldNotFound(msg)
-            })?;
-            let field_dict = field_obj.as_dict_mut().map_err(|e| {
-                let msg = format!("field \"{}\" is not a dictionary", target_field);
-                error!(error = ?e, msg);
-                ActionError::InvalidPdfSheet(msg.to_string())
-            })?;
+    Ok(())
+}
+
+/// Default appearance used when neither the field nor the AcroForm declares one.
+const DEFAULT_APPEARANCE: &[u8] = b"/Helv 0 Tf 0 g";
+
+/// Width and height of a widget's `/Rect`.
+fn widget_size(rect: &Object) -> Option<(f32, f32)> {
+    let rect: Vec<f32> = rect
+        .as_array()
+        .ok()?
+        .iter()
+        .map(Object::as_float)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match rect.as_slice() {
+        [x1, y1, x2, y2] => Some(((x2 - x1).abs(), (y2 - y1).abs())),
+        _ => None,
+    }
+}
+
+/// Builds the content of a single-line text field appearance showing `value`. A font size of 0
+/// in the default appearance means auto-size, which is approximated from the widget height.
+/// Without font metrics, glyphs are assumed to be half as wide as the font size.
+fn text_appearance(
+    default_appearance: &[u8],
+    quadding: i64,
+    width: f32,
+    height: f32,
+    value: &str,
+) -> Result<Vec<u8>, ActionError> {
+    const PADDING: f32 = 2.0;
+
+    let mut appearance_ops = Content::decode(default_appearance)
+        .or_else(|_| Content::decode(DEFAULT_APPEARANCE))
+        .map(|content| content.operations)
+        .unwrap_or_default();
+    let mut font_size = 12.0;
+    for operation in &mut appearance_ops {
+        if operation.operator == "Tf" && operation.operands.len() == 2 {
+            font_size = operation.operands[1].as_float().unwrap_or(0.0);
+            if font_size <= 0.0 {
+                font_size = (height - 2.0 * PADDING).clamp(4.0, 12.0);
+                operation.operands[1] = font_size.into();
+            }
+        }
+    }
+
+    let text_width = value.chars().count() as f32 * font_size * 0.5;
+    let x = match quadding {
+        1 => (width - text_width) / 2.0,
+        2 => width - PADDING - text_width,
+        _ => PADDING,
+    }
+    .max(PADDING);
+    let y = (height - font_size * 0.7) / 2.0;
+
+    let mut operations = vec![
+        Operation::new("BMC", vec!["Tx".into()]),
+        Operation::new("q", vec![]),
+        Operation::new(
+            "re",
+            vec![
+                1.into(),
+                1.into(),
+                (width - 2.0).into(),
+                (height - 2.0).into(),
+            ],
+        ),
+        Operation::new("W", vec![]),
+        Operation::new("n", vec![]),
+        Operation::new("BT", vec![]),
+    ];
+    operations.extend(appearance_ops);
+    operations.extend([
+        Operation::new("Td", vec![x.into(), y.into()]),
+        Operation::new("Tj", vec![Object::string_literal(value)]),
+        Operation::new("ET", vec![]),
+        Operation::new("Q", vec![]),
+        Operation::new("EMC", vec![]),
+    ]);
+
+    Content { operations }.encode().map_err(|e| {
+        let msg = "failed to encode field appearance";
+        error!(error = ?e, msg);
+        ActionError::InvalidPdfSheet(msg.to_string())
+    })
+}
+
+/// Lists every terminal field of the AcroForm with the object holding its dictionary.
+fn terminal_form_fields(doc: &Document) -> Result<Vec<(ObjectId, FormField)>, ActionError> {
+    let (_, fields_array_id) = find_acroform_ids(doc)?;
+
+    let fields = doc
+        .get_object(fields_array_id)
+        .and_then(Object::as_array)
+        .map_err(|e| {
+            let msg = "failed to get AcroForm Fields array";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    Ok(terminal_fields(doc, fields)
+        .into_iter()
+        .filter_map(|field| {
+            let kind = form_field_kind(field.field_type, field.flags);
+            Some((field.id?, FormField::new(field.name, kind)))
+        })
+        .collect())
+}
+
+/// Classifies a field by its /FT and /Ff.
+/// Ff flags: bit 16 (65536) = Pushbutton, bit 15 (32768) = Radio
+fn form_field_kind(field_type: Option<&[u8]>, flags: i64) -> FormFieldKind {
+    match field_type {
+        Some(b"Tx") => FormFieldKind::Text,
+        Some(b"Ch") => FormFieldKind::Choice,
+        Some(b"Btn") if flags & 65536 != 0 => FormFieldKind::PushButton,
+        Some(b"Btn") if flags & 32768 != 0 => FormFieldKind::Radio,
+        Some(b"Btn") => FormFieldKind::Checkbox,
+        Some(b"Sig") => FormFieldKind::Signature,
+        _ => FormFieldKind::Unknown,
+    }
+}
+
+fn load_document(sheet_path: &Path) -> Result<Document, ActionError> {
+    Document::load(sheet_path).map_err(|e| {
+        error!(error = ?e, "failed to load PDF sheet");
+        ActionError::LoadPdfError
+    })
+}
+
+fn save_document(doc: &mut Document, sheet_path: &Path) -> Result<(), ActionError> {
+    doc.save(sheet_path).map_err(|e| {
+        error!(error = ?e, "failed to save PDF");
+        ActionError::SavePdfError
+    })?;
+
+    Ok(())
+}
+
+/// Installs `js` as the document-level helper script. Returns `false` when the embedded
+/// helper already matches and the document was left untouched.
+fn install_helper_js(doc: &mut Document, js: &str) -> Result<bool, ActionError> {
+    let catalog_id = doc
+        .trailer
+        .get(b"Root")
+        .and_then(|root| root.as_reference())
+        .map_err(|e| {
+            let msg = "failed to get Root reference from PDF trailer";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    let (js_tree_id, mut entries) = read_js_name_tree(doc, catalog_id);
+
+    match entries
+        .iter()
+        .position(|(name, _)| name.as_slice() == HELPERS_JS_NAME)
+    {
+        Some(index) => {
+            if read_js_action_source(doc, &entries[index].1).as_deref() == Some(js.as_bytes()) {
+                return Ok(false);
+            }
 
-            // Ensure /AA dictionary exists
-            match field_dict.get_mut(b"AA") {
-                Ok(Object::Dictionary(_)) => {}
+            // Replace the helper script in place so the name tree stays untouched
+            match entries[index].1.as_reference() {
+                Ok(js_action_id) if doc.get_dictionary(js_action_id).is_ok() => {
+                    let js_dict = doc
+                        .get_object_mut(js_action_id)
+                        .and_then(|obj| obj.as_dict_mut())
+                        .map_err(|e| {
+                            let msg = "failed to get helper JavaScript action dictionary";
+                            error!(error = ?e, msg);
+                            ActionError::InvalidPdfSheet(msg.to_string())
+                        })?;
+                    // A script kept in its own stream is orphaned once replaced by a string
+                    let replaced_js_id = js_dict.get(b"JS").and_then(Object::as_reference).ok();
+                    js_dict.set(
+                        "JS",
+                        Object::String(js.as_bytes().to_vec(), StringFormat::Literal),
+                    );
+                    remove_replaced_objects(doc, replaced_js_id);
+                }
                 _ => {
-                    field_dict.set(b"AA", dictionary! {});
+                    let replaced_action_id = entries[index].1.as_reference().ok();
+                    entries[index].1 = Object::Reference(add_js_action(doc, js));
+                    write_js_name_tree(doc, catalog_id, js_tree_id, entries)?;
+                    remove_replaced_objects(doc, replaced_action_id);
                 }
-            };
+            }
+        }
+        None => {
+            let js_action_id = add_js_action(doc, js);
+            entries.push((HELPERS_JS_NAME.to_vec(), Object::Reference(js_action_id)));
+            write_js_name_tree(doc, catalog_id, js_tree_id, entries)?;
+        }
+    }
+
+    Ok(true)
+}
 
-            let aa_dict = field_dict
-                .get_mut(b"AA")
-                .expect("AA dictionary should exist") // AA dictionary will be created above if it doesn't exist
-                .as_dict_mut()
-                .expect("AA dictionary should be a dictionary"); // AA object will be created above as a dictionary
-            aa_dict.set(b"C", Object::Reference(js_action_id));
+/// Removes the object of a replaced helper or calculation action or script, leaving every other
+/// object of the document untouched.
+fn remove_replaced_objects(doc: &mut Document, replaced_id: Option<ObjectId>) {
+    if let Some(id) = replaced_id
+        && doc.objects.remove(&id).is_some()
+    {
+        debug!(object_id = ?id, "removed replaced JavaScript object");
+    }
+}
+
+/// Sets `js` as the `/AA /C` calculation action of the target field and adds the field to
+/// the AcroForm calculation order.
+fn attach_field_calculation_js(
+    doc: &mut Document,
+    js: &str,
+    target_field: &str,
+) -> Result<(), ActionError> {
+    let (acroform_id, fields_array_id) = find_acroform_ids(doc)?;
+
+    // Find the target field object by its fully-qualified name, searching the hierarchy
+    let target_field_id = find_form_field_by_name(doc, fields_array_id, target_field)
+        .ok_or(ActionError::FieldNotFound(target_field.to_string()))?;
+
+    let js_action_id = add_js_action(doc, js);
+
+    let replaced_js_id = {
+        let field_obj = doc.get_object_mut(target_field_id).map_err(|e| {
+            let msg = format!(
+                "failed to get field dictionary for field \"{}\"",
+                target_field
+            );
+            error!(error = ?e, msg);
+            ActionError::FieldNotFound(msg)
+        })?;
+        let field_dict = field_obj.as_dict_mut().map_err(|e| {
+            let msg = format!("field \"{}\" is not a dictionary", target_field);
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+        // Ensure /AA dictionary exists
+        match field_dict.get_mut(b"AA") {
+            Ok(Object::Dictionary(_)) => {}
+            _ => {
+                field_dict.set(b"AA", dictionary! {});
+            }
+        };
+
+        let aa_dict = field_dict
+            .get_mut(b"AA")
+            .expect("AA dictionary should exist") // AA dictionary will be created above if it doesn't exist
+            .as_dict_mut()
+            .expect("AA dictionary should be a dictionary"); // AA object will be created above as a dictionary
+        let replaced_js_id = aa_dict.get(b"C").and_then(Object::as_reference).ok();
+        aa_dict.set(b"C", Object::Reference(js_action_id));
+        replaced_js_id
+    };
+    remove_replaced_objects(doc, replaced_js_id);
+
+    let acroform_dict = doc
+        .get_object_mut(acroform_id)
+        .and_then(|obj| obj.as_dict_mut())
+        .map_err(|e| {
+            let msg = "failed to get AcroForm dictionary";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    // Ensure /AcroForm /CO array includes the target field (calculation order), keeping the
+    // existing order; `apply_calculation_order` moves it into place afterwards
+    {
+        let mut co_arr: Vec<Object> = acroform_dict
+            .get(b"CO")
+            .and_then(Object::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        if !co_arr
+            .iter()
+            .any(|field| field.as_reference().ok() == Some(target_field_id))
+        {
+            co_arr.push(Object::Reference(target_field_id));
+            acroform_dict.set(b"CO", Object::Array(co_arr));
         }
+    }
+
+    // Ask viewers to regenerate appearances
+    acroform_dict.set(b"NeedAppearances", Object::Boolean(true));
+
+    Ok(())
+}
 
-        // Ensure /AcroForm /CO array includes the target field (calculation order)
+/// Rearranges the `/CO` entries of the fields named in `calculation_order` so they run in that
+/// order. They are permuted among the slots they already occupy, so entries for any other
+/// calculated field keep their position.
+fn apply_calculation_order(
+    doc: &mut Document,
+    calculation_order: &[String],
+) -> Result<(), ActionError> {
+    let (acroform_id, fields_array_id) = find_acroform_ids(doc)?;
+
+    let ordered_ids: Vec<ObjectId> = calculation_order
+        .iter()
+        .filter_map(|field_name| find_form_field_by_name(doc, fields_array_id, field_name))
+        .collect();
+
+    let acroform_dict = doc
+        .get_object_mut(acroform_id)
+        .and_then(|obj| obj.as_dict_mut())
+        .map_err(|e| {
+            let msg = "failed to get AcroForm dictionary";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    let Ok(Object::Array(co_arr)) = acroform_dict.get_mut(b"CO") else {
+        return Ok(());
+    };
+
+    let in_co: BTreeSet<ObjectId> = co_arr
+        .iter()
+        .filter_map(|field| field.as_reference().ok())
+        .collect();
+    let mut ordered = ordered_ids.iter().filter(|id| in_co.contains(id));
+
+    for slot in co_arr.iter_mut() {
+        if slot
+            .as_reference()
+            .is_ok_and(|field_id| ordered_ids.contains(&field_id))
+            && let Some(field_id) = ordered.next()
         {
-            let acroform_dict = doc
-                .get_object_mut(acroform_id)
-                .and_then(|obj| obj.as_dict_mut())
-                .map_err(|e| {
-                    let msg = "failed to get AcroForm dictionary";
-                    error!(error = ?e, msg);
-                    ActionError::InvalidPdfSheet(msg.to_string())
-                })?;
+            *slot = Object::Reference(*field_id);
+        }
+    }
 
-            // Build a set so we don't duplicate
-            let mut existing: BTreeSet<ObjectId> = BTreeSet::new();
-            if let Ok(co) = acroform_dict.get(b"CO")
-                && let Ok(co_arr) = co.as_array()
-            {
-                for field in co_arr {
-                    if let Ok(field_ref) = field.as_reference() {
-                        existing.insert(field_ref);
-                    }
-                }
+    Ok(())
+}
+
+/// Resolves the `/AcroForm` dictionary and its `/Fields` array object IDs from the catalog.
+fn find_acroform_ids(doc: &Document) -> Result<(ObjectId, ObjectId), ActionError> {
+    let catalog_id = doc
+        .trailer
+        .get(b"Root")
+        .and_then(|root| root.as_reference())
+        .map_err(|e| {
+            let msg = "failed to get Root reference from PDF trailer";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    let acroform_id = doc
+        .get_object(catalog_id)
+        .and_then(|obj| obj.as_dict())
+        .and_then(|catalog_dict| catalog_dict.get(b"AcroForm"))
+        .and_then(|obj| obj.as_reference())
+        .map_err(|e| {
+            let msg = "failed to get AcroForm reference";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    let fields_array_id = doc
+        .get_object(acroform_id)
+        .and_then(|obj| obj.as_dict())
+        .and_then(|acroform_dict| acroform_dict.get(b"Fields"))
+        .and_then(|obj| obj.as_reference())
+        .map_err(|e| {
+            let msg = "failed to get Fields array reference from AcroForm";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    Ok((acroform_id, fields_array_id))
+}
+
+/// Adds a JavaScript action dictionary holding `js` and returns its object ID.
+fn add_js_action(doc: &mut Document, js: &str) -> ObjectId {
+    doc.add_object(dictionary! {
+        b"S" => Object::Name(b"JavaScript".to_vec()),
+        b"JS" => Object::String(js.as_bytes().to_vec(), StringFormat::Literal),
+    })
+}
+
+/// Reads the `/Names /JavaScript` name tree, returning the ID of its root node (if it is an
+/// indirect object) and all of its `(name, action)` entries, flattened across `/Kids`.
+fn read_js_name_tree(
+    doc: &Document,
+    catalog_id: ObjectId,
+) -> (Option<ObjectId>, Vec<(Vec<u8>, Object)>) {
+    let mut entries = Vec::new();
+
+    let Some((js_tree_id, js_tree)) = doc
+        .get_dictionary(catalog_id)
+        .and_then(|catalog| catalog.get_deref(b"Names", doc))
+        .and_then(Object::as_dict)
+        .and_then(|names| names.get(b"JavaScript"))
+        .and_then(|tree| doc.dereference(tree))
+        .ok()
+    else {
+        return (None, entries);
+    };
+
+    collect_name_tree_entries(doc, js_tree, &mut entries);
+
+    (js_tree_id, entries)
+}
+
+/// Depth-first collection of the entries of a name tree node and its `/Kids`.
+fn collect_name_tree_entries(doc: &Document, node: &Object, entries: &mut Vec<(Vec<u8>, Object)>) {
+    let Ok(node) = node.as_dict() else {
+        return;
+    };
+
+    if let Ok(names) = node.get(b"Names").and_then(Object::as_array) {
+        for pair in names.chunks_exact(2) {
+            if let Ok(name) = pair[0].as_str() {
+                entries.push((name.to_vec(), pair[1].clone()));
             }
+        }
+    }
 
-            if !existing.contains(&target_field_id) {
-                existing.insert(target_field_id);
-                let co_arr: Vec<Object> = existing.into_iter().map(Object::Reference).collect();
-                acroform_dict.set(b"CO", Object::Array(co_arr));
+    if let Ok(kids) = node.get(b"Kids").and_then(Object::as_array) {
+        for kid in kids {
+            if let Ok((_, kid)) = doc.dereference(kid) {
+                collect_name_tree_entries(doc, kid, entries);
             }
         }
+    }
+}
 
-        // Ask viewers to regenerate appearances
-        {
-            let acroform_dict = doc
-                .get_object_mut(acroform_id)
+/// Returns the source of a JavaScript action whose `/JS` is either a string or a stream.
+fn read_js_action_source(doc: &Document, action: &Object) -> Option<Vec<u8>> {
+    let (_, action) = doc.dereference(action).ok()?;
+    let (_, js) = doc
+        .dereference(action.as_dict().ok()?.get(b"JS").ok()?)
+        .ok()?;
+
+    match js {
+        Object::String(bytes, _) => Some(bytes.clone()),
+        Object::Stream(stream) => stream.get_plain_content().ok(),
+        _ => None,
+    }
+}
+
+/// Writes `entries` as a single sorted leaf node for the `/Names /JavaScript` name tree,
+/// reusing the existing root node object when there is one.
+fn write_js_name_tree(
+    doc: &mut Document,
+    catalog_id: ObjectId,
+    js_tree_id: Option<ObjectId>,
+    mut entries: Vec<(Vec<u8>, Object)>,
+) -> Result<(), ActionError> {
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let names: Vec<Object> = entries
+        .into_iter()
+        .flat_map(|(name, action)| [Object::String(name, StringFormat::Literal), action])
+        .collect();
+    let js_name_tree = Object::Dictionary(dictionary! { b"Names" => Object::Array(names) });
+
+    let js_tree_id = match js_tree_id {
+        Some(id) => {
+            doc.objects.insert(id, js_name_tree);
+            id
+        }
+        None => doc.add_object(js_name_tree),
+    };
+
+    let catalog = doc
+        .get_object_mut(catalog_id)
+        .and_then(|obj| obj.as_dict_mut())
+        .map_err(|e| {
+            let msg = "failed to get Catalog object from PDF trailer";
+            error!(error = ?e, msg);
+            ActionError::InvalidPdfSheet(msg.to_string())
+        })?;
+
+    // The Names dictionary may be inline in the catalog or an indirect object
+    let names_dict_id = match catalog.get_mut(b"Names") {
+        Ok(Object::Dictionary(names_dict)) => {
+            names_dict.set("JavaScript", Object::Reference(js_tree_id));
+            return Ok(());
+        }
+        Ok(Object::Reference(id)) => Some(*id),
+        _ => None,
+    };
+
+    match names_dict_id.filter(|id| doc.get_dictionary(*id).is_ok()) {
+        Some(names_dict_id) => {
+            let names_dict = doc
+                .get_object_mut(names_dict_id)
                 .and_then(|obj| obj.as_dict_mut())
                 .map_err(|e| {
-                    let msg = "failed to get AcroForm dictionary";
+                    let msg = "failed to get Names dictionary from PDF trailer";
                     error!(error = ?e, msg);
                     ActionError::InvalidPdfSheet(msg.to_string())
                 })?;
-            acroform_dict.set(b"NeedAppearances", Object::Boolean(true));
+            names_dict.set("JavaScript", Object::Reference(js_tree_id));
+        }
+        None => {
+            let names_dict_id =
+                doc.add_object(dictionary! { b"JavaScript" => Object::Reference(js_tree_id) });
+            let catalog = doc
+                .get_object_mut(catalog_id)
+                .and_then(|obj| obj.as_dict_mut())
+                .map_err(|e| {
+                    let msg = "failed to get Catalog object from PDF trailer";
+                    error!(error = ?e, msg);
+                    ActionError::InvalidPdfSheet(msg.to_string())
+                })?;
+            catalog.set("Names", Object::Reference(names_dict_id));
         }
-
-        doc.save(sheet_path).map_err(|e| {
-            error!(error = ?e, "failed to save PDF");
-            ActionError::SavePdfError
-        })?;
-
-        Ok(())
     }
+
+    Ok(())
 }
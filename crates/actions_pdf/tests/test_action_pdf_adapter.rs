@@ -0,0 +1,526 @@
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{
+        TestContext, dnd5e_sheet_copy, nested_fields_sheet, read_document_javascript,
+    };
+    use actions_core::error::ActionError;
+    use actions_core::ports::driven::{
+        ActionPdfPort, CalculationScript, ComputedValue, FormField, FormFieldKind,
+    };
+    use actions_core::suggestion::suggest_actions;
+    use actions_pdf::adapter::PdfActionAdapter;
+    use common_pdf::find_form_field_by_name;
+    use lopdf::{Document, Object, Stream, StringFormat, dictionary};
+    use pretty_assertions::assert_eq;
+    use rstest::{fixture, rstest};
+    use std::collections::HashMap;
+    use std::fs;
+
+    #[fixture]
+    fn ctx() -> TestContext {
+        TestContext::setup()
+    }
+
+    #[rstest]
+    fn test_should_add_doc_level_js_under_stable_key(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+
+        adapter
+            .add_doc_level_js("function helper() {}", sheet.path())
+            .unwrap();
+
+        let actual = read_document_javascript(sheet.path());
+        assert_eq!(
+            actual,
+            vec![("HelpersJS".to_string(), "function helper() {}".to_string())]
+        );
+    }
+
+    #[rstest]
+    fn test_should_skip_doc_level_js_when_helper_is_up_to_date(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+        adapter
+            .add_doc_level_js("function helper() {}", sheet.path())
+            .unwrap();
+        let expected = fs::read(sheet.path()).unwrap();
+
+        adapter
+            .add_doc_level_js("function helper() {}", sheet.path())
+            .unwrap();
+
+        let actual = fs::read(sheet.path()).unwrap();
+        assert!(actual == expected, "sheet was rewritten");
+    }
+
+    #[rstest]
+    fn test_should_replace_outdated_helper_in_place(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+        adapter
+            .add_doc_level_js("function helper() { return 1; }", sheet.path())
+            .unwrap();
+        let object_count = Document::load(sheet.path()).unwrap().objects.len();
+
+        adapter
+            .add_doc_level_js("function helper() { return 2; }", sheet.path())
+            .unwrap();
+
+        let actual = read_document_javascript(sheet.path());
+        assert_eq!(
+            actual,
+            vec![(
+                "HelpersJS".to_string(),
+                "function helper() { return 2; }".to_string()
+            )]
+        );
+        assert_eq!(
+            Document::load(sheet.path()).unwrap().objects.len(),
+            object_count
+        );
+    }
+
+    #[rstest]
+    fn test_should_remove_only_replaced_helper_objects(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+        let (helper_stream_id, unrelated_id) = {
+            let mut doc = Document::load(sheet.path()).unwrap();
+            let helper_stream_id = doc.add_object(Stream::new(
+                dictionary! {},
+                b"function helper() { return 1; }".to_vec(),
+            ));
+            let js_action_id = doc.add_object(dictionary! {
+                b"S" => Object::Name(b"JavaScript".to_vec()),
+                b"JS" => Object::Reference(helper_stream_id),
+            });
+            let js_tree_id = doc.add_object(dictionary! {
+                b"Names" => Object::Array(vec![
+                    Object::String(b"HelpersJS".to_vec(), StringFormat::Literal),
+                    Object::Reference(js_action_id),
+                ]),
+            });
+            let catalog = doc.catalog_mut().unwrap();
+            catalog.set(
+                "Names",
+                dictionary! { b"JavaScript" => Object::Reference(js_tree_id) },
+            );
+            let unrelated_id = doc.add_object(dictionary! { b"Note" => "unreferenced" });
+            doc.save(sheet.path()).unwrap();
+            (helper_stream_id, unrelated_id)
+        };
+
+        adapter
+            .add_doc_level_js("function helper() { return 2; }", sheet.path())
+            .unwrap();
+
+        let doc = Document::load(sheet.path()).unwrap();
+        assert!(doc.get_object(helper_stream_id).is_err());
+        assert!(doc.get_object(unrelated_id).is_ok());
+        assert_eq!(
+            read_document_javascript(sheet.path()),
+            vec![(
+                "HelpersJS".to_string(),
+                "function helper() { return 2; }".to_string()
+            )]
+        );
+    }
+
+    #[rstest]
+    fn test_should_preserve_third_party_doc_level_js(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+        {
+            let mut doc = Document::load(sheet.path()).unwrap();
+            let js_action_id = doc.add_object(dictionary! {
+                b"S" => Object::Name(b"JavaScript".to_vec()),
+                b"JS" => Object::String(b"app.alert('hi');".to_vec(), StringFormat::Literal),
+            });
+            let js_tree_id = doc.add_object(dictionary! {
+                b"Names" => Object::Array(vec![
+                    Object::String(b"Greeting".to_vec(), StringFormat::Literal),
+                    Object::Reference(js_action_id),
+                ]),
+            });
+            let catalog = doc.catalog_mut().unwrap();
+            catalog.set(
+                "Names",
+                dictionary! { b"JavaScript" => Object::Reference(js_tree_id) },
+            );
+            doc.save(sheet.path()).unwrap();
+        }
+
+        adapter
+            .add_doc_level_js("function helper() {}", sheet.path())
+            .unwrap();
+
+        let actual = read_document_javascript(sheet.path());
+        assert_eq!(
+            actual,
+            vec![
+                ("Greeting".to_string(), "app.alert('hi');".to_string()),
+                ("HelpersJS".to_string(), "function helper() {}".to_string()),
+            ]
+        );
+    }
+
+    fn has_calculation_action(doc: &Document, group: &str) -> bool {
+        doc.objects.values().any(|obj| {
+            obj.as_dict().is_ok_and(|dict| {
+                dict.get(b"Parent")
+                    .and_then(Object::as_reference)
+                    .and_then(|parent| doc.get_dictionary(parent))
+                    .and_then(|parent| parent.get(b"T"))
+                    .and_then(Object::as_str)
+                    .is_ok_and(|t| t == group.as_bytes())
+                    && dict.get(b"AA").is_ok()
+            })
+        })
+    }
+
+    #[rstest]
+    fn test_should_attach_calculation_js_by_fully_qualified_name(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+
+        adapter
+            .attach_calculation_js(
+                "event.value = 1;",
+                sheet.path(),
+                "Saves.Acrobatics",
+                &["Saves.Acrobatics".to_string()],
+            )
+            .unwrap();
+
+        let doc = Document::load(sheet.path()).unwrap();
+        assert!(has_calculation_action(&doc, "Saves"));
+        assert!(!has_calculation_action(&doc, "Skills"));
+    }
+
+    #[rstest]
+    fn test_should_remove_replaced_and_detached_calculation_scripts(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+        let object_count = || Document::load(sheet.path()).unwrap().objects.len();
+        let original_count = object_count();
+        let attach = |js: &str| {
+            adapter
+                .attach_calculation_js(js, sheet.path(), "Name", &["Name".to_string()])
+                .unwrap();
+        };
+
+        attach("event.value = 'first';");
+        let attached_count = object_count();
+        attach("event.value = 'second';");
+        attach("event.value = 'third';");
+
+        assert_eq!(object_count(), attached_count);
+        let bytes = fs::read(sheet.path()).unwrap();
+        assert!(!bytes.windows(5).any(|window| window == b"first"));
+        assert!(!bytes.windows(6).any(|window| window == b"second"));
+
+        adapter.detach_calculation_js(sheet.path(), "Name").unwrap();
+
+        assert_eq!(object_count(), original_count);
+        let bytes = fs::read(sheet.path()).unwrap();
+        assert!(!bytes.windows(5).any(|window| window == b"third"));
+    }
+
+    #[rstest]
+    fn test_should_not_match_partial_name_of_nested_field(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+
+        let actual =
+            adapter.attach_calculation_js("event.value = 1;", sheet.path(), "Acrobatics", &[]);
+
+        assert_eq!(
+            actual,
+            Err(ActionError::FieldNotFound("Acrobatics".to_string()))
+        );
+    }
+
+    /// Returns the fully-qualified names of the fields in /AcroForm /CO, in order.
+    fn calculation_order(doc: &Document, field_names: &[&str]) -> Vec<String> {
+        let acroform = doc
+            .catalog()
+            .and_then(|catalog| catalog.get(b"AcroForm"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .unwrap();
+        let fields_id = acroform
+            .get(b"Fields")
+            .and_then(Object::as_reference)
+            .unwrap();
+        acroform
+            .get(b"CO")
+            .and_then(Object::as_array)
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                let id = entry.as_reference().unwrap();
+                field_names
+                    .iter()
+                    .find(|name| find_form_field_by_name(doc, fields_id, name) == Some(id))
+                    .map_or_else(|| format!("{:?}", id), |name| name.to_string())
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn test_should_write_calculation_order_keeping_unrelated_entries_in_place(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+        adapter
+            .attach_calculation_js(
+                "event.value = 1;",
+                sheet.path(),
+                "Skills.Acrobatics",
+                &["Skills.Acrobatics".to_string()],
+            )
+            .unwrap();
+
+        // `Name` reads `Saves.Acrobatics`, so it must be calculated last
+        adapter
+            .attach_calculation_scripts(
+                "function helper() {}",
+                &[
+                    CalculationScript::new("Name", "event.value = 2;"),
+                    CalculationScript::new("Saves.Acrobatics", "event.value = 3;"),
+                ],
+                &["Saves.Acrobatics".to_string(), "Name".to_string()],
+                sheet.path(),
+            )
+            .unwrap();
+
+        let doc = Document::load(sheet.path()).unwrap();
+        assert_eq!(
+            calculation_order(&doc, &["Skills.Acrobatics", "Saves.Acrobatics", "Name"]),
+            vec!["Skills.Acrobatics", "Saves.Acrobatics", "Name"]
+        );
+    }
+
+    #[rstest]
+    fn test_should_list_form_fields_by_fully_qualified_name(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+
+        let actual = adapter.list_form_fields(sheet.path()).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                FormField::new("Skills.Acrobatics", FormFieldKind::Text),
+                FormField::new("Saves.Acrobatics", FormFieldKind::Text),
+                FormField::new("Name", FormFieldKind::Text),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_should_read_stored_values_of_fields_that_have_one(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+        let mut doc = Document::load(sheet.path()).unwrap();
+        let fields_id = doc
+            .catalog()
+            .and_then(|catalog| catalog.get(b"AcroForm"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .and_then(|acroform| acroform.get(b"Fields"))
+            .and_then(Object::as_reference)
+            .unwrap();
+        let field_id = find_form_field_by_name(&doc, fields_id, "Skills.Acrobatics").unwrap();
+        doc.get_dictionary_mut(field_id)
+            .unwrap()
+            .set("V", Object::string_literal("+4"));
+        doc.save(sheet.path()).unwrap();
+
+        let actual = adapter.read_field_values(sheet.path()).unwrap();
+
+        assert_eq!(
+            actual,
+            HashMap::from([("Skills.Acrobatics".to_string(), "+4".to_string())])
+        );
+    }
+
+    #[rstest]
+    #[case::without_scripts(false)]
+    #[case::keeping_scripts(true)]
+    fn test_should_bake_computed_value_with_appearance(
+        _ctx: TestContext,
+        #[case] keep_scripts: bool,
+    ) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+        adapter
+            .attach_calculation_js(
+                "event.value = 4;",
+                sheet.path(),
+                "Skills.Acrobatics",
+                &["Skills.Acrobatics".to_string()],
+            )
+            .unwrap();
+
+        adapter
+            .write_computed_values(
+                sheet.path(),
+                &[ComputedValue::new("Skills.Acrobatics", "+4")],
+                keep_scripts,
+            )
+            .unwrap();
+
+        let doc = Document::load(sheet.path()).unwrap();
+        let acroform = doc
+            .catalog()
+            .and_then(|catalog| catalog.get(b"AcroForm"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .unwrap();
+        let fields_id = acroform
+            .get(b"Fields")
+            .and_then(Object::as_reference)
+            .unwrap();
+        let field_id = find_form_field_by_name(&doc, fields_id, "Skills.Acrobatics").unwrap();
+        let field = doc.get_dictionary(field_id).unwrap();
+        assert_eq!(
+            field.get(b"V").and_then(Object::as_str).unwrap(),
+            b"+4".as_slice()
+        );
+        let appearance = field
+            .get(b"AP")
+            .and_then(Object::as_dict)
+            .and_then(|ap| ap.get(b"N"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_object(id))
+            .and_then(Object::as_stream)
+            .unwrap();
+        let content = String::from_utf8_lossy(&appearance.content).into_owned();
+        assert!(content.contains("(+4) Tj"), "appearance: {}", content);
+        assert_eq!(field.has(b"AA"), keep_scripts);
+        assert_eq!(acroform.has(b"CO"), keep_scripts);
+    }
+
+    #[rstest]
+    fn test_should_skip_computed_value_of_missing_field(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+
+        adapter
+            .write_computed_values(
+                sheet.path(),
+                &[
+                    ComputedValue::new("Skills.Removed", "+1"),
+                    ComputedValue::new("Name", "Aria"),
+                ],
+                false,
+            )
+            .unwrap();
+
+        let values = adapter.read_field_values(sheet.path()).unwrap();
+        assert_eq!(
+            values,
+            HashMap::from([("Name".to_string(), "Aria".to_string())])
+        );
+    }
+
+    /// Moves the field type of `Skills.Acrobatics` up to its `Skills` group, as forms whose
+    /// fields share their type with siblings do.
+    fn inherit_field_type(sheet: &std::path::Path, field_type: &str) {
+        let mut doc = Document::load(sheet).unwrap();
+        let fields_id = doc
+            .catalog()
+            .and_then(|catalog| catalog.get(b"AcroForm"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .and_then(|acroform| acroform.get(b"Fields"))
+            .and_then(Object::as_reference)
+            .unwrap();
+        let group_id = find_form_field_by_name(&doc, fields_id, "Skills").unwrap();
+        let field_id = find_form_field_by_name(&doc, fields_id, "Skills.Acrobatics").unwrap();
+        doc.get_dictionary_mut(field_id).unwrap().remove(b"FT");
+        doc.get_dictionary_mut(group_id)
+            .unwrap()
+            .set("FT", Object::Name(field_type.as_bytes().to_vec()));
+        doc.save(sheet).unwrap();
+    }
+
+    #[rstest]
+    #[case::text("Tx", true)]
+    #[case::button("Btn", false)]
+    fn test_should_bake_computed_value_by_inherited_field_type(
+        _ctx: TestContext,
+        #[case] field_type: &str,
+        #[case] baked: bool,
+    ) {
+        let adapter = PdfActionAdapter;
+        let sheet = nested_fields_sheet();
+        inherit_field_type(sheet.path(), field_type);
+
+        adapter
+            .write_computed_values(
+                sheet.path(),
+                &[ComputedValue::new("Skills.Acrobatics", "+4")],
+                false,
+            )
+            .unwrap();
+
+        let doc = Document::load(sheet.path()).unwrap();
+        let fields_id = doc
+            .catalog()
+            .and_then(|catalog| catalog.get(b"AcroForm"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .and_then(|acroform| acroform.get(b"Fields"))
+            .and_then(Object::as_reference)
+            .unwrap();
+        let field_id = find_form_field_by_name(&doc, fields_id, "Skills.Acrobatics").unwrap();
+        let field = doc.get_dictionary(field_id).unwrap();
+        assert_eq!(field.has(b"V"), baked);
+        assert_eq!(field.has(b"AP"), baked);
+    }
+
+    #[rstest]
+    fn test_should_list_form_field_kinds_of_dnd5e_sheet(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+
+        let actual = adapter.list_form_fields(sheet.path()).unwrap();
+
+        let kind_of = |name: &str| {
+            actual
+                .iter()
+                .find(|field| field.name == name)
+                .map(|field| field.kind)
+        };
+        assert_eq!(kind_of("STR"), Some(FormFieldKind::Text));
+        assert_eq!(kind_of("Check Box 11"), Some(FormFieldKind::Checkbox));
+        assert_eq!(kind_of("STRmod"), Some(FormFieldKind::Text));
+        assert_eq!(kind_of("ProfBonus"), Some(FormFieldKind::Text));
+        assert_eq!(kind_of("Check Box 26"), Some(FormFieldKind::Checkbox));
+        assert_eq!(kind_of("ST Strength"), Some(FormFieldKind::Text));
+        assert_eq!(kind_of("Athletics"), Some(FormFieldKind::Text));
+    }
+
+    #[rstest]
+    fn test_should_suggest_actions_for_fields_of_dnd5e_sheet(_ctx: TestContext) {
+        let adapter = PdfActionAdapter;
+        let sheet = dnd5e_sheet_copy();
+        let form_fields = adapter.list_form_fields(sheet.path()).unwrap();
+
+        let actual = suggest_actions(&form_fields);
+
+        assert_eq!(
+            actual.sheet_name,
+            Some("D&D 5e Character Sheet (Wizards of the Coast)")
+        );
+        // Ability and saving throw modifiers, skills, passive perception, initiative, spell DC and attack
+        assert_eq!(actual.suggestions.len(), 6 + 6 + 18 + 4);
+        for suggestion in &actual.suggestions {
+            assert_eq!(suggestion.action.validate_fields(&form_fields), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+use common_telemetry as telemetry;
+use lopdf::{Document, Object};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+pub struct TestContext;
+
+impl TestContext {
+    pub fn setup() -> Self {
+        telemetry::initialize().expect("initialize telemetry");
+
+        Self {}
+    }
+}
+
+/// Copies the D&D 5e fixture into a temp file so tests can modify it freely.
+pub fn dnd5e_sheet_copy() -> NamedTempFile {
+    fixture_copy("DnD_5E_CharacterSheet_FormFillable.pdf")
+}
+
+/// Copies a form whose `Acrobatics` fields only differ by their parent group
+/// (`Skills.Acrobatics` and `Saves.Acrobatics`), next to a top-level `Name` field.
+pub fn nested_fields_sheet() -> NamedTempFile {
+    fixture_copy("nested_fields.pdf")
+}
+
+fn fixture_copy(name: &str) -> NamedTempFile {
+    let here = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture = here.join("tests/fixtures").join(name);
+    let temp_file = NamedTempFile::with_suffix(".pdf").expect("create temp file");
+    fs::copy(fixture, temp_file.path()).expect("copy fixture");
+    temp_file
+}
+
+/// Returns `(name, js)` pairs from the document-level `/Names /JavaScript` name tree.
+pub fn read_document_javascript(path: &Path) -> Vec<(String, String)> {
+    let doc = Document::load(path).expect("failed to load PDF document");
+
+    let js_tree = doc
+        .catalog()
+        .expect("failed to get catalog")
+        .get_deref(b"Names", &doc)
+        .expect("catalog missing 'Names'")
+        .as_dict()
+        .expect("'Names' object is not a dictionary")
+        .get_deref(b"JavaScript", &doc)
+        .expect("'Names' missing 'JavaScript'")
+        .as_dict()
+        .expect("'JavaScript' names tree is not a dictionary");
+
+    let names_array = js_tree
+        .get(b"Names")
+        .expect("javascript names tree missing 'Names' array")
+        .as_array()
+        .expect("javascript names 'Names' entry is not an array");
+
+    names_array
+        .chunks(2)
+        .map(|chunk| {
+            let name = String::from_utf8_lossy(chunk[0].as_str().expect("name is not a string"));
+            let action = doc
+                .dereference(&chunk[1])
+                .expect("failed to dereference JavaScript action")
+                .1
+                .as_dict()
+                .expect("javascript action object is not a dictionary");
+            let js = match action.get(b"JS").expect("javascript action missing 'JS'") {
+                Object::String(bytes, _) => String::from_utf8_lossy(bytes).to_string(),
+                other => panic!("unexpected 'JS' object: {:?}", other),
+            };
+            (name.to_string(), js)
+        })
+        .collect()
+}
@@ -1,6 +1,9 @@
-use lopdf::{Document, Object, ObjectId};
+use lopdf::{Dictionary, Document, Object, ObjectId, decode_text_string};
 
-/// Recursively search /Fields (and /Kids) for a field whose /T equals `name`.
+/// Recursively search /Fields (and /Kids) for a field whose fully-qualified name equals `name`.
+///
+/// The fully-qualified name joins the partial /T names from the root field down with `.`
+/// (e.g. `Skills.Acrobatics`), so identically named fields in different groups never collide.
 pub fn find_form_field_by_name(
     doc: &Document,
     fields_array_id: ObjectId,
@@ -11,7 +14,7 @@ pub fn find_form_field_by_name(
 
     for item in arr {
         if let Ok(fid) = item.as_reference()
-            && let Some(found) = search_form_field_node(doc, fid, name)
+            && let Some(found) = search_form_field_node(doc, fid, None, name)
         {
             return Some(found);
         }
@@ -20,25 +23,176 @@ pub fn find_form_field_by_name(
     None
 }
 
+/// A field without named kids, i.e. one that holds a value, with its inheritable field type and
+/// flags resolved from the enclosing fields.
+#[derive(Debug)]
+pub struct TerminalField<'a> {
+    /// Object holding the field dictionary, `None` when the field is a direct object.
+    pub id: Option<ObjectId>,
+    /// Fully-qualified name, e.g. `Skills.Acrobatics`.
+    pub name: String,
+    pub dict: &'a Dictionary,
+    /// `/FT` of the field or its closest ancestor setting it, e.g. `Tx` or `Btn`.
+    pub field_type: Option<&'a [u8]>,
+    /// `/Ff` of the field or its closest ancestor setting it, 0 when none does.
+    pub flags: i64,
+    /// Widget annotations showing the field: the field itself when it is merged with its only
+    /// widget, otherwise its kids without `/T`.
+    pub widgets: Vec<(Option<ObjectId>, &'a Dictionary)>,
+}
+
+/// Walks the AcroForm `/Fields` array and returns every terminal field in document order.
+/// Entries without `/T` below the root are widget annotations of their parent, not fields.
+pub fn terminal_fields<'a>(doc: &'a Document, fields: &'a [Object]) -> Vec<TerminalField<'a>> {
+    let mut terminal = Vec::new();
+    for field in fields {
+        collect_terminal_fields(doc, field, None, None, 0, &mut terminal);
+    }
+    terminal
+}
+
+/// Appends a field's partial name to its parent's fully-qualified name.
+pub fn qualified_field_name(parent: Option<&str>, partial_name: &str) -> String {
+    match parent {
+        Some(parent) => format!("{}.{}", parent, partial_name),
+        None => partial_name.to_string(),
+    }
+}
+
+/// Decodes a `/V`, `/DV` or `/Opt` entry: text strings, names (button states) and the
+/// comma-joined selection of a multi-select choice field.
+pub fn decode_field_value(value: &Object) -> Option<String> {
+    match value {
+        Object::String(..) => decode_text_string(value).ok(),
+        Object::Name(name) => Some(String::from_utf8_lossy(name).into_owned()),
+        Object::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(decode_field_value)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        _ => None,
+    }
+}
+
+fn collect_terminal_fields<'a>(
+    doc: &'a Document,
+    field: &'a Object,
+    parent: Option<&str>,
+    inherited_type: Option<&'a [u8]>,
+    inherited_flags: i64,
+    terminal: &mut Vec<TerminalField<'a>>,
+) {
+    let Some((id, dict)) = resolve_dict(doc, field) else {
+        return;
+    };
+    let Some(partial_name) = dict
+        .get(b"T")
+        .and_then(Object::as_str)
+        .ok()
+        .and_then(|t| std::str::from_utf8(t).ok())
+    else {
+        return;
+    };
+
+    let name = qualified_field_name(parent, partial_name);
+    let field_type = dict
+        .get(b"FT")
+        .and_then(Object::as_name)
+        .ok()
+        .or(inherited_type);
+    let flags = dict
+        .get(b"Ff")
+        .and_then(Object::as_i64)
+        .unwrap_or(inherited_flags);
+
+    let kids: Vec<(Option<ObjectId>, &Dictionary, &Object)> = dict
+        .get(b"Kids")
+        .and_then(Object::as_array)
+        .map(|kids| {
+            kids.iter()
+                .filter_map(|kid| resolve_dict(doc, kid).map(|(id, dict)| (id, dict, kid)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if kids.iter().any(|(_, kid, _)| kid.has(b"T")) {
+        for (_, kid, kid_obj) in kids {
+            if kid.has(b"T") {
+                collect_terminal_fields(doc, kid_obj, Some(&name), field_type, flags, terminal);
+            }
+        }
+        return;
+    }
+
+    let widgets = if dict.has(b"Subtype") || dict.has(b"Rect") || dict.has(b"AP") {
+        vec![(id, dict)]
+    } else {
+        kids.into_iter().map(|(id, kid, _)| (id, kid)).collect()
+    };
+
+    terminal.push(TerminalField {
+        id,
+        name,
+        dict,
+        field_type,
+        flags,
+        widgets,
+    });
+}
+
+/// Resolves a field or widget entry to its dictionary and, when indirect, its object ID.
+fn resolve_dict<'a>(
+    doc: &'a Document,
+    obj: &'a Object,
+) -> Option<(Option<ObjectId>, &'a Dictionary)> {
+    match obj {
+        Object::Dictionary(dict) => Some((None, dict)),
+        Object::Reference(id) => doc.get_dictionary(*id).ok().map(|dict| (Some(*id), dict)),
+        _ => None,
+    }
+}
+
 /// Depth-first search of a field node (which may have /Kids).
-fn search_form_field_node(doc: &Document, field_id: ObjectId, name: &str) -> Option<ObjectId> {
+fn search_form_field_node(
+    doc: &Document,
+    field_id: ObjectId,
+    parent: Option<&str>,
+    name: &str,
+) -> Option<ObjectId> {
     let obj = doc.get_object(field_id).ok()?;
     let dict = obj.as_dict().ok()?;
 
-    // Read /T if present
-    if let Ok(t_obj) = dict.get(b"T")
-        && let Ok(t) = t_obj.as_str()
-        && let Ok(t_str) = std::str::from_utf8(t)
-        && t_str == name
-    {
+    // Kids without /T are widget annotations and inherit their parent's name
+    let partial_name = dict
+        .get(b"T")
+        .and_then(Object::as_str)
+        .ok()
+        .and_then(|t| std::str::from_utf8(t).ok());
+
+    let qualified_name = match partial_name {
+        Some(partial_name) => qualified_field_name(parent, partial_name),
+        None => parent?.to_string(),
+    };
+
+    if partial_name.is_some() && qualified_name == name {
         return Some(field_id);
     }
 
-    // Recurse into /Kids (some forms store names across a hierarchy)
+    // Only descend into groups that can still lead to `name`
+    let is_prefix = name
+        .strip_prefix(qualified_name.as_str())
+        .is_some_and(|rest| rest.starts_with('.'));
+    if !is_prefix {
+        return None;
+    }
+
     if let Ok(kids) = dict.get(b"Kids").and_then(Object::as_array) {
         for kid in kids {
             if let Ok(kid_id) = kid.as_reference()
-                && let Some(found) = search_form_field_node(doc, kid_id, name)
+                && let Some(found) =
+                    search_form_field_node(doc, kid_id, Some(&qualified_name), name)
             {
                 return Some(found);
             }
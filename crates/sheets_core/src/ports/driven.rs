@@ -10,6 +10,8 @@ use uuid::Uuid;
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait SheetStoragePort: Send + Sync {
+    /// Stores the uploaded sheet and keeps a pristine copy of the original alongside it, so
+    /// actions can later be replayed onto the untouched upload.
     async fn create(&self, sheet_reference: SheetReference) -> Result<SheetReference, SheetError>;
     async fn read(&self, path: PathBuf) -> Result<PathBuf, SheetError>;
 
@@ -44,15 +44,63 @@ impl SheetReference {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Kind of a calculable AcroForm field, with buttons split into checkboxes and radio groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SheetFieldKind {
+    Text,
+    Choice,
+    Checkbox,
+    Radio,
+}
+
+/// Field flags decoded from the AcroForm `/Ff` bit set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SheetFieldFlags {
+    pub read_only: bool,
+    pub required: bool,
+    /// Text fields only.
+    pub multiline: bool,
+    /// Choice fields only: drop-down combo box rather than list box.
+    pub combo: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct SheetField {
+    /// Fully-qualified field name: the partial names of the field and its parents joined by `.`.
     pub name: String,
+    pub kind: SheetFieldKind,
+    pub flags: SheetFieldFlags,
+    /// Current value (`/V`).
+    pub value: Option<String>,
+    /// Value the field resets to (`/DV`).
+    pub default_value: Option<String>,
+    /// Export values of a choice field (`/Opt`).
+    pub options: Vec<String>,
+    /// Maximum length of a text field (`/MaxLen`).
+    pub max_length: Option<u32>,
+    /// Zero-based index of the page showing the field's first widget.
+    pub page_index: Option<u32>,
+    /// First widget's `/Rect` as `[x1, y1, x2, y2]` in PDF user space.
+    pub rect: Option<[f32; 4]>,
+    /// Whether the field already has a calculation action (`/AA /C`).
+    pub has_calculation: bool,
 }
 
 impl SheetField {
-    pub fn new(name: impl Into<String>) -> Self {
+    pub fn new(name: impl Into<String>, kind: SheetFieldKind) -> Self {
         let name = name.into();
-        Self { name }
+        Self {
+            name,
+            kind,
+            flags: SheetFieldFlags::default(),
+            value: None,
+            default_value: None,
+            options: vec![],
+            max_length: None,
+            page_index: None,
+            rect: None,
+            has_calculation: false,
+        }
     }
 }
 
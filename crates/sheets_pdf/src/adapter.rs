@@ -1,8 +1,10 @@
 use async_trait::async_trait;
-use lopdf::Document;
+use common_pdf::{TerminalField, decode_field_value, terminal_fields};
+use lopdf::{Document, Object, ObjectId};
 use sheets_core::error::{PdfError, SheetError};
 use sheets_core::ports::driven::SheetPdfPort;
-use sheets_core::sheet::{Sheet, SheetField};
+use sheets_core::sheet::{Sheet, SheetField, SheetFieldFlags, SheetFieldKind};
+use std::collections::HashMap;
 use std::fs;
 use tracing::{debug, error, instrument};
 
@@ -66,10 +68,12 @@ impl SheetPdfPort for SheetsPdf {
                 PdfError::NotSupported(e.to_string())
             })?;
 
-        let mut sheet_fields: Vec<SheetField> = vec![];
-        for field_obj in fields_array {
-            Self::collect_fields(&doc, field_obj, &mut sheet_fields)?;
-        }
+        let widget_pages = Self::widget_page_indices(&doc);
+
+        let sheet_fields: Vec<SheetField> = terminal_fields(&doc, fields_array)
+            .into_iter()
+            .filter_map(|field| Self::sheet_field(&doc, field, &widget_pages))
+            .collect();
 
         debug!(
             total_fields = sheet_fields.len(),
@@ -147,142 +151,122 @@ impl SheetsPdf {
         }
     }
 
-    #[instrument(
-        name = "pdf.collect_fields",
-        skip(doc, field_obj, fields),
-        level = "debug"
-    )]
-    fn collect_fields(
-        doc: &Document,
-        field_obj: &lopdf::Object,
-        fields: &mut Vec<SheetField>,
-    ) -> Result<(), PdfError> {
-        use lopdf::Object;
-
-        let fields_dict = match field_obj {
-            Object::Dictionary(dict) => dict,
-            Object::Reference(id) => {
-                doc.get_object(*id)
-                    .and_then(|obj| obj.as_dict())
-                    .map_err(|e| {
-                        error!(error = ?e, "failed to get field dictionary");
-                        PdfError::ParseError(e.to_string())
-                    })?
-            }
-            other => {
-                debug!(?other, "unexpected object in Fields array; skipping");
-                return Ok(());
-            }
-        };
-
-        debug!("fields dict: {:?}", fields_dict);
-
-        if let Ok(kids) = fields_dict.get(b"Kids").and_then(|o| o.as_array()) {
-            let field_name = fields_dict
-                .get(b"T")
-                .and_then(|obj| obj.as_str())
-                .ok()
-                .and_then(|bytes| str::from_utf8(bytes).ok());
-
-            debug!(
-                field_name = ?field_name,
-                num_kids = kids.len(),
-                "processing parent field with children"
-            );
-
-            for kid in kids {
-                // Best-effort: skip on error, continue with others
-
-                if let Err(e) = Self::collect_fields(doc, kid, fields) {
-                    debug!(error = ?e, "failed to collect child field; skipping");
+    /// Maps each widget annotation to the zero-based index of the page listing it in `/Annots`.
+    fn widget_page_indices(doc: &Document) -> HashMap<ObjectId, u32> {
+        let mut widget_pages = HashMap::new();
+        for (page_index, page_id) in doc.get_pages().into_values().enumerate() {
+            let annots = doc
+                .get_dictionary(page_id)
+                .and_then(|page| page.get_deref(b"Annots", doc))
+                .and_then(Object::as_array);
+            if let Ok(annots) = annots {
+                for annot in annots {
+                    if let Ok(annot_id) = annot.as_reference() {
+                        widget_pages.insert(annot_id, page_index as u32);
+                    }
                 }
             }
-            return Ok(());
         }
+        widget_pages
+    }
 
-        let field_type_bytes = fields_dict
-            .get(b"FT")
-            .and_then(|obj| obj.as_name())
-            .map_err(|e| {
-                debug!(error = ?e, "failed to get field type");
-                PdfError::ParseError(e.to_string())
-            })?;
-
-        let field_type = std::str::from_utf8(field_type_bytes).map_err(|e| {
-            error!(error = ?e, "field type is not valid UTF-8");
-            PdfError::ParseError(e.to_string())
-        })?;
-
-        let supports_calculation = match field_type {
-            "Tx" | "Ch" => true,
-            "Btn" => {
-                // Include radio buttons but exclude push buttons
-                // Ff flags: bit 16 (65536) = Pushbutton, bit 15 (32768) = Radio
-                if let Ok(flags) = fields_dict.get(b"Ff").and_then(|obj| obj.as_i64()) {
-                    let is_pushbutton = (flags & 65536) != 0;
-                    let is_radio = (flags & 32768) != 0;
-                    let supported = is_radio && !is_pushbutton;
-                    debug!(
-                        field_type = "Btn",
-                        flags = flags,
-                        is_pushbutton = is_pushbutton,
-                        is_radio = is_radio,
-                        supported = supported,
-                        "button field with flags"
-                    );
-                    supported
-                } else {
-                    // No flags mean it's a checkbox, include it
-                    debug!(
-                        field_type = "Btn",
-                        flags = "none",
-                        supported = true,
-                        "button field without flags (checkbox)"
-                    );
-                    true
-                }
+    /// Describes a terminal field, or returns `None` for fields that cannot take part in
+    /// calculations: push buttons, signatures and fields without a widget annotation.
+    ///
+    /// Checkboxes are listed whatever their field flags. Before field kinds were reported, only
+    /// checkboxes without `/Ff` were, so read-only or required checkboxes are listed since.
+    fn sheet_field(
+        doc: &Document,
+        field: TerminalField,
+        widget_pages: &HashMap<ObjectId, u32>,
+    ) -> Option<SheetField> {
+        // Ff flags: bit 16 (65536) = Pushbutton, bit 15 (32768) = Radio
+        let kind = match field.field_type {
+            Some(b"Tx") => SheetFieldKind::Text,
+            Some(b"Ch") => SheetFieldKind::Choice,
+            Some(b"Btn") if field.flags & 65536 != 0 => {
+                debug!(field_name = %field.name, "skipping field: push button");
+                return None;
+            }
+            Some(b"Btn") if field.flags & 32768 != 0 => SheetFieldKind::Radio,
+            Some(b"Btn") => SheetFieldKind::Checkbox,
+            field_type => {
+                debug!(field_name = %field.name, field_type = ?field_type.map(String::from_utf8_lossy), "skipping field: does not support calculation");
+                return None;
             }
-            _ => false,
         };
 
-        // This heuristic is okay for now, though real-world widgets
-        // are sometimes separate annotations with /Parent.
-        let has_widget =
-            fields_dict.has(b"Subtype") || fields_dict.has(b"Rect") || fields_dict.has(b"AP");
+        let Some(&(widget_id, widget)) = field.widgets.first() else {
+            debug!(field_name = %field.name, "skipping field: no widget annotation");
+            return None;
+        };
 
-        debug!(
-            field_type = ?field_type,
-            has_widget = ?has_widget,
-            supports_calculation = ?supports_calculation,
-            "check is supported field"
-        );
+        let fields_dict = field.dict;
+        let flags = field.flags;
+        let mut sheet_field = SheetField::new(field.name, kind);
 
-        if !supports_calculation {
-            debug!(field_type = ?field_type, "skipping field: does not support calculation");
-            return Ok(());
-        }
+        // Ff flags: bit 1 = ReadOnly, bit 2 = Required, bit 13 = Multiline (Tx), bit 18 = Combo (Ch)
+        sheet_field.flags = SheetFieldFlags {
+            read_only: (flags & 1) != 0,
+            required: (flags & 2) != 0,
+            multiline: kind == SheetFieldKind::Text && (flags & 4096) != 0,
+            combo: kind == SheetFieldKind::Choice && (flags & 131072) != 0,
+        };
 
-        if !has_widget {
-            debug!(field_type = ?field_type, "skipping field: no widget annotation");
-            return Ok(());
+        sheet_field.value = fields_dict
+            .get_deref(b"V", doc)
+            .ok()
+            .and_then(decode_field_value);
+        sheet_field.default_value = fields_dict
+            .get_deref(b"DV", doc)
+            .ok()
+            .and_then(decode_field_value);
+
+        if let Ok(options) = fields_dict
+            .get_deref(b"Opt", doc)
+            .and_then(Object::as_array)
+        {
+            // Each option is either a text string or an [export value, display text] pair
+            sheet_field.options = options
+                .iter()
+                .filter_map(|option| match option {
+                    Object::Array(pair) => pair.first().and_then(decode_field_value),
+                    other => decode_field_value(other),
+                })
+                .collect();
         }
 
-        let field_name_bytes = fields_dict
-            .get(b"T")
-            .and_then(|obj| obj.as_str())
-            .map_err(|e| {
-                error!(error = ?e, "failed to get field name");
-                PdfError::ParseError(e.to_string())
-            })?;
-
-        let field_name = str::from_utf8(field_name_bytes).map_err(|e| {
-            error!(error = ?e, "field name is not valid UTF-8");
-            PdfError::ParseError(e.to_string())
-        })?;
-
-        debug!(field_name = ?field_name, field_type = ?field_type, "adding field to collection");
-        fields.push(SheetField::new(field_name));
-
-        Ok(())
+        sheet_field.max_length = fields_dict
+            .get(b"MaxLen")
+            .and_then(Object::as_i64)
+            .ok()
+            .and_then(|max_length| u32::try_from(max_length).ok());
+
+        sheet_field.page_index = widget_id
+            .and_then(|id| widget_pages.get(&id).copied())
+            .or_else(|| {
+                let page_id = widget.get(b"P").and_then(Object::as_reference).ok()?;
+                doc.get_pages()
+                    .into_values()
+                    .position(|id| id == page_id)
+                    .map(|index| index as u32)
+            });
+
+        sheet_field.rect = widget
+            .get_deref(b"Rect", doc)
+            .and_then(Object::as_array)
+            .ok()
+            .and_then(|rect| {
+                let coords: Vec<f32> = rect.iter().filter_map(|c| c.as_float().ok()).collect();
+                coords.try_into().ok()
+            });
+
+        sheet_field.has_calculation = fields_dict
+            .get_deref(b"AA", doc)
+            .and_then(Object::as_dict)
+            .is_ok_and(|aa| aa.has(b"C"));
+
+        debug!(field_name = ?sheet_field.name, kind = ?kind, "adding field to collection");
+        Some(sheet_field)
     }
 }
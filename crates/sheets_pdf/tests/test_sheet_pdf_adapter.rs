@@ -2,12 +2,12 @@ mod test_utils;
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::TestContext;
+    use crate::test_utils::{TestContext, field_metadata_sheet, kid_widgets_sheet};
     use pretty_assertions::assert_eq;
     use rstest::{fixture, rstest};
     use sheets_core::error::PdfError;
     use sheets_core::ports::driven::SheetPdfPort;
-    use sheets_core::sheet::{Sheet, SheetField};
+    use sheets_core::sheet::{Sheet, SheetField, SheetFieldFlags, SheetFieldKind};
     use sheets_pdf::adapter::SheetsPdf;
     use std::fs;
     use std::path::PathBuf;
@@ -89,21 +89,118 @@ mod tests {
         let here = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         let sheet_path = here.join("tests/fixtures/list_fields_test.pdf");
         let sheet = Sheet::new(sheet_path, Some("list_fields_test.pdf".to_string()));
-        let mut expected: Vec<SheetField> = vec![
-            SheetField::new("Text Field"),
-            SheetField::new("Check Box"),
-            SheetField::new("Radio Button"),
-            SheetField::new("Combo Box"),
-            SheetField::new("List Box"),
+        let mut expected = vec![
+            ("Text Field".to_string(), SheetFieldKind::Text),
+            ("Check Box".to_string(), SheetFieldKind::Checkbox),
+            ("Radio Button".to_string(), SheetFieldKind::Radio),
+            ("Combo Box".to_string(), SheetFieldKind::Choice),
+            ("List Box".to_string(), SheetFieldKind::Choice),
         ];
-        expected.sort_by_key(|field| field.name.clone());
+        expected.sort_by_key(|(name, _)| name.clone());
 
         let actual = adapter.list_form_fields(&sheet).await;
 
         assert!(actual.is_ok());
-        let mut fields = actual.unwrap();
+        let fields = actual.unwrap();
         assert_eq!(fields.len(), 5);
-        fields.sort_by_key(|field| field.name.clone());
-        assert_eq!(fields, expected);
+        let mut actual: Vec<_> = fields.into_iter().map(|f| (f.name, f.kind)).collect();
+        actual.sort_by_key(|(name, _)| name.clone());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_form_fields_returns_fully_qualified_names(_ctx: TestContext) {
+        let adapter = SheetsPdf;
+        let here = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sheet_path = here.join("tests/fixtures/nested_fields.pdf");
+        let sheet = Sheet::new(sheet_path, None);
+
+        let actual = adapter.list_form_fields(&sheet).await;
+
+        let fields = actual.unwrap();
+        let mut names: Vec<_> = fields.into_iter().map(|field| field.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["Name", "Saves.Acrobatics", "Skills.Acrobatics"]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_form_fields_resolves_inherited_type_and_kid_widgets(_ctx: TestContext) {
+        let adapter = SheetsPdf;
+        let sheet_file = kid_widgets_sheet();
+        let sheet = Sheet::new(sheet_file.path().to_path_buf(), None);
+
+        let actual = adapter.list_form_fields(&sheet).await.unwrap();
+
+        let mut hp = SheetField::new("Stats.HP", SheetFieldKind::Text);
+        hp.page_index = Some(0);
+        hp.rect = Some([10.0, 20.0, 110.0, 40.0]);
+
+        let mut inspiration = SheetField::new("Feats.Inspiration", SheetFieldKind::Checkbox);
+        inspiration.flags = SheetFieldFlags {
+            read_only: true,
+            ..Default::default()
+        };
+        inspiration.page_index = Some(0);
+        inspiration.rect = Some([10.0, 50.0, 110.0, 70.0]);
+
+        assert_eq!(actual, vec![hp, inspiration]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_dnd5e_form_fields_includes_checkboxes_whatever_their_flags(
+        _ctx: TestContext,
+    ) {
+        let adapter = SheetsPdf;
+        let here = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sheet_path = here.join("tests/fixtures/DnD_5E_CharacterSheet_FormFillable.pdf");
+        let sheet = Sheet::new(sheet_path, None);
+
+        let fields = adapter.list_form_fields(&sheet).await.unwrap();
+
+        let checkboxes = fields
+            .iter()
+            .filter(|field| field.kind == SheetFieldKind::Checkbox)
+            .count();
+        assert_eq!(fields.len(), 332);
+        assert_eq!(checkboxes, 122);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_form_fields_returns_field_metadata(_ctx: TestContext) {
+        let adapter = SheetsPdf;
+        let sheet_file = field_metadata_sheet();
+        let sheet = Sheet::new(sheet_file.path().to_path_buf(), None);
+
+        let actual = adapter.list_form_fields(&sheet).await.unwrap();
+
+        let mut name = SheetField::new("Name", SheetFieldKind::Text);
+        name.flags = SheetFieldFlags {
+            required: true,
+            multiline: true,
+            ..Default::default()
+        };
+        name.value = Some("Aria".to_string());
+        name.default_value = Some("Nobody".to_string());
+        name.max_length = Some(20);
+        name.page_index = Some(0);
+        name.rect = Some([10.0, 20.0, 110.0, 40.0]);
+        name.has_calculation = true;
+
+        let mut class = SheetField::new("Class", SheetFieldKind::Choice);
+        class.flags = SheetFieldFlags {
+            read_only: true,
+            combo: true,
+            ..Default::default()
+        };
+        class.value = Some("wiz".to_string());
+        class.options = vec!["wiz".to_string(), "Fighter".to_string()];
+        class.page_index = Some(0);
+        class.rect = Some([10.0, 50.0, 110.0, 70.0]);
+
+        assert_eq!(actual, vec![name, class]);
     }
 }
@@ -1,4 +1,6 @@
 use common_telemetry as telemetry;
+use lopdf::{Document, Object, dictionary};
+use tempfile::NamedTempFile;
 
 pub struct TestContext;
 
@@ -9,3 +11,144 @@ impl TestContext {
         Self {}
     }
 }
+
+/// Builds a form whose `Stats.HP` text field and read-only `Feats.Inspiration` checkbox inherit
+/// their field type and flags from their parent, and are drawn by separate, unnamed widget kids.
+pub fn kid_widgets_sheet() -> NamedTempFile {
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let page_id = doc.new_object_id();
+
+    let mut annots = vec![];
+    let mut fields = vec![];
+    for (group, name, field_type, flags, y) in [
+        ("Stats", "HP", "Tx", 0, 20),
+        ("Feats", "Inspiration", "Btn", 1, 50),
+    ] {
+        let group_id = doc.new_object_id();
+        let field_id = doc.new_object_id();
+        let widget_id = doc.add_object(dictionary! {
+            "Subtype" => "Widget",
+            "Parent" => field_id,
+            "Rect" => vec![10.into(), y.into(), 110.into(), (y + 20).into()],
+            "P" => page_id,
+        });
+        doc.objects.insert(
+            field_id,
+            Object::Dictionary(dictionary! {
+                "T" => Object::string_literal(name),
+                "Parent" => group_id,
+                "Kids" => vec![widget_id.into()],
+            }),
+        );
+        doc.objects.insert(
+            group_id,
+            Object::Dictionary(dictionary! {
+                "T" => Object::string_literal(group),
+                "FT" => field_type,
+                "Ff" => flags,
+                "Kids" => vec![field_id.into()],
+            }),
+        );
+        annots.push(Object::Reference(widget_id));
+        fields.push(Object::Reference(group_id));
+    }
+
+    doc.objects.insert(
+        page_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            "Annots" => annots,
+        }),
+    );
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => vec![page_id.into()],
+            "Count" => 1,
+        }),
+    );
+
+    let fields_id = doc.add_object(fields);
+    let acroform_id = doc.add_object(dictionary! { "Fields" => fields_id });
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+        "AcroForm" => acroform_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+
+    let temp_file = NamedTempFile::with_suffix(".pdf").expect("create temp file");
+    doc.save(temp_file.path()).expect("save kid widgets sheet");
+    temp_file
+}
+
+/// Builds a single-page form whose fields carry values, flags, options and a calculation.
+pub fn field_metadata_sheet() -> NamedTempFile {
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let page_id = doc.new_object_id();
+
+    let js_id = doc.add_object(dictionary! {
+        "S" => "JavaScript",
+        "JS" => Object::string_literal("event.value = 1;"),
+    });
+    let name_id = doc.add_object(dictionary! {
+        "T" => Object::string_literal("Name"),
+        "FT" => "Tx",
+        "Ff" => 2 | 4096,
+        "V" => Object::string_literal("Aria"),
+        "DV" => Object::string_literal("Nobody"),
+        "MaxLen" => 20,
+        "Subtype" => "Widget",
+        "Rect" => vec![10.into(), 20.into(), 110.into(), 40.into()],
+        "AA" => dictionary! { "C" => js_id },
+    });
+    let class_id = doc.add_object(dictionary! {
+        "T" => Object::string_literal("Class"),
+        "FT" => "Ch",
+        "Ff" => 131072 | 1,
+        "V" => Object::string_literal("wiz"),
+        "Opt" => vec![
+            Object::Array(vec![Object::string_literal("wiz"), Object::string_literal("Wizard")]),
+            Object::string_literal("Fighter"),
+        ],
+        "Subtype" => "Widget",
+        "Rect" => vec![10.into(), 50.into(), 110.into(), 70.into()],
+    });
+
+    doc.objects.insert(
+        page_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            "Annots" => vec![name_id.into(), class_id.into()],
+        }),
+    );
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => vec![page_id.into()],
+            "Count" => 1,
+        }),
+    );
+
+    let fields_id = doc.add_object(vec![name_id.into(), class_id.into()]);
+    let acroform_id = doc.add_object(dictionary! { "Fields" => fields_id });
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+        "AcroForm" => acroform_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+
+    let temp_file = NamedTempFile::with_suffix(".pdf").expect("create temp file");
+    doc.save(temp_file.path())
+        .expect("save field metadata sheet");
+    temp_file
+}
@@ -1,15 +1,21 @@
 import z from "zod";
 
+export type FieldKindDto = z.infer<typeof FieldKindDto>;
+export const FieldKindDto = z.enum(["numericText", "text", "checkbox", "choice", "any"]);
+
 export type FieldRoleMetadataDto = z.infer<typeof FieldRoleMetadataDto>;
 export const FieldRoleMetadataDto = z.object({
   isTarget: z.boolean(),
   key: z.string(),
+  kind: FieldKindDto,
+  multiple: z.boolean(),
   required: z.boolean(),
 });
 
 export type ActionTypeMetadataDto = z.infer<typeof ActionTypeMetadataDto>;
 export const ActionTypeMetadataDto = z.object({
   actionLabel: z.string(),
+  freeForm: z.boolean(),
   id: z.string(),
   roles: z.array(FieldRoleMetadataDto),
 });
@@ -22,6 +28,19 @@ export const AttachedActionResponse = z.object({
   targetField: z.string(),
 });
 
+export type FieldDependencyDto = z.infer<typeof FieldDependencyDto>;
+export const FieldDependencyDto = z.object({
+  actionId: z.string(),
+  sourceField: z.string(),
+  targetField: z.string(),
+});
+
+export type DependencyGraphResponse = z.infer<typeof DependencyGraphResponse>;
+export const DependencyGraphResponse = z.object({
+  dependencies: z.array(FieldDependencyDto),
+  fields: z.array(z.string()),
+});
+
 export type CalculationActionSchema = z.infer<typeof CalculationActionSchema>;
 export const CalculationActionSchema = z.union([
   z.object({
@@ -48,8 +67,165 @@ export const CalculationActionSchema = z.union([
       skillModifierFieldName: z.string(),
     }),
   }),
+  z.object({
+    PassiveScore: z.object({
+      advantageFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      bonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      disadvantageFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      passiveScoreFieldName: z.string(),
+      skillModifierFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    ProficiencyBonus: z.object({
+      levelFieldName: z.string(),
+      multiclassLevelFieldNames: z.array(z.string()).optional(),
+      proficiencyBonusFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    Initiative: z.object({
+      dexModifierFieldName: z.string(),
+      initiativeFieldName: z.string(),
+      jackOfAllTradesFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      miscBonusFieldNames: z.array(z.string()).optional(),
+      proficiencyBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+    }),
+  }),
+  z.object({
+    ArmorClass: z.object({
+      armorClassFieldName: z.string(),
+      baseArmorFieldName: z.string(),
+      dexModifierFieldName: z.string(),
+      maxDexBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      miscBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      shieldFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+    }),
+  }),
+  z.object({
+    SpellSaveDc: z.object({
+      charismaModifierFieldName: z.string(),
+      intelligenceModifierFieldName: z.string(),
+      miscBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      proficiencyBonusFieldName: z.string(),
+      spellSaveDcFieldName: z.string(),
+      spellcastingAbilityFieldName: z.string(),
+      wisdomModifierFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    SpellAttackBonus: z.object({
+      charismaModifierFieldName: z.string(),
+      intelligenceModifierFieldName: z.string(),
+      miscBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      proficiencyBonusFieldName: z.string(),
+      spellAttackBonusFieldName: z.string(),
+      spellcastingAbilityFieldName: z.string(),
+      wisdomModifierFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    WeaponAttackBonus: z.object({
+      attackBonusFieldName: z.string(),
+      dexterityModifierFieldName: z.string(),
+      finesseFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      magicBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      proficiencyBonusFieldName: z.string(),
+      proficiencyFieldName: z.string(),
+      rangedFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      strengthModifierFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    WeaponDamageBonus: z.object({
+      damageBonusFieldName: z.string(),
+      dexterityModifierFieldName: z.string(),
+      finesseFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      magicBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      rangedFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      strengthModifierFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    CarryingCapacity: z.object({
+      carryingCapacityFieldName: z.string(),
+      sizeFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      strengthScoreFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    EncumbranceStatus: z.object({
+      encumbranceStatusFieldName: z.string(),
+      sizeFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      strengthScoreFieldName: z.string(),
+      weightFieldNames: z.array(z.string()),
+    }),
+  }),
+  z.object({
+    ProficiencyRankModifier: z.object({
+      abilityModifierFieldName: z.string(),
+      itemBonusFieldName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      levelFieldName: z.string(),
+      modifierFieldName: z.string(),
+      proficiencyRankFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    SumFields: z.object({
+      sourceFieldNames: z.array(z.string()).optional(),
+      sourceFieldPattern: z.union([z.string(), z.null(), z.undefined()]).optional(),
+      sumFieldName: z.string(),
+    }),
+  }),
+  z.object({
+    Formula: z.object({
+      expression: z.string(),
+      targetFieldName: z.string(),
+    }),
+  }),
 ]);
 
+export type PreviewRequest = z.infer<typeof PreviewRequest>;
+export const PreviewRequest = z.object({
+  values: z.union([z.record(z.string()), z.null(), z.undefined()]).optional(),
+});
+
+export type PreviewValueDto = z.infer<typeof PreviewValueDto>;
+export const PreviewValueDto = z.union([z.number(), z.string()]);
+
+export type ActionSuggestionDto = z.infer<typeof ActionSuggestionDto>;
+export const ActionSuggestionDto = z.object({
+  action: CalculationActionSchema,
+  actionType: z.string(),
+  confidence: z.number(),
+  targetField: z.string(),
+});
+
+export type MappingSuggestionsResponse = z.infer<typeof MappingSuggestionsResponse>;
+export const MappingSuggestionsResponse = z.object({
+  sheetName: z.union([z.string(), z.null(), z.undefined()]).optional(),
+  suggestions: z.array(ActionSuggestionDto),
+});
+
+export type MappingTemplateResponse = z.infer<typeof MappingTemplateResponse>;
+export const MappingTemplateResponse = z.object({
+  actions: z.array(z.unknown()),
+  fingerprint: z.string(),
+  id: z.string(),
+  name: z.string(),
+});
+
+export type CreateMappingTemplateRequest = z.infer<typeof CreateMappingTemplateRequest>;
+export const CreateMappingTemplateRequest = z.object({
+  name: z.string(),
+});
+
+export type GameSystemDto = z.infer<typeof GameSystemDto>;
+export const GameSystemDto = z.object({
+  id: z.string(),
+  name: z.string(),
+});
+
 export type DownloadSheetResponse = z.infer<typeof DownloadSheetResponse>;
 export const DownloadSheetResponse = z.object({
   filename: z.string(),
@@ -62,9 +238,29 @@ export const HealthResponse = z.object({
   version: z.string(),
 });
 
+export type SheetFieldFlagsDto = z.infer<typeof SheetFieldFlagsDto>;
+export const SheetFieldFlagsDto = z.object({
+  combo: z.boolean(),
+  multiline: z.boolean(),
+  readOnly: z.boolean(),
+  required: z.boolean(),
+});
+
+export type SheetFieldKindDto = z.infer<typeof SheetFieldKindDto>;
+export const SheetFieldKindDto = z.enum(["text", "choice", "checkbox", "radio"]);
+
 export type SheetFieldDto = z.infer<typeof SheetFieldDto>;
 export const SheetFieldDto = z.object({
+  defaultValue: z.union([z.string(), z.null(), z.undefined()]).optional(),
+  flags: SheetFieldFlagsDto,
+  hasCalculation: z.boolean(),
+  kind: SheetFieldKindDto,
+  maxLength: z.union([z.number(), z.null(), z.undefined()]).optional(),
   name: z.string(),
+  options: z.array(z.string()),
+  pageIndex: z.union([z.number(), z.null(), z.undefined()]).optional(),
+  rect: z.union([z.array(z.number()), z.null(), z.undefined()]).optional(),
+  value: z.union([z.string(), z.null(), z.undefined()]).optional(),
 });
 
 export type ListSheetFieldsResponse = z.infer<typeof ListSheetFieldsResponse>;
@@ -72,15 +268,38 @@ export const ListSheetFieldsResponse = z.object({
   fields: z.array(SheetFieldDto),
 });
 
+export type InvalidParam = z.infer<typeof InvalidParam>;
+export const InvalidParam = z.object({
+  name: z.string(),
+  reason: z.string(),
+});
+
 export type ProblemDetails = z.infer<typeof ProblemDetails>;
 export const ProblemDetails = z.object({
   detail: z.union([z.string(), z.null(), z.undefined()]).optional(),
   instance: z.union([z.string(), z.null(), z.undefined()]).optional(),
+  invalidParams: z.union([z.array(InvalidParam), z.null(), z.undefined()]).optional(),
   status: z.number(),
   title: z.string(),
   type: z.string(),
 });
 
+export type AttachActionResultDto = z.infer<typeof AttachActionResultDto>;
+export const AttachActionResultDto = z.object({
+  actionType: z.string(),
+  error: z.union([ProblemDetails, z.null(), z.undefined()]).optional(),
+  id: z.union([z.string(), z.null(), z.undefined()]).optional(),
+  targetField: z.string(),
+});
+
+export type PreviewResultDto = z.infer<typeof PreviewResultDto>;
+export const PreviewResultDto = z.object({
+  actionType: z.string(),
+  error: z.union([ProblemDetails, z.null(), z.undefined()]).optional(),
+  targetField: z.string(),
+  value: z.union([PreviewValueDto, z.null(), z.undefined()]).optional(),
+});
+
 export type UploadSheetRequest = z.infer<typeof UploadSheetRequest>;
 export const UploadSheetRequest = z.object({
   sheet: z.string(),
@@ -111,6 +330,18 @@ export const get_ListAttachedActions = {
   response: z.array(AttachedActionResponse),
 };
 
+export type get_GetActionDependencyGraph = typeof get_GetActionDependencyGraph;
+export const get_GetActionDependencyGraph = {
+  method: z.literal("GET"),
+  path: z.literal("/dnd5e/{sheet_id}/actions/graph"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+  }),
+  response: DependencyGraphResponse,
+};
+
 export type put_AttachCalculationAction = typeof put_AttachCalculationAction;
 export const put_AttachCalculationAction = {
   method: z.literal("PUT"),
@@ -124,6 +355,336 @@ export const put_AttachCalculationAction = {
   response: z.unknown(),
 };
 
+export type post_AttachCalculationActions = typeof post_AttachCalculationActions;
+export const post_AttachCalculationActions = {
+  method: z.literal("POST"),
+  path: z.literal("/dnd5e/{sheet_id}/actions/batch"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+    body: z.array(CalculationActionSchema),
+  }),
+  response: z.array(AttachActionResultDto),
+};
+
+export type put_UpdateCalculationAction = typeof put_UpdateCalculationAction;
+export const put_UpdateCalculationAction = {
+  method: z.literal("PUT"),
+  path: z.literal("/dnd5e/{sheet_id}/actions/{action_id}"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+      action_id: z.string(),
+    }),
+    body: CalculationActionSchema,
+  }),
+  response: z.unknown(),
+};
+
+export type delete_DetachCalculationAction = typeof delete_DetachCalculationAction;
+export const delete_DetachCalculationAction = {
+  method: z.literal("DELETE"),
+  path: z.literal("/dnd5e/{sheet_id}/actions/{action_id}"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+      action_id: z.string(),
+    }),
+  }),
+  response: z.unknown(),
+};
+
+export type get_ExportComputedSheet = typeof get_ExportComputedSheet;
+export const get_ExportComputedSheet = {
+  method: z.literal("GET"),
+  path: z.literal("/dnd5e/{sheet_id}/export"),
+  parameters: z.object({
+    query: z.object({
+      keepScripts: z.union([z.boolean(), z.undefined()]).optional(),
+    }),
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.unknown(),
+};
+
+export type post_PreviewCalculations = typeof post_PreviewCalculations;
+export const post_PreviewCalculations = {
+  method: z.literal("POST"),
+  path: z.literal("/dnd5e/{sheet_id}/preview"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+    body: PreviewRequest,
+  }),
+  response: z.array(PreviewResultDto),
+};
+
+export type post_RegenerateSheet = typeof post_RegenerateSheet;
+export const post_RegenerateSheet = {
+  method: z.literal("POST"),
+  path: z.literal("/dnd5e/{sheet_id}/regenerate"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.array(AttachActionResultDto),
+};
+
+export type get_SuggestCalculationActions = typeof get_SuggestCalculationActions;
+export const get_SuggestCalculationActions = {
+  method: z.literal("GET"),
+  path: z.literal("/dnd5e/{sheet_id}/suggestions"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+  }),
+  response: MappingSuggestionsResponse,
+};
+
+export type get_ListMappingTemplates = typeof get_ListMappingTemplates;
+export const get_ListMappingTemplates = {
+  method: z.literal("GET"),
+  path: z.literal("/dnd5e/{sheet_id}/templates"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.array(MappingTemplateResponse),
+};
+
+export type post_CreateMappingTemplate = typeof post_CreateMappingTemplate;
+export const post_CreateMappingTemplate = {
+  method: z.literal("POST"),
+  path: z.literal("/dnd5e/{sheet_id}/templates"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+    }),
+    body: CreateMappingTemplateRequest,
+  }),
+  response: MappingTemplateResponse,
+};
+
+export type post_ApplyMappingTemplate = typeof post_ApplyMappingTemplate;
+export const post_ApplyMappingTemplate = {
+  method: z.literal("POST"),
+  path: z.literal("/dnd5e/{sheet_id}/templates/{template_id}/apply"),
+  parameters: z.object({
+    path: z.object({
+      sheet_id: z.string(),
+      template_id: z.string(),
+    }),
+  }),
+  response: z.array(AttachActionResultDto),
+};
+
+export type get_ListGameSystems = typeof get_ListGameSystems;
+export const get_ListGameSystems = {
+  method: z.literal("GET"),
+  path: z.literal("/systems"),
+  parameters: z.never(),
+  response: z.array(GameSystemDto),
+};
+
+export type get_ListSystemActionTypes = typeof get_ListSystemActionTypes;
+export const get_ListSystemActionTypes = {
+  method: z.literal("GET"),
+  path: z.literal("/systems/{system}/action-types"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+    }),
+  }),
+  response: z.array(ActionTypeMetadataDto),
+};
+
+export type get_ListSystemAttachedActions = typeof get_ListSystemAttachedActions;
+export const get_ListSystemAttachedActions = {
+  method: z.literal("GET"),
+  path: z.literal("/systems/{system}/{sheet_id}/actions"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.array(AttachedActionResponse),
+};
+
+export type get_GetSystemActionDependencyGraph = typeof get_GetSystemActionDependencyGraph;
+export const get_GetSystemActionDependencyGraph = {
+  method: z.literal("GET"),
+  path: z.literal("/systems/{system}/{sheet_id}/actions/graph"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+  }),
+  response: DependencyGraphResponse,
+};
+
+export type put_AttachSystemCalculationAction = typeof put_AttachSystemCalculationAction;
+export const put_AttachSystemCalculationAction = {
+  method: z.literal("PUT"),
+  path: z.literal("/systems/{system}/{sheet_id}/actions"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+    body: CalculationActionSchema,
+  }),
+  response: z.unknown(),
+};
+
+export type post_AttachSystemCalculationActions = typeof post_AttachSystemCalculationActions;
+export const post_AttachSystemCalculationActions = {
+  method: z.literal("POST"),
+  path: z.literal("/systems/{system}/{sheet_id}/actions/batch"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+    body: z.array(CalculationActionSchema),
+  }),
+  response: z.array(AttachActionResultDto),
+};
+
+export type put_UpdateSystemCalculationAction = typeof put_UpdateSystemCalculationAction;
+export const put_UpdateSystemCalculationAction = {
+  method: z.literal("PUT"),
+  path: z.literal("/systems/{system}/{sheet_id}/actions/{action_id}"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+      action_id: z.string(),
+    }),
+    body: CalculationActionSchema,
+  }),
+  response: z.unknown(),
+};
+
+export type delete_DetachSystemCalculationAction = typeof delete_DetachSystemCalculationAction;
+export const delete_DetachSystemCalculationAction = {
+  method: z.literal("DELETE"),
+  path: z.literal("/systems/{system}/{sheet_id}/actions/{action_id}"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+      action_id: z.string(),
+    }),
+  }),
+  response: z.unknown(),
+};
+
+export type get_ExportSystemComputedSheet = typeof get_ExportSystemComputedSheet;
+export const get_ExportSystemComputedSheet = {
+  method: z.literal("GET"),
+  path: z.literal("/systems/{system}/{sheet_id}/export"),
+  parameters: z.object({
+    query: z.object({
+      keepScripts: z.union([z.boolean(), z.undefined()]).optional(),
+    }),
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.unknown(),
+};
+
+export type post_PreviewSystemCalculations = typeof post_PreviewSystemCalculations;
+export const post_PreviewSystemCalculations = {
+  method: z.literal("POST"),
+  path: z.literal("/systems/{system}/{sheet_id}/preview"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+    body: PreviewRequest,
+  }),
+  response: z.array(PreviewResultDto),
+};
+
+export type post_RegenerateSystemSheet = typeof post_RegenerateSystemSheet;
+export const post_RegenerateSystemSheet = {
+  method: z.literal("POST"),
+  path: z.literal("/systems/{system}/{sheet_id}/regenerate"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.array(AttachActionResultDto),
+};
+
+export type get_SuggestSystemCalculationActions = typeof get_SuggestSystemCalculationActions;
+export const get_SuggestSystemCalculationActions = {
+  method: z.literal("GET"),
+  path: z.literal("/systems/{system}/{sheet_id}/suggestions"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+  }),
+  response: MappingSuggestionsResponse,
+};
+
+export type get_ListSystemMappingTemplates = typeof get_ListSystemMappingTemplates;
+export const get_ListSystemMappingTemplates = {
+  method: z.literal("GET"),
+  path: z.literal("/systems/{system}/{sheet_id}/templates"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+  }),
+  response: z.array(MappingTemplateResponse),
+};
+
+export type post_CreateSystemMappingTemplate = typeof post_CreateSystemMappingTemplate;
+export const post_CreateSystemMappingTemplate = {
+  method: z.literal("POST"),
+  path: z.literal("/systems/{system}/{sheet_id}/templates"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+    }),
+    body: CreateMappingTemplateRequest,
+  }),
+  response: MappingTemplateResponse,
+};
+
+export type post_ApplySystemMappingTemplate = typeof post_ApplySystemMappingTemplate;
+export const post_ApplySystemMappingTemplate = {
+  method: z.literal("POST"),
+  path: z.literal("/systems/{system}/{sheet_id}/templates/{template_id}/apply"),
+  parameters: z.object({
+    path: z.object({
+      system: z.string(),
+      sheet_id: z.string(),
+      template_id: z.string(),
+    }),
+  }),
+  response: z.array(AttachActionResultDto),
+};
+
 export type get_Health_check = typeof get_Health_check;
 export const get_Health_check = {
   method: z.literal("GET"),
@@ -171,15 +732,43 @@ export const EndpointByMethod = {
   get: {
     "/dnd5e/action-types": get_ListActionTypes,
     "/dnd5e/{sheet_id}/actions": get_ListAttachedActions,
+    "/dnd5e/{sheet_id}/actions/graph": get_GetActionDependencyGraph,
+    "/dnd5e/{sheet_id}/export": get_ExportComputedSheet,
+    "/dnd5e/{sheet_id}/suggestions": get_SuggestCalculationActions,
+    "/dnd5e/{sheet_id}/templates": get_ListMappingTemplates,
     "/health": get_Health_check,
     "/sheets/{sheet_id}": get_DownloadSheet,
     "/sheets/{sheet_id}/fields": get_GetSheetFormFields,
+    "/systems": get_ListGameSystems,
+    "/systems/{system}/action-types": get_ListSystemActionTypes,
+    "/systems/{system}/{sheet_id}/actions": get_ListSystemAttachedActions,
+    "/systems/{system}/{sheet_id}/actions/graph": get_GetSystemActionDependencyGraph,
+    "/systems/{system}/{sheet_id}/export": get_ExportSystemComputedSheet,
+    "/systems/{system}/{sheet_id}/suggestions": get_SuggestSystemCalculationActions,
+    "/systems/{system}/{sheet_id}/templates": get_ListSystemMappingTemplates,
   },
   put: {
     "/dnd5e/{sheet_id}/actions": put_AttachCalculationAction,
+    "/dnd5e/{sheet_id}/actions/{action_id}": put_UpdateCalculationAction,
+    "/systems/{system}/{sheet_id}/actions": put_AttachSystemCalculationAction,
+    "/systems/{system}/{sheet_id}/actions/{action_id}": put_UpdateSystemCalculationAction,
+  },
+  delete: {
+    "/dnd5e/{sheet_id}/actions/{action_id}": delete_DetachCalculationAction,
+    "/systems/{system}/{sheet_id}/actions/{action_id}": delete_DetachSystemCalculationAction,
   },
   post: {
+    "/dnd5e/{sheet_id}/actions/batch": post_AttachCalculationActions,
+    "/dnd5e/{sheet_id}/preview": post_PreviewCalculations,
+    "/dnd5e/{sheet_id}/regenerate": post_RegenerateSheet,
+    "/dnd5e/{sheet_id}/templates": post_CreateMappingTemplate,
+    "/dnd5e/{sheet_id}/templates/{template_id}/apply": post_ApplyMappingTemplate,
     "/sheets": post_UploadSheet,
+    "/systems/{system}/{sheet_id}/actions/batch": post_AttachSystemCalculationActions,
+    "/systems/{system}/{sheet_id}/preview": post_PreviewSystemCalculations,
+    "/systems/{system}/{sheet_id}/regenerate": post_RegenerateSystemSheet,
+    "/systems/{system}/{sheet_id}/templates": post_CreateSystemMappingTemplate,
+    "/systems/{system}/{sheet_id}/templates/{template_id}/apply": post_ApplySystemMappingTemplate,
   },
 };
 export type EndpointByMethod = typeof EndpointByMethod;
@@ -188,6 +777,7 @@ export type EndpointByMethod = typeof EndpointByMethod;
 // <EndpointByMethod.Shorthands>
 export type GetEndpoints = EndpointByMethod["get"];
 export type PutEndpoints = EndpointByMethod["put"];
+export type DeleteEndpoints = EndpointByMethod["delete"];
 export type PostEndpoints = EndpointByMethod["post"];
 export type AllEndpoints = EndpointByMethod[keyof EndpointByMethod];
 // </EndpointByMethod.Shorthands>
@@ -264,6 +854,15 @@ export class ApiClient {
   }
   // </ApiClient.put>
 
+  // <ApiClient.delete>
+  delete<Path extends keyof DeleteEndpoints, TEndpoint extends DeleteEndpoints[Path]>(
+    path: Path,
+    ...params: MaybeOptionalArg<z.infer<TEndpoint["parameters"]>>
+  ): Promise<z.infer<TEndpoint["response"]>> {
+    return this.fetcher("delete", this.baseUrl + path, params[0]) as Promise<z.infer<TEndpoint["response"]>>;
+  }
+  // </ApiClient.delete>
+
   // <ApiClient.post>
   post<Path extends keyof PostEndpoints, TEndpoint extends PostEndpoints[Path]>(
     path: Path,
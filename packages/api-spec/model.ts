@@ -4,11 +4,18 @@
 
 // Re-export types (inferred from Zod schemas)
 export type {
+  ActionSuggestionDto,
   ActionTypeMetadataDto,
   AttachedActionResponse,
+  CreateMappingTemplateRequest,
   DownloadSheetResponse,
+  GameSystemDto,
   HealthResponse,
   ListSheetFieldsResponse,
+  MappingSuggestionsResponse,
+  MappingTemplateResponse,
+  PreviewRequest,
+  PreviewResultDto,
   ProblemDetails,
   SheetFieldDto,
   UploadSheetRequest,
@@ -16,11 +23,18 @@ export type {
 } from "./generated.ts";
 // Re-export Zod schemas for runtime validation
 export {
+  ActionSuggestionDto as ActionSuggestionDtoSchema,
   ActionTypeMetadataDto as ActionTypeMetadataDtoSchema,
   AttachedActionResponse as AttachedActionResponseSchema,
+  CreateMappingTemplateRequest as CreateMappingTemplateRequestSchema,
   DownloadSheetResponse as DownloadSheetResponseSchema,
+  GameSystemDto as GameSystemDtoSchema,
   HealthResponse as HealthResponseSchema,
   ListSheetFieldsResponse as ListSheetFieldsResponseSchema,
+  MappingSuggestionsResponse as MappingSuggestionsResponseSchema,
+  MappingTemplateResponse as MappingTemplateResponseSchema,
+  PreviewRequest as PreviewRequestSchema,
+  PreviewResultDto as PreviewResultDtoSchema,
   ProblemDetails as ProblemDetailsSchema,
   SheetFieldDto as SheetFieldDtoSchema,
   UploadSheetRequest as UploadSheetRequestSchema,